@@ -4,18 +4,23 @@ from matplotlib import pyplot as plt
 def main(filename):
     handle = open(filename, "r")
     txt = handle.read()
-    data = [ln.split(":") for ln in txt.split("\n")][:-1]
+    lines = txt.split("\n")[:-1]
+    # The first line names how the scores are normalized, such as "# Per round".
+    label = "Average score"
+    if lines and lines[0].startswith("#"):
+        label = "Score (" + lines.pop(0)[1:].strip().lower() + ")"
+    data = [ln.split(":") for ln in lines]
     names, means, devs = zip(*data)
     means = list(map(float, means))
     devs = list(map(float, devs))
-    positioning = [i * 0.5 for i in range(10)]
-    plt.bar(positioning, means, width=[.25] * 10)
+    positioning = [i * 0.5 for i in range(len(names))]
+    plt.bar(positioning, means, width=[.25] * len(names))
     plt.errorbar(positioning, means, yerr=devs,
                  fmt=".", color="r", linewidth=2,
                  capsize=3, capthick=2, markersize=1)
     plt.xticks(positioning, names)
     plt.xlabel("Game strategies")
-    plt.ylabel("Average score")
+    plt.ylabel(label)
     plt.legend(loc="best")
     plt.show()
 
@@ -0,0 +1,522 @@
+use crate::gametheory::{number_to_genome, PayoffMatrix, Tournament, GENOME_COUNT};
+use crate::random;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Iterations after which the Markov chain is assumed to have converged.
+const MAX_CHAIN_STEPS: usize = 100_000;
+
+/// Participants whose empirical response profiles lie close together.
+#[derive(Clone)]
+pub struct Cluster {
+    /// Names of the participants in the cluster.
+    pub members: Vec<String>,
+    /// Average cooperation rate per context over the members that encountered it.
+    pub centroid: [Option<f64>; 5],
+    /// Number of members that contributed to each context of the centroid.
+    counts: [u32; 5],
+}
+
+impl Cluster {
+    fn add(&mut self, name: &str, profile: &[Option<f64>; 5]) {
+        self.members.push(name.to_string());
+        for (i, rate) in profile.iter().enumerate() {
+            if let Some(rate) = rate {
+                let mean = self.centroid[i].unwrap_or(0.0);
+                self.counts[i] += 1;
+                self.centroid[i] = Some(mean + (rate - mean) / self.counts[i] as f64);
+            }
+        }
+    }
+}
+
+/// Root-mean-square difference over the contexts both profiles have observed.
+fn profile_distance(a: &[Option<f64>; 5], b: &[Option<f64>; 5]) -> f64 {
+    let diffs: Vec<f64> =
+        a.iter().zip(b).filter_map(|(x, y)| Some((x.as_ref()? - y.as_ref()?).powi(2))).collect();
+    if diffs.is_empty() {
+        return 0.0;
+    }
+    (diffs.iter().sum::<f64>() / diffs.len() as f64).sqrt()
+}
+
+/// Groups participants by behaviour: each joins the first cluster whose centroid is
+/// within `threshold` of its response profile, or starts a new one.
+pub fn cluster_by_behaviour(
+    profiles: &[(String, [Option<f64>; 5])],
+    threshold: f64,
+) -> Vec<Cluster> {
+    let mut clusters: Vec<Cluster> = Vec::new();
+    for (name, profile) in profiles {
+        match clusters.iter_mut().find(|c| profile_distance(&c.centroid, profile) <= threshold) {
+            Some(cluster) => cluster.add(name, profile),
+            None => {
+                let mut cluster = Cluster {
+                    members: Vec::new(),
+                    centroid: [None; 5],
+                    counts: [0; 5],
+                };
+                cluster.add(name, profile);
+                clusters.push(cluster);
+            }
+        }
+    }
+    clusters
+}
+
+/// Payoffs of both players after CC, CD, DC and DD, in that order.
+pub type Payoffs = [(f64, f64); 4];
+
+/// Tabulates a reward function or payoff matrix so that it can be analysed and perturbed.
+pub fn payoffs_of(rules: impl Into<PayoffMatrix>) -> Payoffs {
+    rules.into().outcomes().map(|(n, m)| (n as f64, m as f64))
+}
+
+/// Memory-one strategy given by its probability to cooperate at the start of a matchup and
+/// after CC, CD, DC and DD, own move first, i.e. in the same order as a genome.
+#[derive(Clone, Copy)]
+pub struct MemoryOne(pub [f64; 5]);
+
+impl MemoryOne {
+    /// The deterministic strategy encoded by a genome number.
+    pub fn from_genome(n: u8) -> Self {
+        let genome = number_to_genome(n);
+        MemoryOne(std::array::from_fn(|i| if genome[i] { 1.0 } else { 0.0 }))
+    }
+
+    /// Strategy behaving like an empirical response profile, where contexts that were never
+    /// encountered are filled in as a coin flip.
+    pub fn from_profile(profile: &[Option<f64>; 5]) -> Self {
+        MemoryOne(profile.map(|rate| rate.unwrap_or(0.5)))
+    }
+}
+
+/// Analytic outcome of two memory-one strategies playing each other.
+pub struct PairAnalysis {
+    /// Long-run probability of the states CC, CD, DC and DD, seen from the first strategy.
+    pub stationary: [f64; 4],
+    /// Expected per-round payoff of both strategies in the long run.
+    pub payoffs: (f64, f64),
+    /// Long-run cooperation rate of both strategies.
+    pub cooperation: (f64, f64),
+}
+
+/// Distribution over CC, CD, DC and DD given both players' cooperation probabilities.
+fn state_distribution(p: f64, q: f64) -> [f64; 4] {
+    [p * q, p * (1.0 - q), (1.0 - p) * q, (1.0 - p) * (1.0 - q)]
+}
+
+/// Advances the distribution over states by one round of play.
+fn step(a: &MemoryOne, b: &MemoryOne, dist: &[f64; 4]) -> [f64; 4] {
+    // The second player sees CD as DC and vice versa.
+    const SWAPPED: [usize; 4] = [0, 2, 1, 3];
+    let mut next = [0.0; 4];
+    for s in 0..4 {
+        let moved = state_distribution(a.0[s + 1], b.0[SWAPPED[s] + 1]);
+        for (n, m) in next.iter_mut().zip(moved) {
+            *n += dist[s] * m;
+        }
+    }
+    next
+}
+
+fn first_round(a: &MemoryOne, b: &MemoryOne) -> [f64; 4] {
+    state_distribution(a.0[0], b.0[0])
+}
+
+fn summarise(dist: [f64; 4], payoffs: &Payoffs) -> PairAnalysis {
+    let mut expected = (0.0, 0.0);
+    for (p, (n, m)) in dist.iter().zip(payoffs) {
+        expected.0 += p * n;
+        expected.1 += p * m;
+    }
+    PairAnalysis {
+        stationary: dist,
+        payoffs: expected,
+        cooperation: (dist[0] + dist[1], dist[0] + dist[2]),
+    }
+}
+
+/// Long-run behaviour of two memory-one strategies, starting from their opening moves.
+///
+/// Iterates the lazy chain `(P + I) / 2`, which has the same long-run average as the
+/// original chain but cannot oscillate between deterministic cycles.
+pub fn analyse_pair(a: &MemoryOne, b: &MemoryOne, payoffs: &Payoffs) -> PairAnalysis {
+    let mut dist = first_round(a, b);
+    for _ in 0..MAX_CHAIN_STEPS {
+        let moved = step(a, b, &dist);
+        let next: [f64; 4] = std::array::from_fn(|s| (dist[s] + moved[s]) / 2.0);
+        let change: f64 = next.iter().zip(dist).map(|(x, y)| (x - y).abs()).sum();
+        dist = next;
+        if change < 1e-12 {
+            break;
+        }
+    }
+    summarise(dist, payoffs)
+}
+
+/// Expected average over the first `rounds` rounds, which is what a finite simulation
+/// should approach.
+pub fn analyse_rounds(
+    a: &MemoryOne,
+    b: &MemoryOne,
+    payoffs: &Payoffs,
+    rounds: u32,
+) -> PairAnalysis {
+    let mut dist = first_round(a, b);
+    let mut acc = [0.0; 4];
+    for _ in 0..rounds {
+        for (x, p) in acc.iter_mut().zip(dist) {
+            *x += p / rounds as f64;
+        }
+        dist = step(a, b, &dist);
+    }
+    summarise(acc, payoffs)
+}
+
+/// Comparison of a participant's simulated per-round score with the analytic expectation.
+#[derive(Clone)]
+pub struct CrossCheck {
+    pub name: String,
+    /// Per-round score achieved in the simulation.
+    pub simulated: f64,
+    /// Per-round score expected from the participants' empirical memory-one profiles.
+    pub analytic: f64,
+    /// Long-run cooperation rate expected from the same profiles.
+    pub cooperation: f64,
+}
+
+/// Recomputes every fixed player's score analytically, treating each participant as the
+/// memory-one strategy given by its empirical response profile. Without any opponents the
+/// expectations are 0.
+pub fn cross_check(game: &Tournament, payoffs: &Payoffs) -> Vec<CrossCheck> {
+    let profiles = game.response_profiles();
+    let strategies: Vec<MemoryOne> =
+        profiles.iter().map(|(_, p)| MemoryOne::from_profile(p)).collect();
+    let matchups = game.matchups();
+    let opponents = &strategies[game.player_count()..];
+
+    matchups
+        .iter()
+        .take(game.player_count())
+        .zip(&strategies)
+        .map(|((name, games), strategy)| {
+            let simulated = games.iter().map(|m| m.own_score as f64).sum::<f64>()
+                / (games.len().max(1) as f64 * game.rounds() as f64);
+            let n = opponents.len().max(1) as f64;
+            let analytic = opponents
+                .iter()
+                .map(|o| analyse_rounds(strategy, o, payoffs, game.rounds()).payoffs.0)
+                .sum::<f64>();
+            let cooperation = opponents
+                .iter()
+                .map(|o| analyse_pair(strategy, o, payoffs).cooperation.0)
+                .sum::<f64>();
+            CrossCheck {
+                name: name.clone(),
+                simulated,
+                analytic: analytic / n,
+                cooperation: cooperation / n,
+            }
+        })
+        .collect()
+}
+
+/// Expected per-round payoff of a genome against a population, averaged over its members.
+pub fn payoff_against_mix(genome: u8, population: &[u8], payoffs: &Payoffs, rounds: u32) -> f64 {
+    let strategy = MemoryOne::from_genome(genome);
+    let total: f64 = population
+        .iter()
+        .map(|&other| {
+            analyse_rounds(&strategy, &MemoryOne::from_genome(other), payoffs, rounds).payoffs.0
+        })
+        .sum();
+    total / population.len().max(1) as f64
+}
+
+/// Genome with the highest expected payoff against the population, the lowest number on ties.
+pub fn best_response(population: &[u8], payoffs: &Payoffs, rounds: u32) -> u8 {
+    let mut best = (0, f64::NEG_INFINITY);
+    for genome in 0..GENOME_COUNT as u8 {
+        let payoff = payoff_against_mix(genome, population, payoffs, rounds);
+        if payoff > best.1 {
+            best = (genome, payoff);
+        }
+    }
+    best.0
+}
+
+/// Switches a fraction of the population, worst performers against the current mix first,
+/// to the best response against that mix.
+pub fn best_response_step(
+    population: &[u8],
+    fraction: f64,
+    payoffs: &Payoffs,
+    rounds: u32,
+) -> Box<[u8]> {
+    let target = best_response(population, payoffs, rounds);
+    let switching = ((population.len() as f64 * fraction).round() as usize).max(1);
+    let mut by_payoff: Vec<(usize, f64)> = population
+        .iter()
+        .enumerate()
+        .map(|(i, &g)| (i, payoff_against_mix(g, population, payoffs, rounds)))
+        .collect();
+    by_payoff.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    let mut next = population.to_vec();
+    for &(i, _) in by_payoff.iter().take(switching) {
+        next[i] = target;
+    }
+    next.into_boxed_slice()
+}
+
+/// Lets every individual of `population` watch one random match of another individual of a
+/// tournament that has been run, with probability `observation`, and adopt the observed
+/// individual's genome if it scored more in that match than the observer does on average.
+pub fn social_learning_step(game: &Tournament, population: &[u8], observation: f64) -> Box<[u8]> {
+    let mut rng = random::rng();
+    let results: Vec<Vec<i32>> = game
+        .matchups()
+        .into_iter()
+        .skip(game.player_count())
+        .map(|(_, games)| games.iter().map(|m| m.own_score).collect())
+        .collect();
+    let mut next = population.to_vec();
+    if population.len() < 2 {
+        return next.into_boxed_slice();
+    }
+    for (i, own) in results.iter().enumerate() {
+        if own.is_empty() || !rng.gen_bool(observation) {
+            continue;
+        }
+        let k = (i + rng.gen_range(1..population.len())) % population.len();
+        let Some(&observed) = results[k].choose(&mut rng) else {
+            continue;
+        };
+        let mean = own.iter().sum::<i32>() as f64 / own.len() as f64;
+        if observed as f64 > mean {
+            next[i] = population[k];
+        }
+    }
+    next.into_boxed_slice()
+}
+
+/// Tolerance below which two expected payoffs are considered equal.
+const PAYOFF_EPSILON: f64 = 1e-9;
+
+/// Evolutionary stability of a genome in a population playing only itself.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Stability {
+    /// Evolutionarily stable: every mutant does strictly worse eventually.
+    Strict,
+    /// Neutrally stable: no mutant does better, but some do equally well.
+    Neutral,
+    /// Some mutant can invade.
+    Unstable,
+}
+
+/// Expected per-round payoff of every deterministic genome (rows) against every other (columns).
+pub fn genome_payoffs(payoffs: &Payoffs, rounds: u32) -> Vec<Vec<f64>> {
+    (0..GENOME_COUNT as u8)
+        .map(|a| {
+            let a = MemoryOne::from_genome(a);
+            (0..GENOME_COUNT as u8)
+                .map(|b| analyse_rounds(&a, &MemoryOne::from_genome(b), payoffs, rounds).payoffs.0)
+                .collect()
+        })
+        .collect()
+}
+
+/// Classifies every deterministic genome with Maynard Smith's conditions: S is stable if
+/// for every mutant T either E(S,S) > E(T,S), or E(S,S) = E(T,S) and E(S,T) > E(T,T),
+/// and neutrally stable if the last inequality only holds weakly.
+pub fn evolutionary_stability(payoffs: &Payoffs, rounds: u32) -> Vec<Stability> {
+    let e = genome_payoffs(payoffs, rounds);
+    (0..GENOME_COUNT)
+        .map(|s| {
+            let mut stability = Stability::Strict;
+            for t in (0..GENOME_COUNT).filter(|&t| t != s) {
+                let invasion = e[t][s] - e[s][s];
+                if invasion > PAYOFF_EPSILON {
+                    return Stability::Unstable;
+                }
+                if invasion.abs() <= PAYOFF_EPSILON {
+                    let second_order = e[s][t] - e[t][t];
+                    if second_order < -PAYOFF_EPSILON {
+                        return Stability::Unstable;
+                    }
+                    if second_order <= PAYOFF_EPSILON {
+                        stability = Stability::Neutral;
+                    }
+                }
+            }
+            stability
+        })
+        .collect()
+}
+
+/// Names of the symmetric payoff parameters, in the order used by [`perturb`].
+pub const PAYOFF_PARAMETERS: [&str; 4] = ["R (CC)", "S (CD)", "T (DC)", "P (DD)"];
+
+/// Shifts one symmetric payoff parameter by `delta` for both players.
+pub fn perturb(payoffs: &Payoffs, parameter: usize, delta: f64) -> Payoffs {
+    let mut p = *payoffs;
+    match parameter {
+        0 => {
+            p[0].0 += delta;
+            p[0].1 += delta;
+        }
+        1 => {
+            p[1].0 += delta;
+            p[2].1 += delta;
+        }
+        2 => {
+            p[2].0 += delta;
+            p[1].1 += delta;
+        }
+        _ => {
+            p[3].0 += delta;
+            p[3].1 += delta;
+        }
+    }
+    p
+}
+
+/// Analytic counterpart of a tournament where the first `player_count` strategies play every
+/// remaining one. Returns every strategy's per-round score and cooperation rate.
+pub fn analytic_tournament(
+    strategies: &[MemoryOne],
+    player_count: usize,
+    payoffs: &Payoffs,
+    rounds: u32,
+) -> Vec<(f64, f64)> {
+    let (players, opponents) = strategies.split_at(player_count);
+    let mut res = vec![(0.0, 0.0); strategies.len()];
+    for (j, player) in players.iter().enumerate() {
+        for (i, opponent) in opponents.iter().enumerate() {
+            let pair = analyse_rounds(player, opponent, payoffs, rounds);
+            let (n, m) = (opponents.len() as f64, players.len() as f64);
+            res[j].0 += pair.payoffs.0 / n;
+            res[j].1 += pair.cooperation.0 / n;
+            res[player_count + i].0 += pair.payoffs.1 / m;
+            res[player_count + i].1 += pair.cooperation.1 / m;
+        }
+    }
+    res
+}
+
+/// Outcome of the analytic tournament with one payoff parameter perturbed.
+#[derive(Clone)]
+pub struct SensitivityRow {
+    pub parameter: &'static str,
+    pub delta: f64,
+    /// Participant with the highest per-round score.
+    pub winner: String,
+    pub winner_score: f64,
+    /// Cooperation rate of the winner.
+    pub cooperation: f64,
+}
+
+/// Recomputes the tournament analytically, with every participant playing its empirical
+/// memory-one profile, for each payoff parameter shifted by each of `deltas`.
+pub fn payoff_sensitivity(
+    game: &Tournament,
+    payoffs: &Payoffs,
+    deltas: &[f64],
+) -> Vec<SensitivityRow> {
+    let profiles = game.response_profiles();
+    let strategies: Vec<MemoryOne> =
+        profiles.iter().map(|(_, p)| MemoryOne::from_profile(p)).collect();
+    let mut rows = Vec::new();
+    for (parameter, name) in PAYOFF_PARAMETERS.iter().enumerate() {
+        for &delta in deltas {
+            let perturbed = perturb(payoffs, parameter, delta);
+            let results =
+                analytic_tournament(&strategies, game.player_count(), &perturbed, game.rounds());
+            let (winner, &(winner_score, cooperation)) = results
+                .iter()
+                .enumerate()
+                .max_by(|(_, (a, _)), (_, (b, _))| a.total_cmp(b))
+                .map(|(i, res)| (profiles[i].0.clone(), res))
+                .expect("tournament has participants");
+            rows.push(SensitivityRow {
+                parameter: name,
+                delta,
+                winner,
+                winner_score,
+                cooperation,
+            });
+        }
+    }
+    rows
+}
+
+/// Lowest and highest cooperation rate of the winner reached when varying each parameter,
+/// ready to be drawn as a tornado plot.
+pub fn tornado(rows: &[SensitivityRow]) -> Vec<(&'static str, f64, f64)> {
+    PAYOFF_PARAMETERS
+        .iter()
+        .map(|&name| {
+            let rates = rows.iter().filter(|r| r.parameter == name).map(|r| r.cooperation);
+            let low = rates.clone().fold(f64::INFINITY, f64::min);
+            let high = rates.fold(f64::NEG_INFINITY, f64::max);
+            (name, low, high)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Genome numbers of always defect, tit-for-tat and always cooperate.
+    const ALL_D: u8 = 0;
+    const TIT_FOR_TAT: u8 = 0b11010;
+    const ALL_C: u8 = 0b11111;
+
+    fn prisoners_dilemma() -> Payoffs {
+        payoffs_of(PayoffMatrix::symmetric(3, 0, 5, 1))
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < PAYOFF_EPSILON
+    }
+
+    #[test]
+    fn tit_for_tat_ends_up_defecting_against_always_defect() {
+        let (tft, all_d) = (
+            MemoryOne::from_genome(TIT_FOR_TAT),
+            MemoryOne::from_genome(ALL_D),
+        );
+        let res = analyse_pair(&tft, &all_d, &prisoners_dilemma());
+        assert!(res.stationary.iter().zip([0.0, 0.0, 0.0, 1.0]).all(|(&p, q)| close(p, q)));
+        assert!(close(res.payoffs.0, 1.0) && close(res.payoffs.1, 1.0));
+        assert!(close(res.cooperation.0, 0.0) && close(res.cooperation.1, 0.0));
+        // Over 10 rounds the sucker's payoff of the first round still shows.
+        let res = analyse_rounds(&tft, &all_d, &prisoners_dilemma(), 10);
+        assert!(close(res.payoffs.0, 0.9) && close(res.payoffs.1, 1.4));
+    }
+
+    #[test]
+    fn always_defect_cannot_be_invaded() {
+        let payoffs = prisoners_dilemma();
+        let stability = evolutionary_stability(&payoffs, 200);
+        // Genomes that only differ from always defect after moves it never makes play exactly
+        // like it, so among genomes it is stable only neutrally: no mutant does better.
+        assert!(stability[ALL_D as usize] == Stability::Neutral);
+        let e = genome_payoffs(&payoffs, 200);
+        let (all_d, tft) = (ALL_D as usize, TIT_FOR_TAT as usize);
+        assert!(e[tft][all_d] < e[all_d][all_d]);
+        assert!(stability[ALL_C as usize] == Stability::Unstable);
+    }
+
+    #[test]
+    fn cross_check_without_opponents_expects_nothing() {
+        let mut game =
+            Tournament::with_custom_players(10, crate::prisoners_dillemma_rules, Box::new([]), &[]);
+        game.run();
+        let checks = cross_check(&game, &prisoners_dilemma());
+        assert!(!checks.is_empty());
+        assert!(checks.iter().all(|c| c.analytic == 0.0 && c.cooperation == 0.0));
+    }
+}
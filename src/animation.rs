@@ -0,0 +1,147 @@
+//! Animated PNG of a run, drawn from its recorded generations so that runs can be shared
+//! without the window.
+
+use egui::ecolor::Hsva;
+use egui::Color32;
+use gametheory::stats::StatsRecorder;
+use gametheory::GENOME_COUNT;
+use png::{BitDepth, ColorType, Encoder};
+use std::fs::File;
+use std::io::{self, BufWriter};
+
+const WIDTH: usize = 480;
+/// Height of the score plot, above the genome histogram.
+const PLOT_HEIGHT: usize = 200;
+const HISTOGRAM_HEIGHT: usize = 100;
+const HEIGHT: usize = PLOT_HEIGHT + HISTOGRAM_HEIGHT;
+const MARGIN: f64 = 10.0;
+/// Most frames in an animation; longer runs skip generations evenly.
+const MAX_FRAMES: usize = 200;
+/// Time each frame is shown, in hundredths of a second.
+const FRAME_DELAY: u16 = 8;
+const BACKGROUND: Color32 = Color32::from_gray(27);
+
+/// RGB pixels of one frame.
+struct Canvas {
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new() -> Self {
+        let [r, g, b, _] = BACKGROUND.to_array();
+        Canvas {
+            pixels: [r, g, b].repeat(WIDTH * HEIGHT),
+        }
+    }
+
+    fn set(&mut self, x: f64, y: f64, color: Color32) {
+        let (x, y) = (x.round(), y.round());
+        if x < 0.0 || y < 0.0 || x >= WIDTH as f64 || y >= HEIGHT as f64 {
+            return;
+        }
+        let i = 3 * (y as usize * WIDTH + x as usize);
+        self.pixels[i..i + 3].copy_from_slice(&color.to_array()[..3]);
+    }
+
+    fn line(&mut self, from: [f64; 2], to: [f64; 2], color: Color32) {
+        let steps = (to[0] - from[0]).abs().max((to[1] - from[1]).abs()).ceil().max(1.0);
+        for i in 0..=steps as usize {
+            let t = i as f64 / steps;
+            self.set(
+                from[0] + t * (to[0] - from[0]),
+                from[1] + t * (to[1] - from[1]),
+                color,
+            );
+        }
+    }
+
+    /// Fills the rectangle between the corners `from` and `to`.
+    fn fill(&mut self, from: [f64; 2], to: [f64; 2], color: Color32) {
+        for y in from[1].round() as usize..to[1].round() as usize {
+            for x in from[0].round() as usize..to[0].round() as usize {
+                self.set(x as f64, y as f64, color);
+            }
+        }
+    }
+}
+
+/// Writes one frame per generation of `recorder`: the best and mean scores so far, on the
+/// axes of the whole run, above the individuals per genome in that generation.
+pub fn write_apng(path: &str, recorder: &StatsRecorder) -> io::Result<()> {
+    let generations = &recorder.generations;
+    let Some(last) = generations.len().checked_sub(1) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "nothing to animate",
+        ));
+    };
+    let mut frames: Vec<usize> =
+        (0..=last).step_by(generations.len().div_ceil(MAX_FRAMES)).collect();
+    if frames.last() != Some(&last) {
+        frames.push(last);
+    }
+
+    let scores: Vec<[f64; 2]> = generations.iter().map(|g| [g.best as f64, g.mean]).collect();
+    let (low, high) =
+        scores.iter().flatten().fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), &v| {
+            (low.min(v), high.max(v))
+        });
+    let (low, high) = if low < high {
+        (low, high)
+    } else {
+        (low - 0.5, low + 0.5)
+    };
+    let most = generations.iter().flat_map(|g| g.counts).max().unwrap_or(0).max(1);
+    let x = |gen: usize| MARGIN + gen as f64 / last.max(1) as f64 * (WIDTH as f64 - 2.0 * MARGIN);
+    let y = |score: f64| {
+        PLOT_HEIGHT as f64
+            - MARGIN
+            - (score - low) / (high - low) * (PLOT_HEIGHT as f64 - 2.0 * MARGIN)
+    };
+    let bar_width = (WIDTH as f64 - 2.0 * MARGIN) / GENOME_COUNT as f64;
+    let bottom = (HEIGHT as f64) - MARGIN;
+
+    let mut encoder = Encoder::new(
+        BufWriter::new(File::create(path)?),
+        WIDTH as u32,
+        HEIGHT as u32,
+    );
+    encoder.set_color(ColorType::Rgb);
+    encoder.set_depth(BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0)?;
+    encoder.set_frame_delay(FRAME_DELAY, 100)?;
+    let mut writer = encoder.write_header()?;
+    for &frame in &frames {
+        let mut canvas = Canvas::new();
+        canvas.line(
+            [x(frame), MARGIN],
+            [x(frame), PLOT_HEIGHT as f64 - MARGIN],
+            Color32::DARK_GRAY,
+        );
+        for gen in 1..=frame {
+            for (k, color) in [Color32::LIGHT_BLUE, Color32::LIGHT_YELLOW].into_iter().enumerate() {
+                let from = [x(gen - 1), y(scores[gen - 1][k])];
+                canvas.line(from, [x(gen), y(scores[gen][k])], color);
+            }
+        }
+        canvas.line(
+            [MARGIN, bottom],
+            [WIDTH as f64 - MARGIN, bottom],
+            Color32::DARK_GRAY,
+        );
+        for (g, &count) in generations[frame].counts.iter().enumerate() {
+            // Each genome keeps its hue, so that a spreading genome can be followed.
+            let color = Hsva::new(g as f32 / GENOME_COUNT as f32, 0.6, 0.9, 1.0).into();
+            let left = MARGIN + g as f64 * bar_width;
+            let height = count as f64 / most as f64 * (HISTOGRAM_HEIGHT as f64 - 2.0 * MARGIN);
+            canvas.fill(
+                [left, bottom - height],
+                [left + bar_width.max(1.0), bottom],
+                color,
+            );
+        }
+        writer.write_image_data(&canvas.pixels)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
@@ -0,0 +1,396 @@
+//! The commands that run without the window: simulations written to CSV, duels and
+//! tournaments between the named strategies, and determinism checks.
+
+use gametheory::experiments::{self, RoundRobinRow, Setup};
+use gametheory::settings::{read_config, PayoffChange, Settings};
+use gametheory::simulation::{simulate, simulate_batch, Controller, Progress};
+use gametheory::stats::{self, Normalization};
+use gametheory::{
+    prisoners_dillemma_rules, strategy_names, Decision, PayoffMatrix, STRATEGY_COUNT,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::process::ExitCode;
+
+/// Prints the ranking table of the classic round-robin tournament, run with
+/// `duel-all [repetitions] [strategy=instances ...]`. Once any instance count is given, only
+/// the strategies listed take part, e.g. `duel-all 5 "trusting t4t=5" evil=15`.
+fn duel_all(
+    repetitions: Option<String>,
+    entries: impl Iterator<Item = String>,
+) -> Result<(), String> {
+    let repetitions =
+        number(repetitions, "repetitions")?.unwrap_or(experiments::ROUND_ROBIN_REPETITIONS);
+    let names = strategy_names();
+    let mut copies = [0; STRATEGY_COUNT];
+    let mut listed = false;
+    for entry in entries {
+        let Some((name, count)) = entry.split_once('=') else {
+            return Err(format!("expected strategy=instances, got {entry}"));
+        };
+        let Some(s) = names.iter().position(|n| n.replace('\n', " ") == name) else {
+            return Err(format!("unknown strategy {name}"));
+        };
+        let Ok(count) = count.parse() else {
+            return Err(format!("invalid instance count {count}"));
+        };
+        copies[s] = count;
+        listed = true;
+    }
+    if !listed {
+        copies = [1; STRATEGY_COUNT];
+    }
+    print_ranking(&experiments::round_robin(
+        &duel_setup(),
+        repetitions,
+        &copies,
+        &[0; STRATEGY_COUNT],
+    ));
+    Ok(())
+}
+
+/// Runs a simulation without opening the window and writes its series as CSV, run with
+/// `run [--config PATH] [--generations N] [--rounds N] [--seed N] [--runs N] [--out PATH]`,
+/// e.g. `run --generations 200 --rounds 100 --seed 42 --out results.csv`. The config file is
+/// read like the one given to the window, and the other options override it. With `--runs`
+/// the configuration is run that many times with consecutive seeds, and the mean, variance,
+/// minimum and maximum of the best score per generation are written instead.
+fn run(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut args = args;
+    let (mut config, mut out) = (None, "results.csv".to_string());
+    let (mut generations, mut rounds, mut seed, mut runs) = (None, None, None, 1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" | "--out" => {
+                let Some(value) = args.next() else {
+                    return Err(format!("{arg} expects a path"));
+                };
+                if arg == "--config" {
+                    config = Some(value);
+                } else {
+                    out = value;
+                }
+            }
+            "--generations" | "--rounds" => {
+                let Some(value) = args.next().and_then(|v| v.parse::<u32>().ok()) else {
+                    return Err(format!("{arg} expects a number up to {}", u32::MAX));
+                };
+                if arg == "--generations" {
+                    generations = Some(value);
+                } else {
+                    rounds = Some(value);
+                }
+            }
+            "--seed" | "--runs" => {
+                let Some(value) = args.next().and_then(|v| v.parse::<u64>().ok()) else {
+                    return Err(format!("{arg} expects a number"));
+                };
+                if arg == "--runs" {
+                    runs = value.max(1);
+                } else {
+                    seed = Some(value);
+                }
+            }
+            other => {
+                return Err(format!("unknown option {other}"));
+            }
+        }
+    }
+    let (mut settings, schedule) = read_config(config)?;
+    settings.gen_count = generations.unwrap_or(settings.gen_count);
+    settings.game_count = rounds.unwrap_or(settings.game_count);
+    let seed = seed.or(settings.seed).unwrap_or_else(rand::random);
+    settings.seed = Some(seed);
+    if runs > 1 {
+        return run_batch(settings, &schedule, seed, runs, &out);
+    }
+
+    let progress = Progress::default();
+    simulate(
+        &progress,
+        &mut Controller::default(),
+        settings,
+        &schedule,
+        &[],
+        || {},
+    );
+    let recorder = progress.recorder.lock().unwrap().clone();
+    if let Err(err) = stats::write_series(&out, &recorder, Normalization::Raw) {
+        return Err(format!("could not write {out}: {err}"));
+    }
+    println!(
+        "{} (seed {seed}), wrote {out}",
+        progress.status.lock().unwrap()
+    );
+    Ok(())
+}
+
+/// Runs a configuration twice with the same seed and checks that both runs record the same
+/// generations and end with the same tournament, run with `verify [--config PATH] [--seed N]`.
+/// The first generation at which the runs differ is reported.
+fn verify(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut args = args;
+    let (mut config, mut seed) = (None, None);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => config = Some(args.next().ok_or("--config expects a path")?),
+            "--seed" => {
+                let value = args.next().and_then(|v| v.parse().ok());
+                seed = Some(value.ok_or("--seed expects a number")?);
+            }
+            other => return Err(format!("unknown option {other}")),
+        }
+    }
+    let (settings, schedule) = read_config(config)?;
+    let seed = seed.or(settings.seed).unwrap_or_else(rand::random);
+    let settings = Settings {
+        seed: Some(seed),
+        ..settings
+    };
+    let (series, last) = fingerprint(settings, &schedule);
+    let (other_series, other_last) = fingerprint(settings, &schedule);
+    let differs =
+        (0..series.len().max(other_series.len())).find(|&g| series.get(g) != other_series.get(g));
+    print!("seed {seed}: ");
+    match differs {
+        Some(generation) => println!("the runs differ from generation {generation}"),
+        None if last != other_last => println!("the runs differ in the final tournament"),
+        None => {
+            println!("identical over {} generations ({last:016x})", series.len());
+            return Ok(());
+        }
+    }
+    Err("the runs are not deterministic".to_string())
+}
+
+/// Hashes of every generation recorded by a run of `settings` and of its final tournament.
+fn fingerprint(settings: Settings, schedule: &[PayoffChange]) -> (Vec<u64>, u64) {
+    let hash = |text: String| {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        hasher.finish()
+    };
+    let progress = Progress::default();
+    simulate(
+        &progress,
+        &mut Controller::default(),
+        settings,
+        schedule,
+        &[],
+        || {},
+    );
+    let generations = progress.recorder.lock().unwrap().generations.clone();
+    let last = hash(progress.final_tournament.lock().unwrap().clone());
+    (
+        generations.iter().map(|g| hash(serde_json::to_string(g).unwrap_or_default())).collect(),
+        last,
+    )
+}
+
+/// Runs `settings` `runs` times, seeded from `seed` upwards, and writes the spread of the
+/// best score per generation to `out`.
+fn run_batch(
+    settings: Settings,
+    schedule: &[PayoffChange],
+    seed: u64,
+    runs: u64,
+    out: &str,
+) -> Result<(), String> {
+    let progress = Progress::default();
+    let seeds = seed..seed.saturating_add(runs);
+    let control = &mut Controller::default();
+    let finished = simulate_batch(&progress, control, settings, seeds, schedule, &[], || {});
+    for (run, (seed, status)) in finished.iter().enumerate() {
+        println!("run {} (seed {seed}): {status}", run + 1);
+    }
+    let batch = progress.batch.lock().unwrap().clone();
+    if let Err(err) = stats::write_batch(out, &batch) {
+        return Err(format!("could not write {out}: {err}"));
+    }
+    if let Some(last) = batch.last() {
+        println!(
+            "final best score over {} runs: mean {:.2}, std dev {:.2}, min {}, max {}",
+            last.runs,
+            last.mean,
+            last.variance.sqrt(),
+            last.min,
+            last.max
+        );
+    }
+    println!("wrote {out}");
+    Ok(())
+}
+
+/// Prints the report of repeated matches between two named strategies, run with
+/// `duel <strategy> <strategy> [--rounds N] [--reps K] [--payoffs R,S,T,P] [--transcript]`,
+/// e.g. `duel "trusting t4t" evil --reps 10 --payoffs 3,0,5,1`. Without `--payoffs` the
+/// usual prisoner's dilemma is played.
+fn duel(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let names = strategy_names();
+    let mut strategies = Vec::new();
+    let (mut rounds, mut repetitions, mut transcript) = (experiments::DUEL_ROUNDS, 1, false);
+    let mut payoffs = PayoffMatrix::from(prisoners_dillemma_rules);
+    let mut args = args.peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rounds" | "--reps" => {
+                let Some(value) = args.next().and_then(|v| v.parse().ok()) else {
+                    return Err(format!("{arg} expects a number"));
+                };
+                if arg == "--rounds" {
+                    rounds = value;
+                } else {
+                    repetitions = value;
+                }
+            }
+            "--payoffs" => {
+                let values: Option<Vec<i32>> = args
+                    .next()
+                    .map(|v| v.split(',').map(|p| p.trim().parse().ok()).collect())
+                    .unwrap_or_default();
+                let Some(&[r, s, t, p]) = values.as_deref() else {
+                    return Err("--payoffs expects R,S,T,P".to_string());
+                };
+                payoffs = match PayoffMatrix::prisoners_dilemma(r, s, t, p) {
+                    Ok(payoffs) => payoffs,
+                    Err(err) => {
+                        return Err(format!("invalid payoffs: {err}"));
+                    }
+                };
+            }
+            "--transcript" => transcript = true,
+            name => {
+                let Some(s) = names.iter().position(|n| n.replace('\n', " ") == name) else {
+                    return Err(format!("unknown strategy {name}"));
+                };
+                strategies.push(s);
+            }
+        }
+    }
+    let [a, b] = strategies[..] else {
+        return Err("expected two strategies".to_string());
+    };
+    let (a_name, b_name) = (names[a].replace('\n', " "), names[b].replace('\n', " "));
+    let report = experiments::duel(a, b, rounds, repetitions, payoffs);
+
+    println!("{a_name} vs {b_name}, {rounds} rounds, {repetitions} repetitions");
+    if transcript {
+        let letter = |d: Decision| match d {
+            Decision::Cooperate => 'C',
+            Decision::Defect => 'D',
+        };
+        for (round, &(x, y)) in report.transcript.iter().enumerate() {
+            println!("{:>5}  {}  {}", round + 1, letter(x), letter(y));
+        }
+    }
+    let per_round = |score: i32| score as f64 / rounds as f64;
+    let n = report.scores.len() as f64;
+    let mean = (
+        report.scores.iter().map(|s| per_round(s.0)).sum::<f64>() / n,
+        report.scores.iter().map(|s| per_round(s.1)).sum::<f64>() / n,
+    );
+    println!(
+        "{:<20}{:>12}{:>14}{:>12}",
+        "strategy", "per round", "cooperation", "analytic"
+    );
+    let analytic = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{v:.3}"));
+    println!(
+        "{:<20}{:>12.3}{:>14.3}{:>12}",
+        a_name,
+        mean.0,
+        report.cooperation.0,
+        analytic(report.analytic.map(|p| p.0))
+    );
+    println!(
+        "{:<20}{:>12.3}{:>14.3}{:>12}",
+        b_name,
+        mean.1,
+        report.cooperation.1,
+        analytic(report.analytic.map(|p| p.1))
+    );
+    Ok(())
+}
+
+/// Prints the ranking table of a Swiss-system tournament among the named strategies, run with
+/// `swiss [rounds] [repetitions]`.
+fn swiss(rounds: Option<String>, repetitions: Option<String>) -> Result<(), String> {
+    let rounds = number(rounds, "rounds")?.unwrap_or(experiments::SWISS_ROUNDS);
+    let repetitions =
+        number(repetitions, "repetitions")?.unwrap_or(experiments::ROUND_ROBIN_REPETITIONS);
+    print_ranking(&experiments::swiss(&duel_setup(), rounds, repetitions));
+    Ok(())
+}
+
+/// Game of the tournaments between the named strategies: the defaults of an experiment over
+/// matches of [`experiments::DUEL_ROUNDS`] rounds, with every named strategy taking part.
+fn duel_setup() -> Setup {
+    Setup {
+        game_count: experiments::DUEL_ROUNDS,
+        strategies: [true; STRATEGY_COUNT],
+        ..Setup::default()
+    }
+}
+
+/// Reads the optional positional argument `name` of a command as a number.
+fn number<T: std::str::FromStr>(arg: Option<String>, name: &str) -> Result<Option<T>, String> {
+    arg.map(|a| a.parse().map_err(|_| format!("invalid {name} {a}"))).transpose()
+}
+
+fn print_ranking(rows: &[RoundRobinRow]) {
+    println!(
+        "{:>4}  {:<20}{:>10}{:>10}{:>6}{:>8}",
+        "rank", "strategy", "score", "std dev", "wins", "losses"
+    );
+    for (rank, r) in rows.iter().enumerate() {
+        println!(
+            "{:>4}  {:<20}{:>10.3}{:>10.3}{:>6}{:>8}",
+            rank + 1,
+            r.name.replace('\n', " "),
+            r.score,
+            r.spread,
+            r.wins,
+            r.losses
+        );
+    }
+}
+
+/// Commands of the binary, printed by `--help`.
+const USAGE: &str = "\
+usage: gametheory-cli <command> [options]
+
+commands:
+  run [--config PATH] [--generations N] [--rounds N] [--seed N] [--runs N] [--out PATH]
+      run a simulation and write its series as CSV
+  duel <strategy> <strategy> [--rounds N] [--reps K] [--payoffs R,S,T,P] [--transcript]
+      play repeated matches between two strategies
+  duel-all [repetitions] [strategy=instances ...]
+      rank the strategies in a round-robin tournament
+  swiss [rounds] [repetitions]
+      rank the strategies in a Swiss-system tournament
+  verify [--config PATH] [--seed N]
+      run a configuration twice with the same seed and report where the runs differ
+";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("duel-all") => duel_all(args.next(), args),
+        Some("duel") => duel(args),
+        Some("swiss") => swiss(args.next(), args.next()),
+        Some("run") => run(args),
+        Some("verify") => verify(args),
+        Some("--help" | "-h" | "help") => {
+            print!("{USAGE}");
+            Ok(())
+        }
+        Some(other) => Err(format!("unknown command {other}, see --help")),
+        None => Err(format!("expected a command\n\n{USAGE}")),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
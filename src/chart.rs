@@ -0,0 +1,214 @@
+//! Headless rendering of plotted series to SVG, so that charts can be put in reports and
+//! slides without the window, and saving of plots captured from the window as PNG.
+
+use egui::{Color32, ColorImage, Rect};
+use egui_plot::{Line, LineStyle};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+
+/// Width of an exported chart, in pixels.
+const WIDTH: f64 = 800.0;
+/// Height of a single exported chart, in pixels.
+const HEIGHT: f64 = 360.0;
+/// Space around the plotting area: left for the y ticks, right for the legend, top for the
+/// title and bottom for the x ticks and label.
+const MARGIN: (f64, f64, f64, f64) = (70.0, 170.0, 30.0, 50.0);
+/// Approximate number of ticks along each axis.
+const TICKS: f64 = 5.0;
+const BACKGROUND: &str = "#1b1b1b";
+const FOREGROUND: &str = "#c8c8c8";
+
+/// One named line of a chart.
+pub struct Series {
+    pub name: String,
+    pub color: Color32,
+    pub style: LineStyle,
+    pub points: Vec<[f64; 2]>,
+}
+
+impl Series {
+    /// The series as drawn in the window.
+    pub fn line(&self) -> Line {
+        Line::new(self.points.clone()).name(&self.name).color(self.color).style(self.style)
+    }
+}
+
+/// Series sharing a pair of axes, with their labels.
+pub struct Chart {
+    pub title: String,
+    pub x_label: String,
+    pub y_label: String,
+    pub series: Vec<Series>,
+    /// Values the y axis spans even if no series reaches them.
+    pub include_y: Vec<f64>,
+    /// Labelled x positions marked by a vertical line, such as changes of the game.
+    pub markers: Vec<(f64, String)>,
+}
+
+impl Chart {
+    /// Draws the chart as SVG elements within `HEIGHT` pixels from `top`.
+    fn render(&self, svg: &mut String, top: f64) {
+        let (left, right, margin_top, bottom) = MARGIN;
+        let (x0, x1) = (left, WIDTH - right);
+        let (y0, y1) = (top + HEIGHT - bottom, top + margin_top);
+        let points = self.series.iter().flat_map(|s| &s.points);
+        let (x_min, x_max) = bounds(points.clone().map(|p| p[0]));
+        let (y_min, y_max) = bounds(points.map(|p| p[1]).chain(self.include_y.iter().copied()));
+        let sx = |x: f64| x0 + (x - x_min) / (x_max - x_min) * (x1 - x0);
+        let sy = |y: f64| y0 - (y - y_min) / (y_max - y_min) * (y0 - y1);
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle" font-size="14">{}</text>"#,
+            (x0 + x1) / 2.0,
+            top + 20.0,
+            escape(&self.title)
+        );
+        for x in ticks(x_min, x_max) {
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{0}" y1="{y1}" x2="{0}" y2="{y0}" stroke="#333"/><text x="{0}" y="{1}" text-anchor="middle">{x}</text>"##,
+                sx(x),
+                y0 + 16.0
+            );
+        }
+        for y in ticks(y_min, y_max) {
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{x0}" y1="{0}" x2="{x1}" y2="{0}" stroke="#333"/><text x="{1}" y="{2}" text-anchor="end">{y}</text>"##,
+                sy(y),
+                x0 - 6.0,
+                sy(y) + 4.0
+            );
+        }
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x0}" y="{y1}" width="{}" height="{}" fill="none" stroke="{FOREGROUND}"/>"#,
+            x1 - x0,
+            y0 - y1
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+            (x0 + x1) / 2.0,
+            y0 + 38.0,
+            escape(&self.x_label)
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text transform="translate({}, {}) rotate(-90)" text-anchor="middle">{}</text>"#,
+            left - 50.0,
+            (y0 + y1) / 2.0,
+            escape(&self.y_label)
+        );
+        for (x, label) in &self.markers {
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{0}" y1="{y1}" x2="{0}" y2="{y0}" stroke="#555" stroke-dasharray="4 4"><title>{1}</title></line>"##,
+                sx(*x),
+                escape(label)
+            );
+        }
+
+        for (k, s) in self.series.iter().enumerate() {
+            let path: Vec<String> =
+                s.points.iter().map(|p| format!("{:.1},{:.1}", sx(p[0]), sy(p[1]))).collect();
+            let [r, g, b, _] = s.color.to_array();
+            let colour = format!("rgb({r},{g},{b})");
+            let _ = writeln!(
+                svg,
+                r#"<polyline points="{}" fill="none" stroke="{colour}" stroke-width="1.5"{}/>"#,
+                path.join(" "),
+                dashes(s.style)
+            );
+            let y = top + margin_top + 10.0 + 18.0 * k as f64;
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{0}" y1="{y}" x2="{1}" y2="{y}" stroke="{colour}" stroke-width="2"{2}/><text x="{3}" y="{4}">{5}</text>"#,
+                x1 + 12.0,
+                x1 + 36.0,
+                dashes(s.style),
+                x1 + 42.0,
+                y + 4.0,
+                escape(&s.name)
+            );
+        }
+    }
+}
+
+/// Writes `charts` one below the other as an SVG image.
+pub fn write_svg(path: &str, charts: &[Chart]) -> io::Result<()> {
+    let height = HEIGHT * charts.len() as f64;
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" font-family="sans-serif" font-size="12" fill="{FOREGROUND}">
+<rect width="100%" height="100%" fill="{BACKGROUND}"/>
+"#
+    );
+    for (i, chart) in charts.iter().enumerate() {
+        chart.render(&mut svg, HEIGHT * i as f64);
+    }
+    svg.push_str("</svg>\n");
+    fs::write(path, svg)
+}
+
+/// Writes the part of `screenshot` within `rect`, given in points, as a PNG image.
+pub fn write_png(
+    path: &str,
+    screenshot: &ColorImage,
+    rect: Rect,
+    pixels_per_point: f32,
+) -> image::ImageResult<()> {
+    let region = screenshot.region(&rect, Some(pixels_per_point));
+    let pixels: Vec<u8> = region.pixels.iter().flat_map(|c| c.to_array()).collect();
+    image::save_buffer(
+        path,
+        &pixels,
+        region.width() as u32,
+        region.height() as u32,
+        image::ColorType::Rgba8,
+    )
+}
+
+/// Smallest and largest of `values`, widened so that the axis never has zero length.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (low, high) = values
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), v| {
+            (low.min(v), high.max(v))
+        });
+    if low > high {
+        (0.0, 1.0)
+    } else if low == high {
+        (low - 0.5, high + 0.5)
+    } else {
+        (low, high)
+    }
+}
+
+/// Round values between `low` and `high` at steps of 1, 2 or 5 times a power of ten.
+fn ticks(low: f64, high: f64) -> Vec<f64> {
+    let raw = (high - low) / TICKS;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|&s| s >= raw)
+        .unwrap_or(10.0 * magnitude);
+    let first = (low / step).ceil() as i64;
+    let last = (high / step).floor() as i64;
+    // Rounded so that steps such as 0.1 are not printed as 0.30000000000000004.
+    (first..=last).map(|i| (i as f64 * step * 1e9).round() / 1e9).collect()
+}
+
+fn dashes(style: LineStyle) -> String {
+    match style {
+        LineStyle::Solid => String::new(),
+        LineStyle::Dotted { spacing } => format!(r#" stroke-dasharray="1.5 {spacing}""#),
+        LineStyle::Dashed { length } => format!(r#" stroke-dasharray="{length} {length}""#),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('\n', " ")
+}
@@ -0,0 +1,36 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// How often the configuration file is checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Configuration file given on the command line, watched for changes made in another editor.
+pub struct ConfigFile {
+    pub path: PathBuf,
+    /// Modification time of the file when it was last checked, `Some(None)` if it could not be
+    /// read then.
+    modified: Option<Option<SystemTime>>,
+}
+
+impl ConfigFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        ConfigFile {
+            path: path.into(),
+            modified: None,
+        }
+    }
+
+    /// Returns the contents of the file if it changed since the last call, which is always the
+    /// case for the first call, and an error once each time it becomes unreadable.
+    pub fn changed(&mut self) -> Option<io::Result<String>> {
+        let modified = fs::metadata(&self.path).and_then(|m| m.modified());
+        let stamp = modified.as_ref().ok().copied();
+        if self.modified == Some(stamp) {
+            return None;
+        }
+        self.modified = Some(stamp);
+        Some(modified.and_then(|_| fs::read_to_string(&self.path)))
+    }
+}
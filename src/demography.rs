@@ -0,0 +1,66 @@
+use crate::gametheory::{genome_to_number, number_to_genome, reproduce, Tournament};
+
+/// Wealth every founder starts with.
+const INITIAL_WEALTH: f64 = 1.0;
+/// Wealth an individual needs to reproduce, half of which it hands to its offspring.
+const BIRTH_WEALTH: f64 = 2.0;
+/// Wealth below which an individual dies.
+const DEATH_WEALTH: f64 = 0.0;
+/// Wealth every individual spends per generation to stay alive.
+const LIVING_COST: f64 = 0.5;
+/// Upper bound on the number of individuals, enforced by letting no one reproduce beyond it.
+const MAX_POPULATION: usize = 40;
+
+/// Agent-based population in which individuals accumulate their payoffs as wealth, die when
+/// it runs out and reproduce once they are rich enough, so the population size follows how
+/// well its members do.
+pub struct Demography {
+    /// Wealth of every individual, in population order.
+    wealth: Vec<f64>,
+}
+
+impl Demography {
+    pub fn new(size: usize) -> Self {
+        Demography {
+            wealth: vec![INITIAL_WEALTH; size],
+        }
+    }
+
+    /// Credits every individual of `population` with its income from a tournament that has
+    /// been run, then removes the poor and lets the rich reproduce.
+    ///
+    /// Income is the individual's average payoff per round rescaled so that the worst payoff
+    /// earns nothing and the best earns one, minus [`LIVING_COST`].
+    pub fn step(&mut self, population: &[u8], game: &Tournament) -> Box<[u8]> {
+        let worst = game.worst_payoff() as f64;
+        let best = game.best_payoff() as f64;
+        let incomes = game.matchups().into_iter().skip(game.player_count()).map(|(_, games)| {
+            let rounds = (games.len() as u32 * game.rounds()) as f64;
+            let mean = games.iter().map(|m| m.own_score as f64).sum::<f64>() / rounds;
+            (mean - worst) / (best - worst)
+        });
+
+        let mut next = Vec::new();
+        let mut wealth = Vec::new();
+        for ((&genome, &w), income) in population.iter().zip(&self.wealth).zip(incomes) {
+            let w = w + income - LIVING_COST;
+            if w < DEATH_WEALTH {
+                continue;
+            }
+            if w >= BIRTH_WEALTH && population.len() + next.len() < 2 * MAX_POPULATION {
+                let parent = number_to_genome(genome);
+                next.push(genome_to_number(&reproduce(&parent, &parent)));
+                wealth.push(w / 2.0);
+                next.push(genome);
+                wealth.push(w / 2.0);
+            } else {
+                next.push(genome);
+                wealth.push(w);
+            }
+        }
+        next.truncate(MAX_POPULATION);
+        wealth.truncate(MAX_POPULATION);
+        self.wealth = wealth;
+        next.into_boxed_slice()
+    }
+}
@@ -0,0 +1,1081 @@
+use crate::analysis::{self, MemoryOne};
+use crate::gametheory::{
+    breed, breed_population, default_strategies, genome_label, genome_to_number,
+    get_traced_generation, inherit_rate_gene, inherit_traits, is_punisher, memory_one_rates,
+    number_to_genome, payoff_table, prisoners_dillemma_rules, strategy_names, talk_genes, Decision,
+    GeneticConfig, Genome, Origin, PayoffMatrix, PayoffTable, Tournament, CONTRACT_PENALTY,
+    CONTRACT_ROUNDS, GAME_PRESETS, GENOME_COUNT, MUTATION_RATES, PUNISH_COST, PUNISH_FINE,
+    STRATEGY_COUNT, TALK_COUNT,
+};
+use crate::random;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// The configuration of the main simulation an experiment reruns, so that its results speak
+/// about the game being studied rather than the defaults.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Setup {
+    /// Rounds per match.
+    pub game_count: u32,
+    /// Generations evolved.
+    pub gen_count: u32,
+    /// Symmetric payoffs of the game played.
+    pub payoffs: PayoffTable,
+    /// Genetic algorithm breeding the population.
+    pub genetic: GeneticConfig,
+    /// Probability that a move is flipped when played.
+    pub execution_noise: f64,
+    /// Probability that a move is flipped when remembered by the other side.
+    pub perception_noise: f64,
+    /// Probability of forgetting a partner before each round.
+    pub forgetting: f64,
+    /// Payoff of a round in which someone abstains.
+    pub loner_payoff: i32,
+    /// Which named strategies take part among the fixed players, by index.
+    pub strategies: [bool; STRATEGY_COUNT],
+}
+
+impl Default for Setup {
+    fn default() -> Self {
+        Setup {
+            game_count: 10,
+            gen_count: 100,
+            payoffs: payoff_table(prisoners_dillemma_rules),
+            genetic: GeneticConfig::default(),
+            execution_noise: 0.0,
+            perception_noise: 0.0,
+            forgetting: 0.0,
+            loner_payoff: -2,
+            strategies: default_strategies(),
+        }
+    }
+}
+
+impl Setup {
+    /// Tournament of one generation, in which `population` meets the chosen fixed players.
+    fn tournament(&self, population: &[u8]) -> Tournament {
+        let payoffs = PayoffMatrix::new(self.payoffs);
+        self.configure(Tournament::from(
+            self.game_count,
+            payoffs,
+            population.into(),
+        ))
+    }
+
+    /// `game` played under the noise, forgetting and loner payoff of the setup, among the chosen
+    /// fixed players.
+    fn configure(&self, game: Tournament) -> Tournament {
+        self.play_under(game, &self.strategies).with_enabled_players(&self.strategies)
+    }
+
+    /// Tournament among `copies[s]` instances of every named strategy `s`, played under the
+    /// payoffs, noise, forgetting and loner payoff of the setup.
+    fn roster(&self, copies: &[usize]) -> Tournament {
+        let game = Tournament::roster(self.game_count, PayoffMatrix::new(self.payoffs), copies);
+        let present: Vec<bool> = copies.iter().map(|&c| c > 0).collect();
+        self.play_under(game, &present)
+    }
+
+    /// `game` played under the noise, forgetting and loner payoff of the setup, with the
+    /// punishment stage if a punisher is among the named strategies `present`.
+    fn play_under(&self, game: Tournament, present: &[bool]) -> Tournament {
+        let game = game
+            .with_noise(self.execution_noise, self.perception_noise)
+            .with_forgetting(self.forgetting)
+            .with_loner_payoff(self.loner_payoff);
+        if (0..STRATEGY_COUNT).any(|s| present.get(s) == Some(&true) && is_punisher(s)) {
+            game.with_punishment(&[], PUNISH_COST, PUNISH_FINE)
+        } else {
+            game
+        }
+    }
+
+    /// Evolves the population with the genetic algorithm of the main simulation, every
+    /// individual carrying heritable traits next to its strategy genome: `founder` draws the
+    /// traits of each initial genome, `adapt` hands those of a generation to its tournament and
+    /// `observe` sees the tournament once played, with the genomes and traits of its
+    /// population. A child takes the traits of its first parent through `inherit`, which may
+    /// mutate them. Returns the tournament of the last generation.
+    fn evolve<T: Copy>(
+        &self,
+        founder: impl FnMut(u8) -> T,
+        adapt: impl Fn(Tournament, &[T]) -> Tournament,
+        mut observe: impl FnMut(&Tournament, &[u8], &[T]),
+        mut inherit: impl FnMut(T) -> T,
+    ) -> Option<Tournament> {
+        let mut population = self.genetic.initial_population();
+        let mut traits: Vec<T> = population.iter().copied().map(founder).collect();
+        let mut last = None;
+        for _ in 0..self.gen_count {
+            let mut game = adapt(self.tournament(&population), &traits);
+            game.run();
+            observe(&game, &population, &traits);
+            let (next, origins) = get_traced_generation(&game, &self.genetic);
+            traits = inherit_traits(&traits, &origins, &mut inherit);
+            population = next;
+            last = Some(game);
+        }
+        last
+    }
+
+    /// Evolves the population without any traits and returns the tournament of its last
+    /// generation, or `None` if no generation is played.
+    fn last_generation(&self) -> Option<Tournament> {
+        self.evolve(|_| (), |game, _| game, |_, _, _| {}, |()| ())
+    }
+}
+
+/// Noise probabilities tried for both execution and perception noise.
+pub const NOISE_LEVELS: [f64; 4] = [0.0, 0.01, 0.05, 0.1];
+
+/// Outcome of an evolutionary run under one combination of noise levels.
+#[derive(Clone)]
+pub struct NoiseRow {
+    pub execution: f64,
+    pub perception: f64,
+    /// Rank of every fixed player by total score in the final generation, 1 being the best.
+    pub ranks: Vec<(String, usize)>,
+    /// Best genome of the final generation.
+    pub winner: u8,
+}
+
+/// Reruns the evolution of `setup` for every combination of [`NOISE_LEVELS`] in place of its
+/// noise and reports how the fixed players' ranks and the evolved winner change.
+pub fn noise_robustness(setup: &Setup) -> Vec<NoiseRow> {
+    let mut rows = Vec::new();
+    for &execution in &NOISE_LEVELS {
+        for &perception in &NOISE_LEVELS {
+            let noisy = Setup {
+                execution_noise: execution,
+                perception_noise: perception,
+                ..*setup
+            };
+            let Some(game) = noisy.last_generation() else {
+                continue;
+            };
+            if let Some(winner) = winner(&game) {
+                rows.push(NoiseRow {
+                    execution,
+                    perception,
+                    ranks: player_ranks(&game),
+                    winner,
+                });
+            }
+        }
+    }
+    rows
+}
+
+/// Genome of the fittest opponent of a tournament that has been run, if it has any.
+fn winner(game: &Tournament) -> Option<u8> {
+    let (fittest, _) = game.select_fittest_and_bestscore(1)?;
+    Some(genome_to_number(&fittest[0]))
+}
+
+/// Forgetting probabilities tried by the forgetting report.
+pub const FORGETTING_LEVELS: [f64; 5] = [0.0, 0.05, 0.1, 0.25, 0.5];
+
+/// Outcome of an evolutionary run in which every participant forgets at the same rate.
+#[derive(Clone)]
+pub struct ForgettingRow {
+    pub forgetting: f64,
+    /// Rank of every fixed player by total score in the final generation, 1 being the best.
+    pub ranks: Vec<(String, usize)>,
+    /// Best genome of the final generation.
+    pub winner: u8,
+}
+
+/// Reruns the evolution of `setup` for every level of [`FORGETTING_LEVELS`] in place of its
+/// forgetting and reports how the fixed players' ranks and the evolved winner change as recall
+/// gets worse.
+pub fn forgetting_report(setup: &Setup) -> Vec<ForgettingRow> {
+    let mut rows = Vec::new();
+    for &forgetting in &FORGETTING_LEVELS {
+        let forgetful = Setup {
+            forgetting,
+            ..*setup
+        };
+        let Some(game) = forgetful.last_generation() else {
+            continue;
+        };
+        if let Some(winner) = winner(&game) {
+            rows.push(ForgettingRow {
+                forgetting,
+                ranks: player_ranks(&game),
+                winner,
+            });
+        }
+    }
+    rows
+}
+
+/// Forgetting probability of the single forgetful strategy in [`solo_forgetting`].
+pub const SOLO_FORGETTING: f64 = 0.25;
+
+/// Rank of a named strategy among the others with perfect and with poor recall.
+#[derive(Clone)]
+pub struct SoloForgettingRow {
+    pub name: String,
+    /// Rank when no one forgets, 1 being the best.
+    pub recall_rank: usize,
+    /// Rank when only this strategy forgets with probability [`SOLO_FORGETTING`].
+    pub forgetful_rank: usize,
+}
+
+/// Plays the named strategies against each other once with perfect recall and once per
+/// strategy with only that strategy forgetting, to show which strategies rely on memory.
+pub fn solo_forgetting(game_count: u32) -> Vec<SoloForgettingRow> {
+    let mut recall = Tournament::named(game_count, prisoners_dillemma_rules);
+    recall.run();
+    player_ranks(&recall)
+        .into_iter()
+        .enumerate()
+        .map(|(s, (name, recall_rank))| {
+            let mut rates = [0.0; STRATEGY_COUNT];
+            rates[s] = SOLO_FORGETTING;
+            let mut game = Tournament::named(game_count, prisoners_dillemma_rules)
+                .with_player_forgetting(&rates);
+            game.run();
+            SoloForgettingRow {
+                name,
+                recall_rank,
+                forgetful_rank: player_ranks(&game)[s].1,
+            }
+        })
+        .collect()
+}
+
+/// Ranks of one fixed player across every game of [`GAME_PRESETS`].
+#[derive(Clone)]
+pub struct MetaRow {
+    pub name: String,
+    /// Rank in each game, in the order of [`GAME_PRESETS`], 1 being the best.
+    pub ranks: Vec<usize>,
+    /// Mean rank over all games, lower meaning a better generalist.
+    pub robustness: f64,
+    pub worst_rank: usize,
+}
+
+/// Plays the fixed players of `setup` against its initial population under every game of
+/// [`GAME_PRESETS`] in place of its payoffs and aggregates their ranks, best generalist first.
+pub fn meta_tournament(setup: &Setup) -> Vec<MetaRow> {
+    let mut rows: Vec<MetaRow> = Vec::new();
+    for (_, rules) in GAME_PRESETS {
+        let population = setup.genetic.initial_population();
+        let mut game = setup.configure(Tournament::from(setup.game_count, rules, population));
+        game.run();
+        for (k, (name, rank)) in player_ranks(&game).into_iter().enumerate() {
+            match rows.get_mut(k) {
+                Some(row) => row.ranks.push(rank),
+                None => rows.push(MetaRow {
+                    name,
+                    ranks: vec![rank],
+                    robustness: 0.0,
+                    worst_rank: 0,
+                }),
+            }
+        }
+    }
+    for row in rows.iter_mut() {
+        row.robustness = row.ranks.iter().sum::<usize>() as f64 / row.ranks.len() as f64;
+        row.worst_rank = row.ranks.iter().copied().max().unwrap_or_default();
+    }
+    rows.sort_by(|a, b| a.robustness.total_cmp(&b.robustness));
+    rows
+}
+
+/// Summary of one generation of a population evolving in one environment.
+#[derive(Clone)]
+pub struct EnvironmentRow {
+    /// Mean payoff per round of the population, rescaled so that the worst payoff of the
+    /// environment is 0 and the best is 1, which makes environments comparable.
+    pub fitness: f64,
+    /// Fraction of the population's decisions that were to cooperate.
+    pub cooperation: f64,
+}
+
+/// Evolves the population of `setup` with the genetic algorithm, one of several environments
+/// differing in their game or noise to be compared side by side.
+pub fn evolve_in(setup: &Setup) -> Vec<EnvironmentRow> {
+    let mut rows = Vec::new();
+    setup.evolve(
+        |_| (),
+        |game, _| game,
+        |game, _, _| {
+            let totals = opponent_totals(game);
+            let rounds = (game.rounds() as usize * game.player_count()) as f64;
+            let mean = totals.iter().sum::<f64>() / (totals.len() as f64 * rounds);
+            let (worst, best) = (game.worst_payoff() as f64, game.best_payoff() as f64);
+            rows.push(EnvironmentRow {
+                fitness: (mean - worst) / (best - worst),
+                cooperation: game.cooperation_rate(),
+            });
+        },
+        |()| (),
+    );
+    rows
+}
+
+/// Number of distinct tags in the green-beard experiment.
+pub const TAG_COUNT: u8 = 2;
+/// Probability that a child's tag or green-beard gene differs from its first parent's.
+const TAG_MUTATION_RATE: f64 = 0.05;
+
+/// Composition of the population in one generation of the green-beard experiment.
+#[derive(Clone)]
+pub struct TagRow {
+    /// Fraction of individuals carrying the green-beard gene.
+    pub green_beards: f64,
+    /// Fraction of individuals carrying the most common tag.
+    pub majority_tag: f64,
+    /// Fraction of the population's decisions that were to cooperate.
+    pub cooperation: f64,
+}
+
+/// Evolves organisms that carry a tag and a green-beard gene next to their strategy genome,
+/// where the green-beard gene makes them cooperate with anyone showing the same tag.
+pub fn green_beard(setup: &Setup) -> Vec<TagRow> {
+    let mut rows = Vec::new();
+    setup.evolve(
+        |g| (g % TAG_COUNT, random::rng().gen_bool(0.5)),
+        |game, traits| {
+            let (tags, beards): (Vec<u8>, Vec<bool>) = traits.iter().copied().unzip();
+            game.with_tags(&tags, &beards, TAG_COUNT)
+        },
+        |game, _, traits| {
+            let n = traits.len() as f64;
+            let majority =
+                (0..TAG_COUNT).map(|t| traits.iter().filter(|&&(tag, _)| tag == t).count()).max();
+            rows.push(TagRow {
+                green_beards: traits.iter().filter(|&&(_, beard)| beard).count() as f64 / n,
+                majority_tag: majority.unwrap_or_default() as f64 / n,
+                cooperation: game.cooperation_rate(),
+            });
+        },
+        |(tag, beard)| {
+            let mut rng = random::rng();
+            let tag = if rng.gen_bool(TAG_MUTATION_RATE) {
+                rng.gen_range(0..TAG_COUNT)
+            } else {
+                tag
+            };
+            (tag, beard ^ rng.gen_bool(TAG_MUTATION_RATE))
+        },
+    );
+    rows
+}
+
+/// Total score of every evolved opponent of a tournament that has been run.
+fn opponent_totals(game: &Tournament) -> Vec<f64> {
+    game.matchups()
+        .iter()
+        .skip(game.player_count())
+        .map(|(_, games)| games.iter().map(|m| m.own_score as f64).sum())
+        .collect()
+}
+
+/// Number of steps between always defecting and always cooperating on the first move.
+pub const OPENING_LEVELS: u8 = 4;
+/// Probability that a child's opening level moves one step away from its first parent's.
+const OPENING_MUTATION_RATE: f64 = 0.1;
+
+/// Opening behaviour of the population in one generation of the mixed-opening experiment.
+#[derive(Clone)]
+pub struct OpeningRow {
+    /// Mean probability of cooperating on the first move.
+    pub opening: f64,
+    /// Fraction of individuals whose opening is mixed rather than always C or always D.
+    pub mixed: f64,
+    /// Fraction of the population's decisions that were to cooperate.
+    pub cooperation: f64,
+}
+
+/// Evolves organisms whose opening gene is a level between 0 and [`OPENING_LEVELS`] giving
+/// the probability of cooperating on the first move, instead of a fixed C or D bit. Like the
+/// opening bit it replaces, the level is inherited from the first parent in crossover, and
+/// mutation moves it one level up or down.
+pub fn mixed_openings(setup: &Setup) -> Vec<OpeningRow> {
+    let probability = |level: u8| level as f64 / OPENING_LEVELS as f64;
+    let mut rows = Vec::new();
+    setup.evolve(
+        |_| random::rng().gen_range(0..=OPENING_LEVELS),
+        |game, levels| {
+            game.with_openings(&levels.iter().map(|&l| probability(l)).collect::<Vec<_>>())
+        },
+        |game, _, levels| {
+            let n = levels.len() as f64;
+            rows.push(OpeningRow {
+                opening: levels.iter().map(|&l| probability(l)).sum::<f64>() / n,
+                mixed: levels.iter().filter(|&&l| l > 0 && l < OPENING_LEVELS).count() as f64 / n,
+                cooperation: game.cooperation_rate(),
+            });
+        },
+        |level| {
+            let mut rng = random::rng();
+            if !rng.gen_bool(OPENING_MUTATION_RATE) {
+                level
+            } else if level == 0 || (level < OPENING_LEVELS && rng.gen_bool(0.5)) {
+                level + 1
+            } else {
+                level - 1
+            }
+        },
+    );
+    rows
+}
+
+/// Probability that a child's punishment gene differs from its first parent's.
+const PUNISH_MUTATION_RATE: f64 = 0.05;
+
+/// Composition of the population in one generation of the punishment experiment.
+#[derive(Clone)]
+pub struct PunishRow {
+    /// Fraction of individuals carrying the punishment gene.
+    pub punishers: f64,
+    /// Fraction of the population's decisions that were to cooperate.
+    pub cooperation: f64,
+}
+
+/// Evolves organisms carrying a punishment gene next to their strategy genome, which makes
+/// them pay [`PUNISH_COST`] to fine a defecting opponent [`PUNISH_FINE`] after the round.
+pub fn punishment(setup: &Setup) -> Vec<PunishRow> {
+    let mut rows = Vec::new();
+    setup.evolve(
+        |_| random::rng().gen_bool(0.5),
+        |game, punishers| game.with_punishment(punishers, PUNISH_COST, PUNISH_FINE),
+        |game, _, punishers| {
+            rows.push(PunishRow {
+                punishers: share(punishers),
+                cooperation: game.cooperation_rate(),
+            });
+        },
+        |punishes| punishes ^ random::rng().gen_bool(PUNISH_MUTATION_RATE),
+    );
+    rows
+}
+
+/// Fraction of the individuals carrying a gene.
+fn share(genes: &[bool]) -> f64 {
+    genes.iter().filter(|&&g| g).count() as f64 / genes.len() as f64
+}
+
+/// Probability that a child's contract acceptance gene differs from its first parent's.
+const CONTRACT_MUTATION_RATE: f64 = 0.05;
+
+/// Composition of the population in one generation of the contract experiment.
+#[derive(Clone)]
+pub struct ContractRow {
+    /// Fraction of individuals that accept contracts.
+    pub signers: f64,
+    /// Fraction of the decisions made under a contract that broke it.
+    pub breaches: f64,
+    /// Fraction of the population's decisions that were to cooperate.
+    pub cooperation: f64,
+}
+
+/// Evolves organisms carrying a contract acceptance gene next to their strategy genome. When
+/// both sides of a match accept, they are bound to cooperate for the first
+/// [`CONTRACT_ROUNDS`] rounds, and every defection within them costs the defector
+/// [`CONTRACT_PENALTY`], paid to the other side.
+pub fn contracts(setup: &Setup) -> Vec<ContractRow> {
+    let mut rows = Vec::new();
+    setup.evolve(
+        |_| random::rng().gen_bool(0.5),
+        |game, signers| game.with_contracts(signers, CONTRACT_ROUNDS, CONTRACT_PENALTY),
+        |game, _, signers| {
+            rows.push(ContractRow {
+                signers: share(signers),
+                breaches: game.breach_rate(),
+                cooperation: game.cooperation_rate(),
+            });
+        },
+        |signs| signs ^ random::rng().gen_bool(CONTRACT_MUTATION_RATE),
+    );
+    rows
+}
+
+/// State of the population in one generation of the memory-depth experiment.
+#[derive(Clone)]
+pub struct DepthRow {
+    /// Score of the fittest individual per round and fixed player.
+    pub best: f64,
+    /// Fraction of the population's decisions that were to cooperate.
+    pub cooperation: f64,
+    /// Genes of the fittest individual, as C and D.
+    pub winner: String,
+}
+
+/// Evolves a population of genomes remembering as many previous rounds as the genetic
+/// algorithm of `setup` says against the fixed players, starting from random genomes unless
+/// they remember one round.
+pub fn memory_depth(setup: &Setup) -> Vec<DepthRow> {
+    let Setup {
+        game_count,
+        genetic,
+        ..
+    } = *setup;
+    let mut population = genetic.initial_genomes();
+    let mut rows = Vec::new();
+    for _ in 0..setup.gen_count {
+        let payoffs = PayoffMatrix::new(setup.payoffs);
+        let game = Tournament::with_genomes(game_count, payoffs, genetic.depth, &population, &[]);
+        let mut game = setup.configure(game);
+        game.run();
+        let Some((fittest, best)) = game.select_fittest_and_bestscore(genetic.generation_size)
+        else {
+            break;
+        };
+        rows.push(DepthRow {
+            best: best as f64 / (game_count * game.player_count() as u32).max(1) as f64,
+            cooperation: game.cooperation_rate(),
+            winner: genome_label(&fittest[0]),
+        });
+        population = breed(&game, &genetic);
+    }
+    rows
+}
+
+/// Mutation rate and behaviour of the population in one generation of the self-adaptive
+/// mutation experiment.
+#[derive(Clone)]
+pub struct AdaptiveRow {
+    /// Mean mutation rate encoded by the rate genes.
+    pub mutation_rate: f64,
+    /// Fraction of the population playing its most common strategy genome.
+    pub consensus: f64,
+    /// Fraction of the population's decisions that were to cooperate.
+    pub cooperation: f64,
+}
+
+/// Evolves organisms carrying a rate gene next to their strategy genome, an index into
+/// [`MUTATION_RATES`] giving the probability that their children's strategy genomes mutate
+/// when bred by the genetic algorithm. The rate gene is inherited from the first parent as
+/// [`inherit_rate_gene`] says, so that the mutation rate itself evolves.
+pub fn self_adaptive_mutation(setup: &Setup) -> Vec<AdaptiveRow> {
+    let mut rows = Vec::new();
+    setup.evolve(
+        |_| random::rng().gen_range(0..MUTATION_RATES.len() as u8),
+        |game, genes| game.with_rate_genes(genes),
+        |game, population, genes| {
+            let n = genes.len() as f64;
+            let mut counts = [0; GENOME_COUNT];
+            for &genome in population {
+                counts[genome as usize] += 1;
+            }
+            rows.push(AdaptiveRow {
+                mutation_rate: genes.iter().map(|&g| MUTATION_RATES[g as usize]).sum::<f64>() / n,
+                consensus: *counts.iter().max().unwrap() as f64 / n,
+                cooperation: game.cooperation_rate(),
+            });
+        },
+        inherit_rate_gene,
+    );
+    rows
+}
+
+/// Probability that each of a child's talk genes differs from its first parent's.
+const TALK_MUTATION_RATE: f64 = 0.05;
+
+/// Signalling behaviour of the population in one generation of the cheap-talk experiment.
+#[derive(Clone)]
+pub struct TalkRow {
+    /// Fraction of individuals announcing cooperation.
+    pub cooperative_signals: f64,
+    /// Fraction of individuals opening with cooperation when cooperation is announced.
+    pub trusting: f64,
+    /// Fraction of individuals whose opening depends on the other's signal.
+    pub responsive: f64,
+    /// Fraction of the population's decisions that were to cooperate.
+    pub cooperation: f64,
+}
+
+/// Evolves organisms carrying talk genes next to their strategy genome, which choose the
+/// non-binding signal announced before every match and the opening move in reply to the
+/// other's signal. Mutation flips every talk gene independently.
+pub fn cheap_talk(setup: &Setup) -> Vec<TalkRow> {
+    let mut rows = Vec::new();
+    setup.evolve(
+        |_| random::rng().gen_range(0..TALK_COUNT),
+        |game, talk| game.with_signals(talk),
+        |game, _, talk| {
+            let share = |f: fn((Decision, Decision, Decision)) -> bool| {
+                talk.iter().filter(|&&t| f(talk_genes(t))).count() as f64 / talk.len() as f64
+            };
+            rows.push(TalkRow {
+                cooperative_signals: share(|(signal, _, _)| signal == Decision::Cooperate),
+                trusting: share(|(_, on_c, _)| on_c == Decision::Cooperate),
+                responsive: share(|(_, on_c, on_d)| on_c != on_d),
+                cooperation: game.cooperation_rate(),
+            });
+        },
+        |talk| {
+            (0..3).fold(talk, |t, bit| {
+                t ^ ((random::rng().gen_bool(TALK_MUTATION_RATE) as u8) << bit)
+            })
+        },
+    );
+    rows
+}
+
+/// Kin composition and behaviour of one generation of the kin-selection experiment.
+#[derive(Clone)]
+pub struct KinRow {
+    /// Fraction of pairings between individuals descending from the same founder.
+    pub kin_pairs: f64,
+    /// Number of founder lineages still present.
+    pub lineages: usize,
+    /// Expected cooperation rate over all pairings.
+    pub cooperation: f64,
+}
+
+/// Evolves a population whose members play each other, where an individual's fitness also
+/// counts a fraction `relatedness` of the payoff of every opponent descending from the same
+/// founder, so that Hamilton's rule r·b > c decides whether cooperation spreads.
+///
+/// Ancestry is tracked by letting every child inherit the founder of its first parent. Payoffs
+/// are the Markov chain expectations of [`analysis::analyse_rounds`] under the rules of
+/// `setup`, and the population breeds with its genetic algorithm.
+pub fn kin_selection(setup: &Setup, relatedness: f64) -> Vec<KinRow> {
+    let payoffs = analysis::payoffs_of(PayoffMatrix::new(setup.payoffs));
+    let strategies: Vec<MemoryOne> = (0..32).map(MemoryOne::from_genome).collect();
+    let table: Vec<Vec<((f64, f64), f64)>> = strategies
+        .iter()
+        .map(|a| {
+            strategies
+                .iter()
+                .map(|b| {
+                    let pair = analysis::analyse_rounds(a, b, &payoffs, setup.game_count);
+                    (pair.payoffs, pair.cooperation.0)
+                })
+                .collect()
+        })
+        .collect();
+
+    // (genome, founder) of every individual.
+    let mut organisms: Vec<(u8, usize)> =
+        setup.genetic.initial_population().iter().copied().zip(0..).collect();
+    let mut rows = Vec::new();
+    for _ in 0..setup.gen_count {
+        let n = organisms.len();
+        let mut fitness = vec![0.0; n];
+        let (mut kin_pairs, mut cooperation) = (0, 0.0);
+        for (i, &(a, founder_a)) in organisms.iter().enumerate() {
+            for (j, &(b, founder_b)) in organisms.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                let ((own, other), cooperated) = table[a as usize][b as usize];
+                let r = if founder_a == founder_b {
+                    kin_pairs += 1;
+                    relatedness
+                } else {
+                    0.0
+                };
+                fitness[i] += own + r * other;
+                cooperation += cooperated;
+            }
+        }
+        let mut founders: Vec<usize> = organisms.iter().map(|o| o.1).collect();
+        founders.sort();
+        founders.dedup();
+        let pairs = (n * n.saturating_sub(1)).max(1) as f64;
+        rows.push(KinRow {
+            kin_pairs: kin_pairs as f64 / pairs,
+            lineages: founders.len(),
+            cooperation: cooperation / pairs,
+        });
+
+        let (genomes, founders): (Vec<Genome>, Vec<usize>) =
+            organisms.iter().map(|&(g, founder)| (number_to_genome(g), founder)).unzip();
+        let (next, origins): (Vec<Genome>, Vec<Origin>) =
+            breed_population(&genomes, &fitness, &setup.genetic).into_iter().unzip();
+        let founders = inherit_traits(&founders, &origins, |founder| founder);
+        organisms = next.iter().map(genome_to_number).zip(founders).collect();
+    }
+    rows
+}
+
+/// Types of the loner experiment, in the order of its shares.
+pub const LONER_TYPES: [&str; 3] = ["Cooperators", "Defectors", "Loners"];
+/// Number of individuals sampled into every public goods group.
+const GROUP_SIZE: usize = 5;
+/// Factor the pooled contributions are multiplied by before being shared.
+const GOODS_MULTIPLIER: f64 = 3.0;
+/// Payoff of abstaining, or of being the only participant of a group.
+const LONER_PAYOFF: f64 = 1.0;
+/// Length of a replicator step.
+const LONER_STEP: f64 = 0.05;
+
+/// Expected payoffs of cooperators, defectors and loners in the optional public goods game,
+/// averaging over the composition of the other members of a group.
+fn optional_goods_payoffs(shares: [f64; 3]) -> [f64; 3] {
+    let others = GROUP_SIZE - 1;
+    let factorial = |n: usize| (1..=n).product::<usize>() as f64;
+    let mut payoffs = [0.0, 0.0, LONER_PAYOFF];
+    for cooperators in 0..=others {
+        for defectors in 0..=others - cooperators {
+            let loners = others - cooperators - defectors;
+            let probability = factorial(others)
+                / (factorial(cooperators) * factorial(defectors) * factorial(loners))
+                * shares[0].powi(cooperators as i32)
+                * shares[1].powi(defectors as i32)
+                * shares[2].powi(loners as i32);
+            // Payoff of a cooperator and a defector joining these other members.
+            let share =
+                |c: usize, participants: usize| GOODS_MULTIPLIER * c as f64 / participants as f64;
+            let participants = cooperators + defectors + 1;
+            let (cooperator, defector) = if participants == 1 {
+                (LONER_PAYOFF, LONER_PAYOFF)
+            } else {
+                (
+                    share(cooperators + 1, participants) - 1.0,
+                    share(cooperators, participants),
+                )
+            };
+            payoffs[0] += probability * cooperator;
+            payoffs[1] += probability * defector;
+        }
+    }
+    payoffs
+}
+
+/// Integrates the replicator dynamics of cooperators, defectors and loners in the optional
+/// public goods game, where abstaining earns a fixed payoff. Loners invade defectors,
+/// cooperators invade loners once groups become small, and defectors invade cooperators,
+/// so the shares of [`LONER_TYPES`] returned after every step cycle around.
+///
+/// Groups of more than two are needed for the cycle, since in pairwise games cooperators
+/// cannot outperform loners while defectors are around.
+pub fn loner_dynamics(steps: u32) -> Vec<[f64; 3]> {
+    let mut shares = [0.5, 0.2, 0.3];
+    let mut rows = vec![shares];
+    for _ in 0..steps {
+        let payoffs = optional_goods_payoffs(shares);
+        let mean: f64 = (0..3).map(|k| shares[k] * payoffs[k]).sum();
+        shares = std::array::from_fn(|k| {
+            (shares[k] * (1.0 + LONER_STEP * (payoffs[k] - mean))).max(1e-9)
+        });
+        let total: f64 = shares.iter().sum();
+        shares = shares.map(|x| x / total);
+        rows.push(shares);
+    }
+    rows
+}
+
+/// Repetitions of the classic round-robin tournament run from the GUI.
+pub const ROUND_ROBIN_REPETITIONS: u32 = 5;
+
+/// Rounds per match in the round-robin run from the command line, as in Axelrod's first tournament.
+pub const DUEL_ROUNDS: u32 = 200;
+
+/// Default number of rounds of a Swiss-system tournament, enough for a clear winner among the
+/// named strategies.
+pub const SWISS_ROUNDS: u32 = 5;
+
+/// Standing of one named strategy in the classic round-robin tournament.
+#[derive(Clone)]
+pub struct RoundRobinRow {
+    pub name: String,
+    /// Mean payoff per round over every opponent and repetition.
+    pub score: f64,
+    /// Standard deviation of that mean payoff across instances and repetitions.
+    pub spread: f64,
+    /// Matches in which the strategy scored more than its opponent, over all repetitions.
+    pub wins: u32,
+    /// Matches in which the strategy scored less than its opponent, over all repetitions.
+    pub losses: u32,
+}
+
+/// Plays Axelrod's tournament among the named strategies only, under the game of `setup`:
+/// every strategy meets every other one and its own twin, repeated `repetitions` times. With
+/// `copies[s]` instances of strategy `s` every instance meets every other one, and the row of
+/// a strategy aggregates all its instances. Every instance of `s` pays `costs[s]` per round.
+/// Returns the ranking table, highest mean payoff first.
+pub fn round_robin(
+    setup: &Setup,
+    repetitions: u32,
+    copies: &[usize],
+    costs: &[i32],
+) -> Vec<RoundRobinRow> {
+    let game_count = setup.game_count;
+    let mut rows: Vec<RoundRobinRow> = Vec::new();
+    let mut means: Vec<Vec<f64>> = Vec::new();
+    let costs: Vec<i32> =
+        copies.iter().zip(costs).flat_map(|(&c, &cost)| std::iter::repeat_n(cost, c)).collect();
+    for _ in 0..repetitions {
+        let mut game = setup.roster(copies).with_costs(&costs, &costs);
+        game.run();
+        for (name, games) in game.matchups().into_iter().take(game.player_count()) {
+            let total: i32 = games.iter().map(|m| m.own_score).sum();
+            let mean = total as f64 / (game_count as usize * games.len()) as f64;
+            let wins = games.iter().filter(|m| m.own_score > m.other_score).count() as u32;
+            let losses = games.iter().filter(|m| m.own_score < m.other_score).count() as u32;
+            match rows.iter().position(|row| row.name == name) {
+                Some(k) => {
+                    rows[k].wins += wins;
+                    rows[k].losses += losses;
+                    means[k].push(mean);
+                }
+                None => {
+                    rows.push(RoundRobinRow {
+                        name,
+                        score: 0.0,
+                        spread: 0.0,
+                        wins,
+                        losses,
+                    });
+                    means.push(vec![mean]);
+                }
+            }
+        }
+    }
+    summarize_repetitions(rows, &means)
+}
+
+/// Plays a Swiss-system tournament among the named strategies chosen in `setup`, under its
+/// game: in each of `rounds` rounds,
+/// strategies are paired with the one closest to them by running mean payoff that they have
+/// not met yet, so that far fewer matches are needed than in [`round_robin`]. With an odd
+/// number of strategies the lowest placed one sits out each round. Repeated `repetitions`
+/// times and returned as a ranking table, highest mean payoff first.
+pub fn swiss(setup: &Setup, rounds: u32, repetitions: u32) -> Vec<RoundRobinRow> {
+    let game_count = setup.game_count;
+    let mut rows: Vec<RoundRobinRow> = Vec::new();
+    let mut means: Vec<Vec<f64>> = Vec::new();
+    for _ in 0..repetitions {
+        let mut game = setup.roster(&setup.strategies.map(usize::from)).unplayed();
+        let n = game.player_count();
+        // (total payoff, matches played, wins, losses) of every strategy so far.
+        let mut standings = vec![(0, 0, 0, 0); n];
+        let mut met = vec![vec![false; n]; n];
+        let mean = |(total, played, _, _): (i32, u32, u32, u32)| {
+            total as f64 / (played.max(1) * game_count) as f64
+        };
+        for _ in 0..rounds {
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_by(|&a, &b| mean(standings[b]).total_cmp(&mean(standings[a])));
+            while order.len() > 1 {
+                let a = order.remove(0);
+                let k = order.iter().position(|&b| !met[a][b]).unwrap_or(0);
+                let b = order.remove(k);
+                met[a][b] = true;
+                met[b][a] = true;
+                let (b_score, a_score) = game.play(b, a);
+                for (x, own, other) in [(a, a_score, b_score), (b, b_score, a_score)] {
+                    let (total, played, wins, losses) = &mut standings[x];
+                    *total += own;
+                    *played += 1;
+                    *wins += (own > other) as u32;
+                    *losses += (own < other) as u32;
+                }
+            }
+        }
+        for (k, (name, _)) in game.matchups().into_iter().take(n).enumerate() {
+            let (_, _, wins, losses) = standings[k];
+            match rows.get_mut(k) {
+                Some(row) => {
+                    row.wins += wins;
+                    row.losses += losses;
+                    means[k].push(mean(standings[k]));
+                }
+                None => {
+                    rows.push(RoundRobinRow {
+                        name,
+                        score: 0.0,
+                        spread: 0.0,
+                        wins,
+                        losses,
+                    });
+                    means.push(vec![mean(standings[k])]);
+                }
+            }
+        }
+    }
+    summarize_repetitions(rows, &means)
+}
+
+/// One match of the single-elimination bracket.
+#[derive(Clone)]
+pub struct BracketMatch {
+    /// Name of the higher seed.
+    pub first: String,
+    /// Name of the lower seed, or `None` when the higher seed advances without playing.
+    pub second: Option<String>,
+    /// Scores of the first and second strategy in this match.
+    pub scores: (i32, i32),
+    /// Whether the first strategy advances, which it does on a tie.
+    pub first_wins: bool,
+}
+
+/// Plays a single-elimination tournament among the named strategies chosen in `setup`, under
+/// its game, seeded in the order they are registered so that the top seeds can only meet in the late rounds. Byes fill the
+/// bracket up to a power of two and go to the top seeds. Returns the matches of every round,
+/// the final last.
+pub fn knockout(setup: &Setup) -> Vec<Vec<BracketMatch>> {
+    let mut game = setup.roster(&setup.strategies.map(usize::from)).unplayed();
+    let names: Vec<String> = game.matchups().into_iter().map(|(name, _)| name).collect();
+    let n = game.player_count();
+    let mut order = vec![0];
+    while order.len() < n {
+        let size = 2 * order.len();
+        order = order.iter().flat_map(|&s| [s, size - 1 - s]).collect();
+    }
+    let mut entrants: Vec<Option<usize>> =
+        order.into_iter().map(|s| (s < n).then_some(s)).collect();
+    let mut rounds = Vec::new();
+    while entrants.len() > 1 {
+        let mut matches = Vec::new();
+        let mut winners = Vec::new();
+        for pair in entrants.chunks(2) {
+            let (a, b) = match (pair[0], pair[1]) {
+                (Some(a), Some(b)) => (a.min(b), Some(a.max(b))),
+                (Some(a), None) | (None, Some(a)) => (a, None),
+                (None, None) => {
+                    winners.push(None);
+                    continue;
+                }
+            };
+            let scores = match b {
+                Some(b) => {
+                    let (b_score, a_score) = game.play(b, a);
+                    (a_score, b_score)
+                }
+                None => (0, 0),
+            };
+            let first_wins = scores.0 >= scores.1;
+            winners.push(Some(if first_wins { a } else { b.unwrap_or(a) }));
+            matches.push(BracketMatch {
+                first: names[a].clone(),
+                second: b.map(|b| names[b].clone()),
+                scores,
+                first_wins,
+            });
+        }
+        rounds.push(matches);
+        entrants = winners;
+    }
+    rounds
+}
+
+/// Repeated matches between two named strategies, as reported by the `duel` command.
+pub struct DuelReport {
+    /// Scores of both strategies in every repetition, first strategy first.
+    pub scores: Vec<(i32, i32)>,
+    /// Fraction of rounds in which each strategy cooperated, over all repetitions.
+    pub cooperation: (f64, f64),
+    /// Moves of both strategies in every round of the first repetition.
+    pub transcript: Vec<(Decision, Decision)>,
+    /// Expected payoff per round of both strategies when both are memory-one.
+    pub analytic: Option<(f64, f64)>,
+}
+
+/// Plays `repetitions` matches of `rounds` rounds between the named strategies `a` and `b`,
+/// indices into [`strategy_names`], each with fresh memories, under `payoffs`.
+pub fn duel(
+    a: usize,
+    b: usize,
+    rounds: u32,
+    repetitions: u32,
+    payoffs: PayoffMatrix,
+) -> DuelReport {
+    let mut scores = Vec::new();
+    let mut transcripts = Vec::new();
+    for _ in 0..repetitions.max(1) {
+        let mut game = Tournament::named(rounds, payoffs).unplayed();
+        let (b_score, a_score) = game.play(b, a);
+        scores.push((a_score, b_score));
+        transcripts.push(game.transcript(b, a));
+    }
+    let moves = transcripts.iter().flatten();
+    let total = moves.clone().count().max(1) as f64;
+    let rate = |cooperated: usize| cooperated as f64 / total;
+    let cooperation = (
+        rate(moves.clone().filter(|(m, _)| *m == Decision::Cooperate).count()),
+        rate(moves.filter(|(_, m)| *m == Decision::Cooperate).count()),
+    );
+    let analytic = memory_one_rates(a).zip(memory_one_rates(b)).map(|(p, q)| {
+        let payoffs = analysis::payoffs_of(payoffs);
+        analysis::analyse_rounds(&MemoryOne(p), &MemoryOne(q), &payoffs, rounds).payoffs
+    });
+    DuelReport {
+        scores,
+        cooperation,
+        transcript: transcripts.swap_remove(0),
+        analytic,
+    }
+}
+
+/// Noise with which the variants of the named strategies are generated by default.
+pub const VARIANT_NOISE: f64 = 0.05;
+
+/// Scores of a named strategy and of its noisy variant in the same round-robin.
+#[derive(Clone)]
+pub struct VariantRow {
+    pub name: String,
+    /// Mean payoff per round of the strategy as is.
+    pub clean: f64,
+    /// Mean payoff per round of its variant with execution noise.
+    pub noisy: f64,
+}
+
+/// Plays a round-robin among every named strategy and a noisy variant of each, which flips its
+/// decisions with probability `noise`, repeated `repetitions` times. Returns the scores of both
+/// versions of every strategy, the one losing the least to noise first.
+pub fn noisy_variants(game_count: u32, noise: f64, repetitions: u32) -> Vec<VariantRow> {
+    let mut rows: Vec<VariantRow> = strategy_names()
+        .into_iter()
+        .map(|name| VariantRow {
+            name: name.to_string(),
+            clean: 0.0,
+            noisy: 0.0,
+        })
+        .collect();
+    for _ in 0..repetitions {
+        let mut game = Tournament::noisy_variants(game_count, prisoners_dillemma_rules, noise);
+        game.run();
+        for (k, (_, games)) in game.matchups().into_iter().take(game.player_count()).enumerate() {
+            let total: i32 = games.iter().map(|m| m.own_score).sum();
+            let mean =
+                total as f64 / (game_count as usize * games.len() * repetitions as usize) as f64;
+            let row = &mut rows[k / 2];
+            if k % 2 == 0 {
+                row.clean += mean;
+            } else {
+                row.noisy += mean;
+            }
+        }
+    }
+    rows.sort_by(|a, b| (b.noisy - b.clean).total_cmp(&(a.noisy - a.clean)));
+    rows
+}
+
+/// Fills in the mean payoff of every row and its spread over the repetitions from `means`,
+/// and sorts the rows by mean payoff, highest first.
+fn summarize_repetitions(mut rows: Vec<RoundRobinRow>, means: &[Vec<f64>]) -> Vec<RoundRobinRow> {
+    for (row, means) in rows.iter_mut().zip(means) {
+        let n = means.len() as f64;
+        row.score = means.iter().sum::<f64>() / n;
+        row.spread = (means.iter().map(|m| (m - row.score).powi(2)).sum::<f64>() / n).sqrt();
+    }
+    rows.sort_by(|a, b| b.score.total_cmp(&a.score));
+    rows
+}
+
+/// Ranks the fixed players of a tournament that has been run by their total score.
+fn player_ranks(game: &Tournament) -> Vec<(String, usize)> {
+    let totals: Vec<(String, i32)> = game
+        .matchups()
+        .into_iter()
+        .take(game.player_count())
+        .map(|(name, games)| (name, games.iter().map(|m| m.own_score).sum()))
+        .collect();
+    totals
+        .iter()
+        .map(|(name, total)| {
+            (
+                name.clone(),
+                1 + totals.iter().filter(|(_, other)| other > total).count(),
+            )
+        })
+        .collect()
+}
@@ -1,31 +1,555 @@
+use crate::random::{self, rng};
 use grid::Grid;
-use rand::distributions::{Bernoulli, Distribution};
-use rand::Rng;
-use std::collections::HashMap;
+use rand::distributions::{Bernoulli, Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::ops::Not;
+use std::path::Path;
+use std::{fs, io};
 
 /// Outcome scores for both players based on their decisions in a game iteration.
-type RewardFunc = fn(&Decision, &Decision) -> (i32, i32);
+pub type RewardFunc = fn(&Decision, &Decision) -> (i32, i32);
 
-/// boolean array of length 5 used to compose [`DecisionTable`]'s in a softcoded way
-type Genome = Box<[bool]>;
+/// Scores of a [`RewardFunc`] after CC, CD, DC and DD, first player's move and score first.
+pub type PayoffTable = [(i32, i32); 4];
 
+/// Tabulates the scores `rules` gives for every combination of moves.
+pub fn payoff_table(rules: RewardFunc) -> PayoffTable {
+    PayoffMatrix::from(rules).outcomes()
+}
+
+/// Payoffs of a game built at runtime, which tournaments accept wherever a [`RewardFunc`] is
+/// accepted.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PayoffMatrix {
+    /// Scores after CC, CD, DC and DD, as in a [`PayoffTable`].
+    outcomes: PayoffTable,
+}
+
+impl PayoffMatrix {
+    /// Game with the given scores after CC, CD, DC and DD, first player's move and score first.
+    pub fn new(outcomes: PayoffTable) -> Self {
+        PayoffMatrix { outcomes }
+    }
+
+    /// Symmetric game in which mutual cooperation pays `reward`, mutual defection pays
+    /// `punishment`, and a defector facing a cooperator receives `temptation` while the
+    /// cooperator receives `sucker`.
+    pub fn symmetric(reward: i32, sucker: i32, temptation: i32, punishment: i32) -> Self {
+        PayoffMatrix::new([
+            (reward, reward),
+            (sucker, temptation),
+            (temptation, sucker),
+            (punishment, punishment),
+        ])
+    }
+
+    /// Like [`PayoffMatrix::symmetric`], but only if the payoffs make a prisoner's dilemma:
+    /// T > R > P > S, so that defecting pays whatever the other does, and 2R > T + S, so
+    /// that taking turns exploiting each other does not beat mutual cooperation.
+    pub fn prisoners_dilemma(
+        reward: i32,
+        sucker: i32,
+        temptation: i32,
+        punishment: i32,
+    ) -> Result<Self, String> {
+        if !(temptation > reward && reward > punishment && punishment > sucker) {
+            return Err(format!(
+                "expected T > R > P > S, got T={temptation}, R={reward}, P={punishment}, \
+                 S={sucker}"
+            ));
+        }
+        if 2 * reward <= temptation + sucker {
+            return Err(format!(
+                "expected 2R > T + S, got 2R={} and T + S={}",
+                2 * reward,
+                temptation + sucker
+            ));
+        }
+        Ok(PayoffMatrix::symmetric(
+            reward, sucker, temptation, punishment,
+        ))
+    }
+
+    /// Scores after CC, CD, DC and DD.
+    pub fn outcomes(self) -> PayoffTable {
+        self.outcomes
+    }
+
+    /// Scores of both players when the first plays `a` and the second `b`.
+    pub fn payoff(self, a: Decision, b: Decision) -> (i32, i32) {
+        self.outcomes[context_index(Some(a), Some(b)) - 1]
+    }
+}
+
+impl<F: Fn(&Decision, &Decision) -> (i32, i32)> From<F> for PayoffMatrix {
+    fn from(rules: F) -> Self {
+        use Decision::*;
+        PayoffMatrix::new(
+            [
+                (Cooperate, Cooperate),
+                (Cooperate, Defect),
+                (Defect, Cooperate),
+                (Defect, Defect),
+            ]
+            .map(|(a, b)| rules(&a, &b)),
+        )
+    }
+}
+
+/// boolean array of length 5 used to compose a [`GenomeStrategy`] in a softcoded way
+pub type Genome = Box<[bool]>;
+
+/// Position of a participant on its side of a [`Tournament`], by which the participants on the
+/// other side index their memories of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PlayerId(usize);
+
+/// Participant of a [`Tournament`], with its strategy and its memory of every other participant.
+#[derive(Serialize, Deserialize)]
 pub struct Player {
-    /// Stores own previous move towards players keyed by a String, values initialised to None.
-    prev_move_self: HashMap<String, Option<Decision>>,
-    /// Stores other players decisions towards self, same storage.
-    prev_move_other: HashMap<String, Option<Decision>>,
-    /// Strategy function.
-    strategy: DecisionTable,
+    /// Own previous move towards every participant on the other side, by [`PlayerId`], `None`
+    /// before the first round.
+    prev_move_self: Vec<Option<Decision>>,
+    /// Other participants' previous moves towards self, indexed like the above.
+    prev_move_other: Vec<Option<Decision>>,
+    /// (own move, other's move as remembered) of every round so far, indexed like the above.
+    history: Vec<Vec<(Decision, Decision)>>,
+    /// Number of rounds at the start of the history that are forgotten and no longer reach the
+    /// strategy, indexed like the above; the transcript keeps them.
+    forgotten: Vec<usize>,
+    /// Own instance of the strategy for every other participant, indexed like the above, so
+    /// that state kept by the strategy belongs to one matchup.
+    #[serde(serialize_with = "serialize_strategies")]
+    #[serde(deserialize_with = "deserialize_strategies")]
+    strategies: Vec<Box<dyn Strategy>>,
     /// Name of used player strategy.
     strategy_name: String,
+    /// Label of the participant, unique even when several share a strategy name.
+    id: String,
+    /// (times cooperated, times decided) per context, indexed like a [`Genome`].
+    responses: [(u32, u32); 5],
+    /// Heritable marker visible to every other participant.
+    tag: u8,
+    /// Whether to cooperate unconditionally with participants carrying the same tag.
+    green_beard: bool,
+    /// Public image score, raised by every cooperation and lowered by every defection.
+    image: i32,
+    /// Punishment gene: whether to punish the other after every round in which it defected,
+    /// whatever the strategy decides.
+    punishment_gene: bool,
+    /// Flat cost subtracted from the payoff of every round played, such as a memory cost.
+    round_cost: i32,
+    /// Probability that this participant's own decisions are flipped before being played, on
+    /// top of the tournament's execution noise.
+    tremble: f64,
+    /// Probability of cooperating on the first move, overriding the strategy's opening.
+    opening: Option<f64>,
+    /// Resources left to pay for cooperating with, or `None` when cooperation is free.
+    budget: Option<i32>,
+    /// Whether to accept a contract to cooperate at the start of every match.
+    signs_contracts: bool,
+    /// Probability of forgetting the previous moves against an opponent before each round.
+    forgetting: f64,
+    /// Non-binding signal announced to the other before every match, if any.
+    signal: Option<Decision>,
+    /// First move in reply to the other's signal of cooperation and of defection, replacing
+    /// the strategy's opening when the other sent a signal.
+    signal_response: Option<(Decision, Decision)>,
+    /// Probability that the children of this participant have a gene flipped, given by its
+    /// rate gene in place of the genetic algorithm's, if it carries one.
+    mutation_rate: Option<f64>,
+}
+
+impl Player {
+    /// Decides on the next move against `other`, known as `other_id`, given own and other's
+    /// previous move, or returns `None` to abstain from the round.
+    fn decide(
+        &mut self,
+        other: &Player,
+        other_id: PlayerId,
+        own_pm: Option<Decision>,
+        other_pm: Option<Decision>,
+    ) -> Option<Decision> {
+        if self.green_beard && self.tag == other.tag {
+            return Some(Decision::Cooperate);
+        }
+        if let (Some((on_c, on_d)), Some(signal), None, None) =
+            (self.signal_response, other.signal, own_pm, other_pm)
+        {
+            return Some(match signal {
+                Decision::Cooperate => on_c,
+                Decision::Defect => on_d,
+            });
+        }
+        if let (Some(p), None, None) = (self.opening, own_pm, other_pm) {
+            return Some(if rng().gen_bool(p) {
+                Decision::Cooperate
+            } else {
+                Decision::Defect
+            });
+        }
+        let context = Context {
+            own_pm,
+            other_pm,
+            history: &self.history[other_id.0][self.forgotten[other_id.0]..],
+            own_image: self.image,
+            other_image: other.image,
+        };
+        self.strategies[other_id.0].decide(&context)
+    }
+
+    /// Whether to punish the participant `other` for its move `decision` in the round just
+    /// played, by the punishment gene or as the strategy decides.
+    fn punishes(&self, other: PlayerId, decision: Decision) -> bool {
+        (self.punishment_gene && decision == Decision::Defect)
+            || self.strategies[other.0].punishes(decision)
+    }
+
+    /// Rounds played against the participant `other` that are still remembered.
+    fn memory(&self, other: PlayerId) -> &[(Decision, Decision)] {
+        &self.history[other.0][self.forgotten[other.0]..]
+    }
+
+    /// Clears the previous moves remembered against the participant `other` and the state its
+    /// strategy keeps about the matchup, so that the next round opens the matchup again.
+    fn start_afresh(&mut self, other: PlayerId) {
+        self.prev_move_self[other.0] = None;
+        self.prev_move_other[other.0] = None;
+        self.strategies[other.0].reset();
+    }
+
+    /// Whether to refuse playing `other`, because it defected in most of the last
+    /// [`OSTRACISM_WINDOW`] rounds between them or, when `by_image`, because its image is
+    /// too poor.
+    fn refuses(&self, other: &Player, other_id: PlayerId, by_image: bool) -> bool {
+        let history = self.memory(other_id);
+        let recent = &history[history.len().saturating_sub(OSTRACISM_WINDOW)..];
+        let defections = recent.iter().filter(|(_, d)| *d == Decision::Defect).count();
+        2 * defections > OSTRACISM_WINDOW || (by_image && other.image <= OSTRACISM_IMAGE)
+    }
+
+    /// Pays `cost` from the budget for `d` if it is a cooperation, returning the decision
+    /// actually played and whether it had to be turned into a defection for lack of budget.
+    fn afford(&mut self, d: Decision, cost: i32) -> (Decision, bool) {
+        match (&mut self.budget, d) {
+            (Some(budget), Decision::Cooperate) if *budget < cost => (Decision::Defect, true),
+            (Some(budget), Decision::Cooperate) => {
+                *budget -= cost;
+                (d, false)
+            }
+            _ => (d, false),
+        }
+    }
+
+    /// Adds a round's payoff to the budget, which cannot fall below zero.
+    fn replenish(&mut self, payoff: i32) {
+        if let Some(budget) = &mut self.budget {
+            *budget = (*budget + payoff).max(0);
+        }
+    }
+
+    /// Forgets every round played against the participant keyed `other` with probability
+    /// `self.forgetting`, as if the pair had not met yet.
+    fn forget(&mut self, other: PlayerId) {
+        if self.forgetting > 0.0 && rng().gen_bool(self.forgetting) {
+            self.start_afresh(other);
+            self.forgotten[other.0] = self.history[other.0].len();
+        }
+    }
+
+    /// Updates the public image after playing `d`.
+    fn observe(&mut self, d: Decision) {
+        let change = match d {
+            Decision::Cooperate => 1,
+            Decision::Defect => -1,
+        };
+        self.image = (self.image + change).clamp(-MAX_IMAGE, MAX_IMAGE);
+    }
+
+    /// Own and other's images as good (cooperate) or bad (defect) standing, in place of the
+    /// previous moves once the pair has met.
+    fn standing(
+        &self,
+        other: &Player,
+        pm: (Option<Decision>, Option<Decision>),
+    ) -> (Option<Decision>, Option<Decision>) {
+        let standing = |image: i32| {
+            if image >= 0 {
+                Decision::Cooperate
+            } else {
+                Decision::Defect
+            }
+        };
+        match pm {
+            (Some(_), Some(_)) => (Some(standing(self.image)), Some(standing(other.image))),
+            pm => pm,
+        }
+    }
+
+    /// Records the decision taken given own and other's previous move.
+    fn record_response(
+        &mut self,
+        own_pm: Option<Decision>,
+        other_pm: Option<Decision>,
+        d: Decision,
+    ) {
+        let (cooperated, total) = &mut self.responses[context_index(own_pm, other_pm)];
+        if let Decision::Cooperate = d {
+            *cooperated += 1;
+        }
+        *total += 1;
+    }
+}
+
+/// Position in a [`Genome`] of the gene responsible for the given previous moves.
+fn context_index(own_pm: Option<Decision>, other_pm: Option<Decision>) -> usize {
+    use Decision::*;
+    match (own_pm, other_pm) {
+        (None, None) => 0,
+        (Some(Cooperate), Some(Cooperate)) => 1,
+        (Some(Cooperate), Some(Defect)) => 2,
+        (Some(Defect), Some(Cooperate)) => 3,
+        (Some(Defect), Some(Defect)) => 4,
+        (Some(_), None) | (None, Some(_)) => unreachable!("impossible move combination"),
+    }
+}
+
+/// Usual cost of punishing, paid by the punisher, once punishment is switched on with
+/// [`Tournament::with_punishment`].
+pub const PUNISH_COST: i32 = 1;
+/// Usual fine inflicted on the punished participant.
+pub const PUNISH_FINE: i32 = 3;
+
+/// Whether the named strategy `s` punishes every defection against it, which costs nothing
+/// until punishment is switched on with [`Tournament::with_punishment`].
+pub fn is_punisher(s: usize) -> bool {
+    named_strategies().get(s).is_some_and(|s| s.punishes(Decision::Defect))
+}
+
+/// Default number of opening rounds of a match a contract binds both sides to cooperate in.
+pub const CONTRACT_ROUNDS: u32 = 10;
+/// Default penalty a participant pays the other for every defection breaking a contract.
+pub const CONTRACT_PENALTY: i32 = 3;
+
+/// Number of distinct combinations of talk genes, see [`talk_genes`].
+pub const TALK_COUNT: u8 = 8;
+
+/// Decodes talk genes into the signal sent and the opening moves in reply to a signal of
+/// cooperation and of defection, from the lowest bit up, a set bit meaning cooperate.
+pub fn talk_genes(talk: u8) -> (Decision, Decision, Decision) {
+    let bit = |n: u8| {
+        if talk >> n & 1 == 1 {
+            Decision::Cooperate
+        } else {
+            Decision::Defect
+        }
+    };
+    (bit(0), bit(1), bit(2))
 }
 
+/// Fixed players that accept every contract offered to them.
+const CONTRACT_SIGNERS: [&str; 3] = ["trusting\nt4t", "naive", "moralist"];
+
+/// Number of recent rounds considered when deciding whether to refuse a partner.
+const OSTRACISM_WINDOW: usize = 5;
+/// Image score at or below which participants are refused by everyone.
+const OSTRACISM_IMAGE: i32 = -3;
+
+/// Budget every participant starts with when cooperating costs resources.
+pub const INITIAL_BUDGET: i32 = 10;
+
+/// Bound on the magnitude of image scores.
+const MAX_IMAGE: i32 = 5;
+
 const GENOME_LENGTH: i32 = 5;
-const POPULATION_SIZE: usize = 20;
-const GENERATION_SIZE: usize = 10;
+/// Number of distinct genomes.
+pub const GENOME_COUNT: usize = 1 << GENOME_LENGTH;
+/// Most previous rounds an evolved genome can remember.
+pub const MAX_DEPTH: usize = 3;
+/// Default number of evolved individuals in a generation.
+pub const POPULATION_SIZE: usize = 20;
+/// Default number of the fittest individuals that survive and breed the next generation.
+pub const GENERATION_SIZE: usize = 10;
+/// Default probability that a child has one of its genes flipped.
+pub const MUTATION_RATE: f64 = 0.1;
+
+/// Mutation rates a rate gene can encode, indexed by the gene, see
+/// [`Tournament::with_rate_genes`].
+pub const MUTATION_RATES: [f64; 8] = [0.0, 0.01, 0.02, 0.05, 0.1, 0.2, 0.3, 0.5];
+
+/// Rate gene of a child whose first parent carries `gene`: with the rate the gene encodes, it
+/// moves one step up or down, so that the mutation rate itself evolves.
+pub fn inherit_rate_gene(gene: u8) -> u8 {
+    let mut rng = rng();
+    if !rng.gen_bool(MUTATION_RATES[gene as usize]) {
+        gene
+    } else if gene == 0 || (gene as usize + 1 < MUTATION_RATES.len() && rng.gen_bool(0.5)) {
+        gene + 1
+    } else {
+        gene - 1
+    }
+}
+
+/// How the individuals that survive and breed are chosen from a generation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Selection {
+    /// The fittest individuals, in order.
+    Truncation,
+    /// Draws with probability proportional to fitness above the least fit individual's.
+    RouletteWheel,
+    /// Draws with probability proportional to the number of individuals ranked below.
+    Rank,
+    /// The fittest of the given number of individuals drawn at random, for every draw.
+    Tournament(usize),
+}
+
+impl Selection {
+    /// Name of the operator, without its parameter.
+    pub fn label(self) -> &'static str {
+        match self {
+            Selection::Truncation => "Truncation",
+            Selection::RouletteWheel => "Roulette wheel",
+            Selection::Rank => "Rank",
+            Selection::Tournament(_) => "Tournament",
+        }
+    }
+}
+
+/// How a child's genes are taken from its two parents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Crossover {
+    /// Even genes from the first parent and odd genes from the second.
+    Alternating,
+    /// The first parent's genes up to a random point, the second's after it.
+    SinglePoint,
+    /// The second parent's genes between two random points, the first's elsewhere.
+    TwoPoint,
+    /// Every gene from either parent with equal probability.
+    Uniform,
+}
+
+impl Crossover {
+    /// Every crossover operator.
+    pub const ALL: [Crossover; 4] = [
+        Crossover::Alternating,
+        Crossover::SinglePoint,
+        Crossover::TwoPoint,
+        Crossover::Uniform,
+    ];
+
+    /// Name of the operator.
+    pub fn label(self) -> &'static str {
+        match self {
+            Crossover::Alternating => "Alternating",
+            Crossover::SinglePoint => "Single-point",
+            Crossover::TwoPoint => "Two-point",
+            Crossover::Uniform => "Uniform",
+        }
+    }
 
-fn number_to_genome(n: u8) -> Genome {
+    /// Child of two parent genomes of the same length, before any mutation.
+    pub fn cross(self, p1: &[bool], p2: &[bool]) -> Genome {
+        let mut rng = rng();
+        let len = p1.len();
+        let (start, end) = match self {
+            Crossover::Alternating | Crossover::Uniform => (0, 0),
+            Crossover::SinglePoint => (rng.gen_range(0..=len), len),
+            Crossover::TwoPoint => {
+                let (a, b) = (rng.gen_range(0..=len), rng.gen_range(0..=len));
+                (a.min(b), a.max(b))
+            }
+        };
+        p1.iter()
+            .zip(p2)
+            .enumerate()
+            .map(|(i, (&g1, &g2))| {
+                let second = match self {
+                    Crossover::Alternating => i % 2 == 1,
+                    Crossover::Uniform => rng.gen_bool(0.5),
+                    Crossover::SinglePoint | Crossover::TwoPoint => (start..end).contains(&i),
+                };
+                if second {
+                    g2
+                } else {
+                    g1
+                }
+            })
+            .collect()
+    }
+}
+
+/// Parameters of the genetic algorithm, chosen at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GeneticConfig {
+    /// Number of evolved individuals in a generation.
+    pub population_size: usize,
+    /// Number of individuals chosen to breed the children of the next generation, at most
+    /// `population_size`.
+    pub generation_size: usize,
+    /// How those individuals are chosen.
+    pub selection: Selection,
+    /// Number of the fittest individuals copied unchanged into the next generation.
+    pub elitism: usize,
+    /// Replace only this many of the least fit individuals by children every generation,
+    /// keeping everyone else, instead of renewing all but the elite.
+    pub steady_state: Option<usize>,
+    /// How their children inherit their genes.
+    pub crossover: Crossover,
+    /// Probability that a child has one of its genes flipped.
+    pub mutation_rate: f64,
+    /// Number of previous rounds the evolved genomes remember, for [`genome_length`]`(depth)`
+    /// genes each. Only genomes remembering one round are numbered.
+    pub depth: usize,
+}
+
+impl Default for GeneticConfig {
+    fn default() -> Self {
+        GeneticConfig {
+            population_size: POPULATION_SIZE,
+            generation_size: GENERATION_SIZE,
+            selection: Selection::Truncation,
+            elitism: GENERATION_SIZE,
+            steady_state: None,
+            crossover: Crossover::Alternating,
+            mutation_rate: MUTATION_RATE,
+            depth: 1,
+        }
+    }
+}
+
+impl GeneticConfig {
+    /// The first generation: the genomes in encoding order, starting over after the last one
+    /// when the population is larger than [`GENOME_COUNT`].
+    pub fn initial_population(&self) -> Box<[u8]> {
+        (0..self.population_size).map(|n| (n % GENOME_COUNT) as u8).collect()
+    }
+
+    /// The first generation with `champions`, such as the winners of earlier runs, in the
+    /// places of its first individuals, so that they start out more common than the rest.
+    pub fn seeded_population(&self, champions: &[u8]) -> Box<[u8]> {
+        let mut population = self.initial_population();
+        for (individual, &champion) in population.iter_mut().zip(champions) {
+            *individual = champion;
+        }
+        population
+    }
+
+    /// The first generation as genomes of `depth`: those of
+    /// [`GeneticConfig::initial_population`] when they remember one round, random ones
+    /// otherwise.
+    pub fn initial_genomes(&self) -> Vec<Genome> {
+        match self.depth {
+            1 => self.initial_population().iter().map(|&n| number_to_genome(n)).collect(),
+            depth => (0..self.population_size).map(|_| random_genome(depth)).collect(),
+        }
+    }
+}
+
+/// Decodes a genome from its number, the most significant bit being the first gene.
+pub fn number_to_genome(n: u8) -> Genome {
     let mut genome = [false; GENOME_LENGTH as usize];
     let mut mask = 1;
     for i in (0..GENOME_LENGTH).rev() {
@@ -38,7 +562,8 @@ fn number_to_genome(n: u8) -> Genome {
     Box::new(genome)
 }
 
-fn genome_to_number(g: &Genome) -> u8 {
+/// Encodes a genome as a number, the inverse of [`number_to_genome`].
+pub fn genome_to_number(g: &Genome) -> u8 {
     let mut acc: u8 = 0;
     let mut exp = 0;
     for i in (0..GENOME_LENGTH).rev() {
@@ -51,235 +576,1408 @@ fn genome_to_number(g: &Genome) -> u8 {
     acc
 }
 
+/// Accumulated scores of one pairing, seen from one of the two participants.
+pub struct Matchup {
+    /// Name of the other participant.
+    pub opponent: String,
+    /// Total score of the participant this matchup belongs to.
+    pub own_score: i32,
+    /// Total score of the other participant.
+    pub other_score: i32,
+}
+
+/// Cumulative score of one participant over the rounds played so far.
+#[derive(Clone)]
+pub struct Standing {
+    pub name: String,
+    /// Whether the participant is one of the evolved opponents rather than a fixed player.
+    pub evolved: bool,
+    pub score: i32,
+}
+
+/// Accumulated scores of every pairing of a tournament, with the names along both axes.
+#[derive(Clone)]
+pub struct ScoreGrid {
+    /// Names of the fixed players, one per column.
+    pub players: Vec<String>,
+    /// Names of the opponents, one per row.
+    pub opponents: Vec<String>,
+    /// (opponent, player) totals of every pairing, `None` where it was not played.
+    pub scores: Grid<Option<(i32, i32)>>,
+    /// Rounds every pairing played.
+    pub rounds: u32,
+}
+
+/// Iterated game between the fixed players and the evolved opponents, or within a population.
+/// It serialises with its whole state, finished or not, so that it plays on from where it was
+/// saved once read back.
+#[derive(Serialize, Deserialize)]
 pub struct Tournament {
     /// Players in the game.
     players: Box<[Player]>,
     /// Opponents to the players (clone of players but with separate memory)
     opponents: Box<[Player]>,
-    /// 10x10 grid where each tuple represents (player vertical score, player horizontal score).
+    /// Opponents x players grid where each tuple represents (player vertical score, player
+    /// horizontal score).
     scores: Grid<(i32, i32)>,
     /// Number of times to apply the [`RewardFunc`].
     max_iter: u32,
-    /// What the reward function is.
-    rewardsystem: RewardFunc,
+    /// Scores the reward function gives, possibly changed since the tournament was created.
+    payoffs: PayoffTable,
+    /// Probability that a decision is flipped before it is played.
+    execution_noise: f64,
+    /// Probability that a player remembers the other's move as the opposite one.
+    perception_noise: f64,
+    /// Whether evolved genomes read public standing instead of the previous moves.
+    reputation: bool,
+    /// Payoff both participants receive when either of them abstains.
+    loner_payoff: i32,
+    /// (cost to the punisher, fine to the punished) of a punishment, nothing unless set with
+    /// [`Tournament::with_punishment`].
+    punishment: (i32, i32),
+    /// Whether participants may refuse partners, taking the loner payoff instead.
+    ostracism: bool,
+    /// Number of pairings that were refused, counted per round.
+    refusals: u32,
+    /// Budget spent by every cooperation when participants have budgets.
+    cooperation_cost: i32,
+    /// Number of decisions to cooperate turned into defections by an exhausted budget.
+    forced_defections: u32,
+    /// (rounds bound, penalty per defection) of the contract offered before every match.
+    contract: Option<(u32, i32)>,
+    /// Number of rounds played under a contract, and of defections breaking one.
+    contracted: (u32, u32),
+    /// Whether each (opponent, player) pairing is played, indexed like `scores`.
+    scheduled: Grid<bool>,
+}
+
+/// Rules of a single round, copied out of a [`Tournament`] so that rounds can be played on
+/// other threads.
+#[derive(Clone, Copy)]
+struct RoundRules {
+    payoffs: PayoffTable,
+    execution_noise: f64,
+    perception_noise: f64,
+    reputation: bool,
+    loner_payoff: i32,
+    punishment: (i32, i32),
+    ostracism: bool,
+    cooperation_cost: i32,
+    contract: Option<(u32, i32)>,
+}
+
+/// What a round adds to the scores and counters of a [`Tournament`].
+#[derive(Clone, Copy, Default)]
+struct RoundOutcome {
+    /// (opponent, player) payoffs.
+    scores: (i32, i32),
+    refusals: u32,
+    forced_defections: u32,
+    contracted: (u32, u32),
+}
+
+impl RoundOutcome {
+    fn add(&mut self, other: RoundOutcome) {
+        self.scores.0 += other.scores.0;
+        self.scores.1 += other.scores.1;
+        self.refusals += other.refusals;
+        self.forced_defections += other.forced_defections;
+        self.contracted.0 += other.contracted.0;
+        self.contracted.1 += other.contracted.1;
+    }
+}
+
+/// Returns the opposite decision with probability `p`.
+fn flip_with(p: f64, d: Decision) -> Decision {
+    if p > 0.0 && rng().gen_bool(p) {
+        !d
+    } else {
+        d
+    }
+}
+
+/// Number of genes of a genome remembering `depth` previous rounds: one per sequence of up to
+/// `depth` (own, other) move pairs, including the empty one before the first round.
+pub fn genome_length(depth: usize) -> usize {
+    (0..=depth).map(|k| 4usize.pow(k as u32)).sum()
+}
+
+/// Genome of random genes remembering `depth` previous rounds.
+pub fn random_genome(depth: usize) -> Genome {
+    let mut rng = rng();
+    (0..genome_length(depth)).map(|_| rng.gen_bool(0.5)).collect()
+}
+
+/// The genes of `genome` as C for cooperate and D for defect, in order.
+pub fn genome_label(genome: &[bool]) -> String {
+    genome.iter().map(|&g| if g { 'C' } else { 'D' }).collect()
+}
+
+/// The memory-one genome encoded as `n` as a decision table, e.g.
+/// `opening move: C; after CC: C; after CD: D; after DC: C; after DD: D` for tit for tat.
+pub fn describe_genome(n: u8) -> String {
+    let genes = genome_label(&number_to_genome(n));
+    let rows: Vec<String> = crate::stats::GENE_CONTEXTS
+        .iter()
+        .zip(genes.chars())
+        .map(|(context, g)| format!("{context}: {g}"))
+        .collect();
+    rows.join("; ")
+}
+
+/// Creates an evolved participant playing the genome encoded as `c`, with empty memories of
+/// the `others` participants on the other side.
+fn genome_player(c: u8, id: String, others: usize) -> Player {
+    new_player(id, GenomeStrategy::new(c), others)
+}
+
+/// Samples drawn per context by [`memory_one_rates`].
+const RATE_SAMPLES: u32 = 1000;
+
+/// Probabilities of the named strategy `s` cooperating at the start of a matchup and after
+/// CC, CD, DC and DD, estimated by sampling its decision table, or `None` if it does not
+/// decide from the previous moves alone or punishes on top of its moves.
+pub fn memory_one_rates(s: usize) -> Option<[f64; 5]> {
+    use Decision::*;
+    let Policy::MemoryOne(table) = named_strategies().get(s)?.policy else {
+        return None;
+    };
+    let contexts = [
+        None,
+        Some((Cooperate, Cooperate)),
+        Some((Cooperate, Defect)),
+        Some((Defect, Cooperate)),
+        Some((Defect, Defect)),
+    ];
+    Some(contexts.map(|context| {
+        let (own, other) = context.unzip();
+        let cooperated = (0..RATE_SAMPLES).filter(|_| table(own, other) == Cooperate).count();
+        cooperated as f64 / RATE_SAMPLES as f64
+    }))
+}
+
+/// Number of named strategies.
+pub const STRATEGY_COUNT: usize = 20;
+
+/// The fixed players' names and strategies, in the order they take part in a [`Tournament`].
+fn named_strategies() -> [NamedStrategy; STRATEGY_COUNT] {
+    use Decision::*;
+    let named = |name, policy| NamedStrategy { name, policy };
+    [
+        named("trusting\nt4t", Policy::MemoryOne(good_tit_for_tat)),
+        named("suspicious\nt4t", Policy::MemoryOne(sus_tit_for_tat)),
+        named("naive", Policy::MemoryOne(naive)),
+        named("evil", Policy::MemoryOne(evil)),
+        named("random", Policy::MemoryOne(random)),
+        named("xor", Policy::MemoryOne(xor)),
+        named("opposite\nt4t", Policy::MemoryOne(opposite_tit_for_tat)),
+        named("xnor", Policy::MemoryOne(xnor)),
+        named("nand", Policy::MemoryOne(nand)),
+        named("Bernoulli", Policy::MemoryOne(random_biased)),
+        named("tranquilizer", Policy::History(tranquilizer)),
+        named("grim\ntrigger", Policy::History(grim_trigger)),
+        named("gradual", Policy::History(gradual)),
+        named("handshake\nCD", Policy::Handshake(&[Cooperate, Defect])),
+        named(
+            "handshake\nDDC",
+            Policy::Handshake(&[Defect, Defect, Cooperate]),
+        ),
+        named("image\nscorer", Policy::Reputation(image_scorer)),
+        named("loner", Policy::Optional(loner)),
+        named("walk\naway", Policy::Optional(walk_away)),
+        named("moralist", Policy::Punishing(naive)),
+        named("extortioner", Policy::MemoryOne(extortioner)),
+    ]
+}
+
+/// Named strategies that change the game for whoever meets them, and so only take part in a
+/// run when chosen: the loners abstain and the moralist fines defections.
+const OPT_IN_STRATEGIES: [&str; 3] = ["loner", "walk\naway", "moralist"];
+
+/// Which named strategies take part in a run unless chosen otherwise, in the order of
+/// [`strategy_names`]: all but those that change the game for whoever meets them.
+pub fn default_strategies() -> [bool; STRATEGY_COUNT] {
+    named_strategies().map(|s| !OPT_IN_STRATEGIES.contains(&s.name))
+}
+
+/// Names of the fixed players, in the order they take part in a [`Tournament`].
+pub fn strategy_names() -> Vec<&'static str> {
+    named_strategies().iter().map(|s| s.name).collect()
+}
+
+/// Creates a participant with empty memories of the `others` participants on the other side
+/// and its own copy of `strategy` for each of them.
+fn new_player(id: String, strategy: impl Strategy + Clone + 'static, others: usize) -> Player {
+    let name = strategy.name();
+    Player {
+        prev_move_self: vec![None; others],
+        prev_move_other: vec![None; others],
+        history: vec![Vec::new(); others],
+        forgotten: vec![0; others],
+        strategies: (0..others).map(|_| Box::new(strategy.clone()) as Box<dyn Strategy>).collect(),
+        strategy_name: name.to_string(),
+        id,
+        responses: [(0, 0); 5],
+        tag: 0,
+        green_beard: false,
+        image: 0,
+        punishment_gene: false,
+        round_cost: 0,
+        tremble: 0.0,
+        opening: None,
+        budget: None,
+        signs_contracts: CONTRACT_SIGNERS.contains(&name),
+        forgetting: 0.0,
+        signal: None,
+        signal_response: None,
+        mutation_rate: None,
+    }
 }
 
 impl Tournament {
     /// Create a new [`Tournament`].
-    pub fn from(n_iter: u32, rules: RewardFunc, opponent_starting_pop: Box<[u8]>) -> Self {
-        static PLAYER_INIT_DATA: [(&str, fn(Option<Decision>, Option<Decision>) -> Decision); 10] = [
-            ("trusting\nt4t", good_tit_for_tat),
-            ("suspicious\nt4t", sus_tit_for_tat),
-            ("naive", naive),
-            ("evil", evil),
-            ("random", random),
-            ("xor", xor),
-            ("opposite\nt4t", opposite_tit_for_tat),
-            ("xnor", xnor),
-            ("nand", nand),
-            ("Bernoulli", random_biased),
-        ];
-        let opponent_names: Vec<String> = (0..POPULATION_SIZE)
+    pub fn from(
+        n_iter: u32,
+        rules: impl Into<PayoffMatrix>,
+        opponent_starting_pop: Box<[u8]>,
+    ) -> Self {
+        Self::with_custom_players(n_iter, rules, opponent_starting_pop, &[])
+    }
+
+    /// Like [`Tournament::from`], with a fixed player for every `(name, genome)` of `custom`
+    /// after the named strategies, which plays the memory-one strategy the genome encodes.
+    pub fn with_custom_players(
+        n_iter: u32,
+        rules: impl Into<PayoffMatrix>,
+        opponent_starting_pop: Box<[u8]>,
+        custom: &[(String, u8)],
+    ) -> Self {
+        let opponent_ids: Vec<String> =
+            opponent_starting_pop.iter().enumerate().map(|(n, c)| format!("{c}#{n}")).collect();
+        let (player_names, players) = Self::fixed_players(custom, &opponent_ids);
+
+        let opponents_selection = opponent_starting_pop
+            .iter()
+            .zip(opponent_ids)
+            .map(|(&c, id)| genome_player(c, id, player_names.len()))
+            .collect();
+
+        Self::with_participants(n_iter, rules.into(), players, opponents_selection)
+    }
+
+    /// Like [`Tournament::with_custom_players`], but the population also plays within itself
+    /// as in [`Tournament::self_play`]: the players are the fixed players followed by copies of
+    /// the opponents, so every individual meets every fixed player, every other individual and
+    /// its own twin, and its fitness depends on the make-up of the population.
+    pub fn with_round_robin(
+        n_iter: u32,
+        rules: impl Into<PayoffMatrix>,
+        population: &[u8],
+        custom: &[(String, u8)],
+    ) -> Self {
+        let ids: Vec<String> =
+            population.iter().enumerate().map(|(n, c)| format!("{c}#{n}")).collect();
+        let (mut everyone, mut players) = Self::fixed_players(custom, &ids);
+        players.extend(
+            population.iter().zip(&ids).map(|(&c, id)| genome_player(c, id.clone(), ids.len())),
+        );
+        everyone.extend(ids.iter().cloned());
+        let opponents = population
+            .iter()
+            .zip(&ids)
+            .map(|(&c, id)| genome_player(c, id.clone(), everyone.len()));
+        Self::with_participants(n_iter, rules.into(), players, opponents.collect())
+    }
+
+    /// Create a [`Tournament`] between two evolving populations: the fixed players are replaced
+    /// by the genomes of `players`, a second gene pool, so that each population adapts to the
+    /// other. Rank and breed the players with [`Tournament::swap_sides`].
+    pub fn coevolving(
+        n_iter: u32,
+        rules: impl Into<PayoffMatrix>,
+        population: &[u8],
+        players: &[u8],
+    ) -> Self {
+        let opponent_ids: Vec<String> =
+            population.iter().enumerate().map(|(n, c)| format!("{c}#{n}")).collect();
+        let player_ids: Vec<String> =
+            players.iter().enumerate().map(|(n, c)| format!("{c}#pool{n}")).collect();
+        let players = players
+            .iter()
+            .zip(&player_ids)
+            .map(|(&c, id)| genome_player(c, id.clone(), opponent_ids.len()));
+        let opponents = population
+            .iter()
+            .zip(&opponent_ids)
+            .map(|(&c, id)| genome_player(c, id.clone(), player_ids.len()));
+        Self::with_participants(n_iter, rules.into(), players.collect(), opponents.collect())
+    }
+
+    /// The named strategies followed by the `custom` genomes, with empty memories of every
+    /// opponent in `opponent_ids`, together with their ids.
+    fn fixed_players(
+        custom: &[(String, u8)],
+        opponent_ids: &[String],
+    ) -> (Vec<String>, Vec<Player>) {
+        let player_init_data = named_strategies();
+        let player_names: Vec<String> = player_init_data
+            .iter()
+            .map(|s| s.name.to_string())
+            .chain(custom.iter().enumerate().map(|(k, (name, _))| format!("{name}#custom{k}")))
+            .collect();
+
+        let mut players: Vec<Player> = player_init_data
             .into_iter()
-            .map(|n| (opponent_starting_pop[n] as i32).to_string())
+            .map(|s| new_player(s.name.to_string(), s, opponent_ids.len()))
             .collect();
+        for ((name, c), id) in custom.iter().zip(&player_names[STRATEGY_COUNT..]) {
+            let mut player = genome_player(*c, id.clone(), opponent_ids.len());
+            player.strategy_name = name.clone();
+            players.push(player);
+        }
+        (player_names, players)
+    }
 
-        let players: Vec<Player> = PLAYER_INIT_DATA
+    /// Like [`Tournament::with_custom_players`], but the opponents play genomes remembering
+    /// `depth` previous rounds, each [`genome_length`]`(depth)` genes long, as described at
+    /// [`GenomeStrategy`].
+    pub fn with_genomes(
+        n_iter: u32,
+        rules: impl Into<PayoffMatrix>,
+        depth: usize,
+        population: &[Genome],
+        custom: &[(String, u8)],
+    ) -> Self {
+        let opponent_ids: Vec<String> = population
             .iter()
-            .map(|(name, table)| {
-                let mut initial_player_memory = HashMap::new();
-                for opponent_name in opponent_names.clone() {
-                    initial_player_memory.insert(opponent_name.clone(), None);
-                }
-                let memory_of_opponents = initial_player_memory.clone();
-                Player {
-                    prev_move_self: initial_player_memory,
-                    prev_move_other: memory_of_opponents,
-                    strategy: Box::new(table),
-                    strategy_name: name.to_string(),
-                }
+            .enumerate()
+            .map(|(n, g)| format!("{}#{n}", genome_label(g)))
+            .collect();
+        let (player_names, players) = Self::fixed_players(custom, &opponent_ids);
+        let opponents = population
+            .iter()
+            .zip(opponent_ids)
+            .map(|(g, id)| {
+                new_player(
+                    id,
+                    GenomeStrategy::from_genome(g, depth),
+                    player_names.len(),
+                )
             })
             .collect();
+        Self::with_participants(n_iter, rules.into(), players, opponents)
+    }
 
-        let opponents_selection = opponent_starting_pop
-            .iter()
-            .map(|&c| {
-                let mut initial_opponent_memory = HashMap::new();
-                for (name, _) in PLAYER_INIT_DATA {
-                    initial_opponent_memory.insert(name.to_string(), None);
+    /// Like [`Tournament::named`], but every named strategy is entered twice: once as is and
+    /// once as a variant suffixed "noisy" whose decisions are flipped with probability `noise`.
+    pub fn noisy_variants(n_iter: u32, rules: impl Into<PayoffMatrix>, noise: f64) -> Self {
+        let names = strategy_names();
+        let entries: Vec<(usize, f64)> =
+            (0..STRATEGY_COUNT).flat_map(|s| [(s, 0.0), (s, noise)]).collect();
+        let ids: Vec<String> =
+            entries.iter().enumerate().map(|(n, &(s, _))| format!("{}#{n}", names[s])).collect();
+        let participants = || {
+            entries.iter().zip(&ids).map(|(&(s, tremble), id)| {
+                let strategy = named_strategies()[s];
+                let mut player = new_player(id.clone(), strategy, ids.len());
+                if tremble > 0.0 {
+                    player.strategy_name = format!("{}\nnoisy", strategy.name);
+                    player.tremble = tremble;
                 }
-                let memory_of_players = initial_opponent_memory.clone();
-                let gene: Vec<Decision> = number_to_genome(c)
-                    .iter()
-                    .map(|&b| {
-                        if b {
-                            Decision::Cooperate
-                        } else {
-                            Decision::Defect
-                        }
-                    })
-                    .collect();
+                player
+            })
+        };
+        Self::with_participants(
+            n_iter,
+            rules.into(),
+            participants().collect(),
+            participants().collect(),
+        )
+    }
 
-                let strat: DecisionTable = Box::new(move |own_pm, other_pm| {
-                    use Decision::*;
-                    match (own_pm, other_pm) {
-                        (None, None) => gene[0],
-                        (Some(ownpm), Some(otherpm)) => match (ownpm, otherpm) {
-                            (Cooperate, Cooperate) => gene[1],
-                            (Cooperate, Defect) => gene[2],
-                            (Defect, Cooperate) => gene[3],
-                            (Defect, Defect) => gene[4],
-                        },
-                        (Some(_), None) | (None, Some(_)) => {
-                            unreachable!("impossible move combination")
-                        }
-                    }
-                });
+    /// Create a [`Tournament`] in which the population plays within itself, the standard
+    /// well-mixed evolutionary setting: the fixed players are replaced by copies of the
+    /// opponents, so every individual meets every other one and its own twin, and its fitness
+    /// depends on the make-up of the population.
+    pub fn self_play(n_iter: u32, rules: impl Into<PayoffMatrix>, population: &[u8]) -> Self {
+        let ids: Vec<String> =
+            population.iter().enumerate().map(|(n, c)| format!("{c}#{n}")).collect();
+        let participants =
+            || population.iter().zip(&ids).map(|(&c, id)| genome_player(c, id.clone(), ids.len()));
+        Self::with_participants(
+            n_iter,
+            rules.into(),
+            participants().collect(),
+            participants().collect(),
+        )
+    }
 
-                Player {
-                    prev_move_self: initial_opponent_memory,
-                    prev_move_other: memory_of_players,
-                    strategy: strat,
-                    strategy_name: (c as i32).to_string(),
-                }
-            })
+    /// Create a [`Tournament`] in which the fixed players only meet each other: the opponents
+    /// are fresh copies of the fixed players, so every named strategy plays every other one,
+    /// including its own twin, once from its own side.
+    pub fn named(n_iter: u32, rules: impl Into<PayoffMatrix>) -> Self {
+        Self::roster(n_iter, rules, &[1; STRATEGY_COUNT])
+    }
+
+    /// Like [`Tournament::named`], but with `copies[s]` instances of the named strategy `s`,
+    /// in the order of [`strategy_names`]. Every instance has its own identity and memory, so
+    /// that ecological setups such as five tit-for-tats among fifteen defectors can be played.
+    pub fn roster(n_iter: u32, rules: impl Into<PayoffMatrix>, copies: &[usize]) -> Self {
+        let entries: Vec<(usize, String)> = strategy_names()
+            .iter()
+            .zip(copies)
+            .enumerate()
+            .flat_map(|(s, (name, &c))| (0..c).map(move |_| (s, name.to_string())))
+            .enumerate()
+            .map(|(n, (s, name))| (s, format!("{name}#{n}")))
             .collect();
+        let ids: Vec<String> = entries.iter().map(|(_, id)| id.clone()).collect();
+        let participants = || {
+            entries.iter().map(|(s, id)| new_player(id.clone(), named_strategies()[*s], ids.len()))
+        };
+        Self::with_participants(
+            n_iter,
+            rules.into(),
+            participants().collect(),
+            participants().collect(),
+        )
+    }
 
+    /// Like [`Tournament::named`], but among `strategies` instead of the named strategies, so
+    /// that strategies written outside this module can be played against each other.
+    pub fn among<S: Strategy + Clone + 'static>(
+        n_iter: u32,
+        rules: impl Into<PayoffMatrix>,
+        strategies: &[S],
+    ) -> Self {
+        let ids: Vec<String> =
+            strategies.iter().enumerate().map(|(n, s)| format!("{}#{n}", s.name())).collect();
+        let participants = || {
+            strategies.iter().zip(&ids).map(|(s, id)| new_player(id.clone(), s.clone(), ids.len()))
+        };
+        Self::with_participants(
+            n_iter,
+            rules.into(),
+            participants().collect(),
+            participants().collect(),
+        )
+    }
+
+    fn with_participants(
+        n_iter: u32,
+        payoffs: PayoffMatrix,
+        players: Vec<Player>,
+        opponents: Vec<Player>,
+    ) -> Self {
         Tournament {
-            players: players.into_boxed_slice(),
-            opponents: opponents_selection,
-            scores: Grid::new(POPULATION_SIZE, 10),
             max_iter: n_iter,
-            rewardsystem: rules,
+            payoffs: payoffs.outcomes,
+            execution_noise: 0.0,
+            perception_noise: 0.0,
+            reputation: false,
+            loner_payoff: payoffs.outcomes[3].0,
+            punishment: (0, 0),
+            ostracism: false,
+            refusals: 0,
+            cooperation_cost: 0,
+            forced_defections: 0,
+            contract: None,
+            contracted: (0, 0),
+            scheduled: Grid::init(opponents.len(), players.len(), true),
+            scores: Grid::new(opponents.len(), players.len()),
+            players: players.into_boxed_slice(),
+            opponents: opponents.into_boxed_slice(),
         }
     }
 
+    /// Swaps the sides of the tournament, the players becoming the opponents and the other way
+    /// round, with their scores, so that the players can be ranked and bred like opponents.
+    pub fn swap_sides(mut self) -> Self {
+        std::mem::swap(&mut self.players, &mut self.opponents);
+        self.scores.transpose();
+        for (opponent_score, player_score) in self.scores.iter_mut() {
+            std::mem::swap(opponent_score, player_score);
+        }
+        self.scheduled.transpose();
+        self
+    }
+
+    /// Sets the probabilities of a decision being flipped when played and when remembered
+    /// by the other participant.
+    pub fn with_noise(mut self, execution: f64, perception: f64) -> Self {
+        self.execution_noise = execution;
+        self.perception_noise = perception;
+        self
+    }
+
+    /// Lets participants refuse to play partners that mostly defected against them recently, or
+    /// have a poor image in the indirect reciprocity mode, in which case both receive the loner
+    /// payoff as an outside option.
+    pub fn with_ostracism(mut self, ostracism: bool) -> Self {
+        self.ostracism = ostracism;
+        self
+    }
+
+    /// Gives the opponents the given punishment genes and sets the cost and fine of punishing,
+    /// which also makes the participants whose strategies punish do so.
+    pub fn with_punishment(mut self, punishers: &[bool], cost: i32, fine: i32) -> Self {
+        for (opponent, &punishes) in self.opponents.iter_mut().zip(punishers) {
+            opponent.punishment_gene = punishes;
+        }
+        self.punishment = (cost, fine);
+        self
+    }
+
+    /// Sets the payoff both participants receive in a round where either of them abstains,
+    /// which defaults to the payoff of mutual defection.
+    pub fn with_loner_payoff(mut self, payoff: i32) -> Self {
+        self.loner_payoff = payoff;
+        self
+    }
+
+    /// Makes the opponents' genes respond to their own and the other's public standing rather
+    /// than to the previous moves, turning direct into indirect reciprocity.
+    pub fn with_reputation(mut self, reputation: bool) -> Self {
+        self.reputation = reputation;
+        self
+    }
+
+    /// Pairs every opponent with only `k` fixed players drawn at random instead of all of them,
+    /// modelling limited interaction in large populations.
+    pub fn with_random_pairing(mut self, k: usize) -> Self {
+        let mut rng = rng();
+        for i in 0..self.opponents.len() {
+            let partners = rand::seq::index::sample(&mut rng, self.players.len(), k);
+            for j in 0..self.players.len() {
+                self.scheduled[(i, j)] = partners.iter().any(|p| p == j);
+            }
+        }
+        self
+    }
+
+    /// Schedules every opponent to play only the same `m` fixed players, drawn at random from
+    /// the whole roster each time this is called, so that a population evaluated against a
+    /// fresh sample every generation cannot specialise in exploiting particular players.
+    pub fn with_sampled_players(mut self, m: usize) -> Self {
+        let sample = rand::seq::index::sample(&mut rng(), self.players.len(), m);
+        for j in 0..self.players.len() {
+            let sampled = sample.iter().any(|s| s == j);
+            for i in 0..self.opponents.len() {
+                self.scheduled[(i, j)] = sampled;
+            }
+        }
+        self
+    }
+
+    /// Unschedules every pairing with a fixed player whose entry in `enabled` is false, leaving
+    /// the players past the end of `enabled` as they are.
+    pub fn with_enabled_players(mut self, enabled: &[bool]) -> Self {
+        for (j, _) in enabled.iter().enumerate().take(self.players.len()).filter(|(_, &e)| !e) {
+            for i in 0..self.opponents.len() {
+                self.scheduled[(i, j)] = false;
+            }
+        }
+        self
+    }
+
+    /// Unschedules every pairing, so that matches are only played one at a time through
+    /// [`Tournament::play`].
+    pub fn unplayed(mut self) -> Self {
+        self.scheduled.fill(false);
+        self
+    }
+
+    /// Sets the flat cost every fixed player and every opponent pays per round played, by
+    /// index, to model handicaps or the cost of complex strategies.
+    pub fn with_costs(mut self, player_costs: &[i32], opponent_costs: &[i32]) -> Self {
+        for (player, &cost) in self.players.iter_mut().zip(player_costs) {
+            player.round_cost = cost;
+        }
+        for (opponent, &cost) in self.opponents.iter_mut().zip(opponent_costs) {
+            opponent.round_cost = cost;
+        }
+        self
+    }
+
+    /// Gives the opponents the given probabilities of cooperating on the first move, which
+    /// replace the opening gene of their genomes.
+    pub fn with_openings(mut self, openings: &[f64]) -> Self {
+        for (opponent, &p) in self.opponents.iter_mut().zip(openings) {
+            opponent.opening = Some(p);
+        }
+        self
+    }
+
+    /// Gives the opponents the given contract acceptance genes and offers a contract before
+    /// every match: when both sides accept, every defection in the first `rounds` rounds costs
+    /// the defector `penalty`, which is paid to the other side.
+    pub fn with_contracts(mut self, signers: &[bool], rounds: u32, penalty: i32) -> Self {
+        for (opponent, &signs) in self.opponents.iter_mut().zip(signers) {
+            opponent.signs_contracts = signs;
+        }
+        self.contract = Some((rounds, penalty));
+        self
+    }
+
+    /// Makes every participant forget its previous moves against the other before each round
+    /// with probability `rate`.
+    pub fn with_forgetting(mut self, rate: f64) -> Self {
+        for p in self.players.iter_mut().chain(self.opponents.iter_mut()) {
+            p.forgetting = rate;
+        }
+        self
+    }
+
+    /// Sets the forgetting probability of every fixed player by index, overriding
+    /// [`Tournament::with_forgetting`] so that strategies can differ in how well they recall.
+    pub fn with_player_forgetting(mut self, rates: &[f64]) -> Self {
+        for (player, &rate) in self.players.iter_mut().zip(rates) {
+            player.forgetting = rate;
+        }
+        self
+    }
+
+    /// Adds a cheap-talk stage before every match: the fixed players announce cooperation and
+    /// every opponent announces the signal given by its talk genes, then opens with the reply
+    /// its talk genes give to the other's signal. See [`talk_genes`] for the encoding.
+    pub fn with_signals(mut self, opponent_talk: &[u8]) -> Self {
+        for player in self.players.iter_mut() {
+            player.signal = Some(Decision::Cooperate);
+        }
+        for (opponent, &talk) in self.opponents.iter_mut().zip(opponent_talk) {
+            let (signal, on_c, on_d) = talk_genes(talk);
+            opponent.signal = Some(signal);
+            opponent.signal_response = Some((on_c, on_d));
+        }
+        self
+    }
+
+    /// Gives the opponents the given rate genes, indices into [`MUTATION_RATES`] setting the
+    /// probability that their children mutate in place of [`GeneticConfig::mutation_rate`].
+    pub fn with_rate_genes(mut self, genes: &[u8]) -> Self {
+        for (opponent, &gene) in self.opponents.iter_mut().zip(genes) {
+            opponent.mutation_rate = Some(MUTATION_RATES[gene as usize]);
+        }
+        self
+    }
+
+    /// Makes every cooperation cost `cost` from a budget of [`INITIAL_BUDGET`] that each
+    /// participant carries across all its rounds and matches, and that every payoff it
+    /// receives replenishes by its excess over the worst outcome of the game. Participants
+    /// that cannot afford to cooperate have to defect.
+    pub fn with_budgets(mut self, cost: i32) -> Self {
+        for p in self.players.iter_mut().chain(self.opponents.iter_mut()) {
+            p.budget = Some(INITIAL_BUDGET);
+        }
+        self.cooperation_cost = cost;
+        self
+    }
+
+    /// Replaces the scores of the reward function the tournament was created with.
+    pub fn with_payoffs(mut self, payoffs: PayoffTable) -> Self {
+        self.payoffs = payoffs;
+        self
+    }
+
+    /// Gives the opponents the given tags and green-beard genes, and the fixed players tags
+    /// cycling through `tag_count` values so that every tag is represented among them.
+    pub fn with_tags(mut self, opponent_tags: &[u8], green_beards: &[bool], tag_count: u8) -> Self {
+        for (j, player) in self.players.iter_mut().enumerate() {
+            player.tag = (j % tag_count as usize) as u8;
+        }
+        for ((opponent, &tag), &green_beard) in
+            self.opponents.iter_mut().zip(opponent_tags).zip(green_beards)
+        {
+            opponent.tag = tag;
+            opponent.green_beard = green_beard;
+        }
+        self
+    }
+
     fn execute_round_and_update_scores(&mut self, i: usize, j: usize) {
-        let player = &mut self.players[j];
-        let opponent = &mut self.opponents[i];
+        let rules = self.round_rules();
+        let (player, opponent) = (&mut self.players[j], &mut self.opponents[i]);
+        let outcome = Self::play_round(&rules, (player, PlayerId(j)), (opponent, PlayerId(i)));
+        self.add_outcome(i, j, outcome);
+    }
+
+    /// The rules every round of the tournament is played by.
+    fn round_rules(&self) -> RoundRules {
+        RoundRules {
+            payoffs: self.payoffs,
+            execution_noise: self.execution_noise,
+            perception_noise: self.perception_noise,
+            reputation: self.reputation,
+            loner_payoff: self.loner_payoff,
+            punishment: self.punishment,
+            ostracism: self.ostracism,
+            cooperation_cost: self.cooperation_cost,
+            contract: self.contract,
+        }
+    }
+
+    /// Adds what a round between opponent `i` and player `j` scored and counted.
+    fn add_outcome(&mut self, i: usize, j: usize, outcome: RoundOutcome) {
+        let mut total = RoundOutcome {
+            scores: self.scores[(i, j)],
+            refusals: self.refusals,
+            forced_defections: self.forced_defections,
+            contracted: self.contracted,
+        };
+        total.add(outcome);
+        self.scores[(i, j)] = total.scores;
+        self.refusals = total.refusals;
+        self.forced_defections = total.forced_defections;
+        self.contracted = total.contracted;
+    }
+
+    /// Plays one round between a player and an opponent, each given with its [`PlayerId`],
+    /// which touches nothing but the two of them, and returns what it adds to the tournament.
+    fn play_round(
+        rules: &RoundRules,
+        (player, j): (&mut Player, PlayerId),
+        (opponent, i): (&mut Player, PlayerId),
+    ) -> RoundOutcome {
+        let mut outcome = RoundOutcome::default();
+        let by_image = rules.reputation;
+        if rules.ostracism
+            && (player.refuses(opponent, i, by_image) || opponent.refuses(player, j, by_image))
+        {
+            outcome.scores = (rules.loner_payoff, rules.loner_payoff);
+            outcome.refusals = 1;
+            return outcome;
+        }
+
+        player.forget(i);
+        opponent.forget(j);
 
         // Get decisions.
-        let player_decision = (player.strategy)(
-            *player
-                .prev_move_self
-                .get(&opponent.strategy_name)
-                .expect("player memory should be complete"),
-            *player
-                .prev_move_other
-                .get(&opponent.strategy_name)
-                .expect("player memory should be complete"),
+        let player_pm = (player.prev_move_self[i.0], player.prev_move_other[i.0]);
+        let opponent_pm = (opponent.prev_move_self[j.0], opponent.prev_move_other[j.0]);
+        let opponent_input = if rules.reputation {
+            opponent.standing(player, opponent_pm)
+        } else {
+            opponent_pm
+        };
+        let decisions = (
+            player.decide(opponent, i, player_pm.0, player_pm.1),
+            opponent.decide(player, j, opponent_input.0, opponent_input.1),
         );
-        let opponent_decision = (opponent.strategy)(
-            *opponent
-                .prev_move_self
-                .get(&player.strategy_name)
-                .expect("player memory should be complete"),
-            *opponent
-                .prev_move_other
-                .get(&player.strategy_name)
-                .expect("player memory should be complete"),
+        let (Some(player_decision), Some(opponent_decision)) = decisions else {
+            // Someone abstained: both receive the loner payoff and the next round starts afresh.
+            outcome.scores = (rules.loner_payoff, rules.loner_payoff);
+            player.start_afresh(i);
+            opponent.start_afresh(j);
+            return outcome;
+        };
+        let player_decision = flip_with(
+            player.tremble,
+            flip_with(rules.execution_noise, player_decision),
         );
+        let opponent_decision = flip_with(
+            opponent.tremble,
+            flip_with(rules.execution_noise, opponent_decision),
+        );
+        let cost = rules.cooperation_cost;
+        let (player_decision, player_forced) = player.afford(player_decision, cost);
+        let (opponent_decision, opponent_forced) = opponent.afford(opponent_decision, cost);
+        outcome.forced_defections = player_forced as u32 + opponent_forced as u32;
+        player.observe(player_decision);
+        opponent.observe(opponent_decision);
+        player.record_response(player_pm.0, player_pm.1, player_decision);
+        opponent.record_response(opponent_pm.0, opponent_pm.1, opponent_decision);
 
-        // Calculate score.
-        let (n, m) = (self.rewardsystem)(&opponent_decision, &player_decision);
-        let (opponent_score, player_score) = self.scores[(i, j)];
-        self.scores[(i, j)] = (opponent_score + n, player_score + m);
+        // Calculate score, including the punishment stage.
+        let (mut n, mut m) =
+            rules.payoffs[context_index(Some(opponent_decision), Some(player_decision)) - 1];
+        let (cost, fine) = rules.punishment;
+        if player.punishes(i, opponent_decision) {
+            m -= cost;
+            n -= fine;
+        }
+        if opponent.punishes(j, player_decision) {
+            n -= cost;
+            m -= fine;
+        }
+        if let Some((rounds, penalty)) = rules.contract {
+            let played = player.history[i.0].len() as u32;
+            if player.signs_contracts && opponent.signs_contracts && played < rounds {
+                let breaches =
+                    [player_decision, opponent_decision].map(|d| (d == Decision::Defect) as i32);
+                m += penalty * (breaches[1] - breaches[0]);
+                n += penalty * (breaches[0] - breaches[1]);
+                outcome.contracted = (1, (breaches[0] + breaches[1]) as u32);
+            }
+        }
+        n -= opponent.round_cost;
+        m -= player.round_cost;
+        let floor = rules.payoffs.iter().map(|&(n, m)| n.min(m)).min().unwrap();
+        player.replenish(m - floor);
+        opponent.replenish(n - floor);
+        outcome.scores = (n, m);
 
         // Update memories.
-        if player.prev_move_self.remove(&opponent.strategy_name).is_none() {
-            panic!("player memory should be complete")
+        let perceived = flip_with(rules.perception_noise, opponent_decision);
+        player.prev_move_self[i.0] = Some(player_decision);
+        player.prev_move_other[i.0] = Some(perceived);
+        player.history[i.0].push((player_decision, perceived));
+        // ----------------
+
+        let perceived = flip_with(rules.perception_noise, player_decision);
+        opponent.prev_move_self[j.0] = Some(opponent_decision);
+        opponent.prev_move_other[j.0] = Some(perceived);
+        opponent.history[j.0].push((opponent_decision, perceived));
+        outcome
+    }
+
+    /// Plays a whole match between opponent `i` and player `j`, adding it to the pairings
+    /// played, and returns the (opponent, player) scores of this match.
+    pub fn play(&mut self, i: usize, j: usize) -> (i32, i32) {
+        let before = self.scores[(i, j)];
+        self.scheduled[(i, j)] = true;
+        for _ in 0..self.max_iter {
+            self.execute_round_and_update_scores(i, j);
         }
-        player.prev_move_self.insert(opponent.strategy_name.clone(), Some(player_decision));
-        if player.prev_move_other.remove(&opponent.strategy_name).is_none() {
-            panic!("player memory should be complete")
+        let (opponent_score, player_score) = self.scores[(i, j)];
+        (opponent_score - before.0, player_score - before.1)
+    }
+
+    /// Runs entire simulation up to n_iter times with current participants
+    ///
+    /// Every iteration plays the pairings in groups without a participant in common, each group
+    /// in parallel. Every round draws from its own generator, seeded from the engine's generator
+    /// once per run, so that the outcome does not depend on the number of threads.
+    pub fn run(&mut self) {
+        self.run_observed(|_| {});
+    }
+
+    /// Like [`Tournament::run`], calling `observe` once every scheduled pairing has played
+    /// another round, so that the standings can be followed while the tournament runs.
+    pub fn run_observed(&mut self, mut observe: impl FnMut(&Self)) {
+        let (rows, columns) = (self.opponents.len(), self.players.len());
+        let n = rows.max(columns);
+        // The k-th group pairs opponent i with player (i + k) % n, so that no participant
+        // appears twice in a group and every pairing appears in exactly one.
+        let groups: Vec<Vec<(usize, usize)>> = (0..n)
+            .map(|k| {
+                (0..rows)
+                    .map(|i| (i, (i + k) % n))
+                    .filter(|&(i, j)| j < columns && self.scheduled[(i, j)])
+                    .collect()
+            })
+            .filter(|group: &Vec<_>| !group.is_empty())
+            .collect();
+        let run_seed: u64 = rng().gen();
+        let round_rng = |iteration: u32, i: usize, j: usize| {
+            let round = (iteration as usize * rows + i) * columns + j;
+            StdRng::seed_from_u64(run_seed ^ round as u64)
+        };
+        let rules = self.round_rules();
+        for iteration in 0..self.max_iter {
+            for group in &groups {
+                let mut players: Vec<Option<&mut Player>> =
+                    self.players.iter_mut().map(Some).collect();
+                let mut opponents: Vec<Option<&mut Player>> =
+                    self.opponents.iter_mut().map(Some).collect();
+                let pairs: Vec<_> = group
+                    .iter()
+                    .map(|&(i, j)| {
+                        let player = players[j].take().expect("one pairing per player");
+                        let opponent = opponents[i].take().expect("one pairing per opponent");
+                        (i, j, player, opponent)
+                    })
+                    .collect();
+                let outcomes: Vec<_> = pairs
+                    .into_par_iter()
+                    .map(|(i, j, player, opponent)| {
+                        let outcome = random::with_rng(round_rng(iteration, i, j), || {
+                            Self::play_round(&rules, (player, PlayerId(j)), (opponent, PlayerId(i)))
+                        });
+                        (i, j, outcome)
+                    })
+                    .collect();
+                for (i, j, outcome) in outcomes {
+                    self.add_outcome(i, j, outcome);
+                }
+            }
+            observe(self);
         }
-        player.prev_move_other.insert(opponent.strategy_name.clone(), Some(opponent_decision));
-        // ----------------
+    }
+
+    /// Number of times the [`RewardFunc`] is applied per pairing.
+    pub fn rounds(&self) -> u32 {
+        self.max_iter
+    }
+
+    /// Lowest payoff a participant can receive in a single round.
+    pub fn worst_payoff(&self) -> i32 {
+        self.payoffs.iter().map(|&(n, m)| n.min(m)).chain([self.loner_payoff]).min().unwrap()
+            - self.worst_penalty()
+            - self
+                .players
+                .iter()
+                .chain(self.opponents.iter())
+                .map(|p| p.round_cost)
+                .max()
+                .unwrap_or(0)
+    }
+
+    /// Highest payoff a participant can receive in a single round.
+    pub fn best_payoff(&self) -> i32 {
+        self.payoffs.iter().map(|&(n, m)| n.max(m)).chain([self.loner_payoff]).max().unwrap()
+            + self.contract.map_or(0, |(_, penalty)| penalty)
+    }
+
+    /// Largest amount punishment and contract penalties can take from a participant in a
+    /// single round.
+    fn worst_penalty(&self) -> i32 {
+        let (cost, fine) = self.punishment;
+        let mut participants = self.players.iter().chain(self.opponents.iter());
+        let punishers = participants.any(|p| {
+            p.punishment_gene || p.strategies.iter().any(|s| s.punishes(Decision::Defect))
+        });
+        let contract = self.contract.map_or(0, |(_, penalty)| penalty);
+        if punishers {
+            cost + fine + contract
+        } else {
+            contract
+        }
+    }
+
+    /// Moves of player `j` and opponent `i` in every round they played so far, the player's
+    /// move first.
+    pub fn transcript(&self, i: usize, j: usize) -> Vec<(Decision, Decision)> {
+        let player = &self.players[j];
+        let opponent = &self.opponents[i];
+        player.history[i]
+            .iter()
+            .zip(&opponent.history[j])
+            .map(|(&(p, _), &(o, _))| (p, o))
+            .collect()
+    }
+
+    /// Number of fixed players, which come first in [`Tournament::matchups`].
+    pub fn player_count(&self) -> usize {
+        self.players.len()
+    }
+
+    /// Returns every participant's empirical cooperation rate at the start of a matchup and
+    /// after CC, CD, DC and DD, or `None` for contexts it never encountered.
+    pub fn response_profiles(&self) -> Vec<(String, [Option<f64>; 5])> {
+        self.players
+            .iter()
+            .chain(self.opponents.iter())
+            .map(|p| {
+                let profile = p.responses.map(|(cooperated, total)| {
+                    (total > 0).then(|| cooperated as f64 / total as f64)
+                });
+                (p.strategy_name.clone(), profile)
+            })
+            .collect()
+    }
+
+    /// Fraction of all pairings per round that were refused by either side.
+    pub fn refusal_rate(&self) -> f64 {
+        let pairings = self.max_iter as usize * self.scheduled.iter().filter(|&&s| s).count();
+        self.refusals as f64 / pairings as f64
+    }
+
+    /// Fraction of the decisions made under a contract that broke it, or zero when no
+    /// contract was signed.
+    pub fn breach_rate(&self) -> f64 {
+        let (rounds, breaches) = self.contracted;
+        if rounds == 0 {
+            0.0
+        } else {
+            breaches as f64 / (2 * rounds) as f64
+        }
+    }
+
+    /// Fraction of all decisions that were forced to defection by an exhausted budget.
+    pub fn forced_defection_rate(&self) -> f64 {
+        let decisions = 2 * self.max_iter as usize * self.scheduled.iter().filter(|&&s| s).count();
+        self.forced_defections as f64 / decisions as f64
+    }
+
+    /// Fraction of the evolved opponents' decisions that were to cooperate.
+    pub fn cooperation_rate(&self) -> f64 {
+        let (cooperated, total) = self
+            .opponents
+            .iter()
+            .flat_map(|p| p.responses)
+            .fold((0, 0), |(c, t), (cooperated, total)| {
+                (c + cooperated, t + total)
+            });
+        if total == 0 {
+            0.0
+        } else {
+            cooperated as f64 / total as f64
+        }
+    }
+
+    /// Public image score of every participant, in the order of [`Tournament::matchups`].
+    pub fn images(&self) -> Vec<i32> {
+        self.players.iter().chain(self.opponents.iter()).map(|p| p.image).collect()
+    }
+
+    /// Returns the head-to-head totals of every participant in the pairings that were played,
+    /// fixed players first, followed by the opponents in population order.
+    pub fn matchups(&self) -> Vec<(String, Vec<Matchup>)> {
+        let mut res = Vec::new();
+        for (j, player) in self.players.iter().enumerate() {
+            let games = (0..self.opponents.len())
+                .filter(|&i| self.scheduled[(i, j)])
+                .map(|i| {
+                    let (opponent_score, player_score) = self.scores[(i, j)];
+                    Matchup {
+                        opponent: self.opponents[i].strategy_name.clone(),
+                        own_score: player_score,
+                        other_score: opponent_score,
+                    }
+                })
+                .collect();
+            res.push((player.strategy_name.clone(), games));
+        }
+        for (i, opponent) in self.opponents.iter().enumerate() {
+            let games = (0..self.players.len())
+                .filter(|&j| self.scheduled[(i, j)])
+                .map(|j| {
+                    let (opponent_score, player_score) = self.scores[(i, j)];
+                    Matchup {
+                        opponent: self.players[j].strategy_name.clone(),
+                        own_score: opponent_score,
+                        other_score: player_score,
+                    }
+                })
+                .collect();
+            res.push((opponent.strategy_name.clone(), games));
+        }
+        res
+    }
+
+    /// Scores of every pairing played, opponents along the rows and fixed players along the
+    /// columns.
+    pub fn score_grid(&self) -> ScoreGrid {
+        let names = |side: &[Player]| side.iter().map(|p| p.strategy_name.clone()).collect();
+        let mut scores = Grid::new(self.opponents.len(), self.players.len());
+        for ((i, j), &score) in self.scores.indexed_iter() {
+            scores[(i, j)] = self.scheduled[(i, j)].then_some(score);
+        }
+        ScoreGrid {
+            players: names(&self.players),
+            opponents: names(&self.opponents),
+            scores,
+            rounds: self.max_iter,
+        }
+    }
+
+    /// Total score of every participant in the pairings played so far, in the order of
+    /// [`Tournament::matchups`].
+    pub fn standings(&self) -> Vec<Standing> {
+        let players = self.players.iter().enumerate().map(|(j, player)| Standing {
+            name: player.strategy_name.clone(),
+            evolved: false,
+            score: (0..self.opponents.len())
+                .filter(|&i| self.scheduled[(i, j)])
+                .map(|i| self.scores[(i, j)].1)
+                .sum(),
+        });
+        let opponents = self.opponents.iter().enumerate().map(|(i, opponent)| Standing {
+            name: opponent.strategy_name.clone(),
+            evolved: true,
+            score: self.opponent_total(i).0,
+        });
+        players.chain(opponents).collect()
+    }
+
+    /// Total score of opponent `i` and the number of pairings it played.
+    fn opponent_total(&self, i: usize) -> (i32, usize) {
+        (0..self.players.len())
+            .filter(|&j| self.scheduled[(i, j)])
+            .fold((0, 0), |(total, games), j| {
+                (total + self.scores[(i, j)].0, games + 1)
+            })
+    }
+
+    /// Indices of the opponents ordered from the highest to the lowest fitness, their mean
+    /// score per pairing played.
+    pub fn ranking(&self) -> Vec<usize> {
+        rank_by(&self.fitnesses())
+    }
+
+    /// Fitness of every opponent, by index.
+    fn fitnesses(&self) -> Vec<f64> {
+        (0..self.opponents.len()).map(|i| self.fitness(i)).collect()
+    }
+
+    /// Mean score of opponent `i` per pairing played.
+    fn fitness(&self, i: usize) -> f64 {
+        let (total, games) = self.opponent_total(i);
+        total as f64 / games.max(1) as f64
+    }
+
+    /// Probability that the children of opponent `i` mutate, its rate gene's if it has one.
+    fn mutation_rate_of(&self, i: usize, genetic: &GeneticConfig) -> f64 {
+        self.opponents[i].mutation_rate.unwrap_or(genetic.mutation_rate)
+    }
 
-        if opponent.prev_move_self.remove(&player.strategy_name).is_none() {
-            panic!("player memory should be complete")
-        }
-        opponent.prev_move_self.insert(player.strategy_name.clone(), Some(opponent_decision));
-        if opponent.prev_move_other.remove(&player.strategy_name).is_none() {
-            panic!("player memory should be complete")
+    /// Genome played by opponent `i`, decoded from its name.
+    fn genome_of(&self, i: usize) -> Genome {
+        let name = &self.opponents[i].strategy_name;
+        match name.parse() {
+            Ok(c) => number_to_genome(c),
+            Err(_) => name.chars().map(|g| g == 'C').collect(),
         }
-        opponent.prev_move_other.insert(player.strategy_name.clone(), Some(player_decision));
     }
 
-    /// Runs entire simulation up to n_iter times with current participants
-    pub fn run(&mut self) {
-        for _ in 0..self.max_iter {
-            for j in 0..10 {
-                for i in 0..POPULATION_SIZE {
-                    self.execute_round_and_update_scores(i, j);
-                }
-            }
-        }
+    /// Chooses the genomes of `count` opponents by `selection`, possibly the same one several
+    /// times, ordered from the fittest to the least fit.
+    pub fn select(&self, selection: Selection, count: usize) -> Box<[Genome]> {
+        self.select_indices(selection, count).into_iter().map(|i| self.genome_of(i)).collect()
     }
 
-    /// returns the genome of the top [`GENERATION_SIZE`] performing opponents and their scores
-    pub fn select_ten_fittest_and_bestscore(&self) -> (Box<[Genome]>, i32) {
-        let mut score_acc: Vec<(u8, i32)> = Vec::new();
-        for j in 0..10 {
-            let organism: u8 = self.opponents[j].strategy_name.parse().unwrap();
-            let mut acc = 0;
-            for i in 0..POPULATION_SIZE {
-                let (score_part, _) = self.scores[(i, j)];
-                acc += score_part
-            }
-            score_acc.push((organism, acc))
-        }
-        score_acc.sort_by_key(|&(_, n)| n);
-        score_acc.reverse();
-        let mut leaderboard: Vec<Genome> =
-            score_acc.iter().map(|&(c, _)| number_to_genome(c)).collect();
-        while leaderboard.len() > 10 {
+    /// Like [`Tournament::select`], but returns the indices of the chosen opponents.
+    pub fn select_indices(&self, selection: Selection, count: usize) -> Vec<usize> {
+        select_by(&self.fitnesses(), selection, count)
+    }
+
+    /// returns the genome of the top `count` performing opponents and the score of the best one,
+    /// or `None` if there are no opponents
+    pub fn select_fittest_and_bestscore(&self, count: usize) -> Option<(Box<[Genome]>, i32)> {
+        let score_acc: Vec<(Genome, i32)> = self
+            .ranking()
+            .into_iter()
+            .map(|i| (self.genome_of(i), self.opponent_total(i).0))
+            .collect();
+        let mut leaderboard: Vec<Genome> = score_acc.iter().map(|(g, _)| g.clone()).collect();
+        while leaderboard.len() > count {
             let _ = leaderboard.pop();
         }
-        let (_, score_of_best) = score_acc[0];
-        (leaderboard.into_boxed_slice(), score_of_best)
+        let score_of_best = score_acc.first()?.1;
+        Some((leaderboard.into_boxed_slice(), score_of_best))
+    }
+}
+
+/// Indices of a population ordered from the highest to the lowest `fitness`.
+fn rank_by(fitness: &[f64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..fitness.len()).collect();
+    order.sort_by(|&a, &b| fitness[b].total_cmp(&fitness[a]));
+    order
+}
+
+/// Chooses the indices of `count` members of a population with the given `fitness` by
+/// `selection`, possibly the same one several times, ordered from the fittest to the least fit.
+fn select_by(fitness: &[f64], selection: Selection, count: usize) -> Vec<usize> {
+    let ranking = rank_by(fitness);
+    let n = ranking.len();
+    if n == 0 {
+        return Vec::new();
     }
+    let mut rng = rng();
+    // Draws a position in the ranking, uniformly if no weight is positive.
+    let mut draw = |weights: &[f64]| match WeightedIndex::new(weights) {
+        Ok(dist) => dist.sample(&mut rng),
+        Err(_) => rng.gen_range(0..n),
+    };
+    let mut positions: Vec<usize> = match selection {
+        Selection::Truncation => (0..count.min(n)).collect(),
+        Selection::RouletteWheel => {
+            let worst = ranking.last().map_or(0.0, |&i| fitness[i]);
+            let weights: Vec<f64> = ranking.iter().map(|&i| fitness[i] - worst).collect();
+            (0..count).map(|_| draw(&weights)).collect()
+        }
+        Selection::Rank => {
+            let weights: Vec<f64> = (0..n).map(|r| (n - r) as f64).collect();
+            (0..count).map(|_| draw(&weights)).collect()
+        }
+        Selection::Tournament(k) => {
+            (0..count).map(|_| (0..k.max(1)).map(|_| rng.gen_range(0..n)).min().unwrap()).collect()
+        }
+    };
+    positions.sort_unstable();
+    positions.into_iter().map(|r| ranking[r]).collect()
+}
+
+/// Whether the genome encoded as `n` responds to the previous moves at all, rather than
+/// playing the same move after every context, and so needs memory.
+pub fn is_reactive(n: u8) -> bool {
+    let genome = number_to_genome(n);
+    genome[1..].iter().any(|&g| g != genome[1])
 }
 
 /// Mutates gene by NOT-ing its value at a random index.
 pub fn mutate(gene: &mut [bool]) {
-    let i = rand::thread_rng().gen_range(0..=4);
+    let i = rng().gen_range(0..gene.len());
     gene[i] = !gene[i];
 }
 
-/// Given two parent genomes, returns two child genomes with a 10% chance of mutation.
+/// Given two parent genomes of the same length, returns a child genome with a
+/// [`MUTATION_RATE`] chance of mutation, by the default [`GeneticConfig`].
 pub fn reproduce(p1: &Genome, p2: &Genome) -> Genome {
-    let mut child = [false; GENOME_LENGTH as usize];
-    for idx in 0..GENOME_LENGTH {
-        let i = idx as usize;
-        if i % 2 == 0 {
-            child[i] = p1[i];
-        } else {
-            child[i] = p2[i];
-        }
-    }
-    let mutation_dist = Bernoulli::new(0.1).unwrap();
-    if mutation_dist.sample(&mut rand::thread_rng()) {
+    reproduce_with(p1, p2, &GeneticConfig::default())
+}
+
+/// Like [`reproduce`], but crossing the parents over and mutating the child as `genetic` says.
+pub fn reproduce_with(p1: &Genome, p2: &Genome, genetic: &GeneticConfig) -> Genome {
+    let mut child = genetic.crossover.cross(p1, p2);
+    let mutation_dist = Bernoulli::new(genetic.mutation_rate).unwrap();
+    if mutation_dist.sample(&mut rng()) {
         mutate(&mut child);
     }
-    Box::new(child)
+    child
+}
+
+/// Where a member of a new generation comes from, by index among the opponents of the
+/// tournament it was bred from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Origin {
+    /// The same individual, which survived.
+    Survivor(usize),
+    /// A child of two parents, possibly mutated.
+    Child(usize, usize),
+}
+
+/// Returns the next generation bred from the opponents of `game` as `genetic` says: the
+/// survivors, fittest first, followed by the children of neighbours among the selected
+/// parents, going round them as many times as needed. The survivors are the elite of a
+/// population of `genetic.population_size`, or in steady-state mode everyone but the least
+/// fit. Works for genomes of any memory depth. When no parent is selected, as from an empty
+/// population or with a `generation_size` of 0, the survivors are all there is.
+pub fn breed(game: &Tournament, genetic: &GeneticConfig) -> Vec<Genome> {
+    breed_traced(game, genetic).into_iter().map(|(genome, _)| genome).collect()
+}
+
+/// Like [`breed`], but also returns the origin of every member of the new generation.
+/// A child mutates with the rate its first parent's rate gene gives, if it has one.
+pub fn breed_traced(game: &Tournament, genetic: &GeneticConfig) -> Vec<(Genome, Origin)> {
+    let genomes: Vec<Genome> = (0..game.opponents.len()).map(|i| game.genome_of(i)).collect();
+    breed_at_rates(&genomes, &game.fitnesses(), genetic, |i| {
+        game.mutation_rate_of(i, genetic)
+    })
+}
+
+/// Like [`breed_traced`], but from any population of `genomes` with the given `fitness`,
+/// higher being fitter, such as one scored outside a tournament.
+pub fn breed_population(
+    genomes: &[Genome],
+    fitness: &[f64],
+    genetic: &GeneticConfig,
+) -> Vec<(Genome, Origin)> {
+    breed_at_rates(genomes, fitness, genetic, |_| genetic.mutation_rate)
+}
+
+/// Like [`breed_population`], but the children of the individual `i` mutate with the
+/// probability `mutation_rate(i)`.
+fn breed_at_rates(
+    genomes: &[Genome],
+    fitness: &[f64],
+    genetic: &GeneticConfig,
+    mutation_rate: impl Fn(usize) -> f64,
+) -> Vec<(Genome, Origin)> {
+    let ranking = rank_by(fitness);
+    let (kept, size) = match genetic.steady_state {
+        Some(replaced) => (ranking.len().saturating_sub(replaced), ranking.len()),
+        None => (
+            genetic.elitism.min(genetic.population_size),
+            genetic.population_size,
+        ),
+    };
+    let mut new_gen: Vec<(Genome, Origin)> =
+        ranking.iter().take(kept).map(|&i| (genomes[i].clone(), Origin::Survivor(i))).collect();
+    let parents = select_by(fitness, genetic.selection, genetic.generation_size);
+    let mut i = 0;
+    while !parents.is_empty() && new_gen.len() < size {
+        let parent1 = parents[i % parents.len()];
+        let parent2 = parents[(i + 1) % parents.len()];
+        let genetic = GeneticConfig {
+            mutation_rate: mutation_rate(parent1),
+            ..*genetic
+        };
+        let child1 = reproduce_with(&genomes[parent1], &genomes[parent2], &genetic);
+        new_gen.push((child1, Origin::Child(parent1, parent2)));
+        i += 1;
+    }
+    new_gen
 }
 
-/// Given the fittest old generation of size [GENERATION_SIZE],
-/// returns the encoding for the new population, which is a box of encoded genomes
-/// of size [POPULATION_SIZE].
-pub fn get_new_generation(old_gen: Box<[Genome]>) -> Box<[u8]> {
-    let mut new_gen = old_gen.to_vec();
-    for i in 0..GENERATION_SIZE {
-        let parent1 = &old_gen[i];
-        let parent2 = &old_gen[(i + 1) % GENERATION_SIZE];
-        let child1 = reproduce(parent1, parent2);
-        new_gen.push(child1);
+/// Heritable traits, such as tags, of a generation bred as `origins` says from one whose
+/// members carry `traits`: survivors keep theirs and every child takes those of its first
+/// parent through `inherit`, which may mutate them.
+pub fn inherit_traits<T: Copy>(
+    traits: &[T],
+    origins: &[Origin],
+    mut inherit: impl FnMut(T) -> T,
+) -> Vec<T> {
+    origins
+        .iter()
+        .map(|&origin| match origin {
+            Origin::Survivor(i) => traits[i],
+            Origin::Child(parent, _) => inherit(traits[parent]),
+        })
+        .collect()
+}
+
+/// Like [`breed`] for memory-one genomes, returning the encoding for the new population,
+/// which is a box of encoded genomes.
+pub fn get_new_generation(game: &Tournament, genetic: &GeneticConfig) -> Box<[u8]> {
+    breed(game, genetic).iter().map(genome_to_number).collect()
+}
+
+/// Like [`get_new_generation`], but also returns the origin of every member of the new
+/// population.
+pub fn get_traced_generation(
+    game: &Tournament,
+    genetic: &GeneticConfig,
+) -> (Box<[u8]>, Vec<Origin>) {
+    let (population, origins): (Vec<u8>, Vec<Origin>) = breed_traced(game, genetic)
+        .into_iter()
+        .map(|(genome, origin)| (genome_to_number(&genome), origin))
+        .unzip();
+    (population.into_boxed_slice(), origins)
+}
+
+/// Like [`get_new_generation`], but every opponent of `game` has an age in generations and no
+/// one survives past `lifespan`, however fit. The `genetic.generation_size` fittest still
+/// reproduce, and their offspring fill every place left by the dead.
+///
+/// Returns the new population together with the age and the origin of each of its members.
+pub fn get_aged_generation(
+    game: &Tournament,
+    population: &[u8],
+    ages: &[u32],
+    lifespan: u32,
+    genetic: &GeneticConfig,
+) -> (Box<[u8]>, Vec<u32>, Vec<Origin>) {
+    let generation_size = genetic.generation_size;
+    let ranking = game.ranking();
+    let parents: Vec<usize> = ranking.iter().take(generation_size).copied().collect();
+    let survivors: Vec<usize> =
+        ranking.iter().copied().filter(|&i| ages[i] + 1 < lifespan).take(generation_size).collect();
+    let mut new_gen: Vec<u8> = survivors.iter().map(|&i| population[i]).collect();
+    let mut new_ages: Vec<u32> = survivors.iter().map(|&i| ages[i] + 1).collect();
+    let mut origins: Vec<Origin> = survivors.into_iter().map(Origin::Survivor).collect();
+    let mut i = 0;
+    while !parents.is_empty() && new_gen.len() < population.len() {
+        let (parent1, parent2) = (parents[i % parents.len()], parents[(i + 1) % parents.len()]);
+        let genetic = GeneticConfig {
+            mutation_rate: game.mutation_rate_of(parent1, genetic),
+            ..*genetic
+        };
+        let child = reproduce_with(
+            &number_to_genome(population[parent1]),
+            &number_to_genome(population[parent2]),
+            &genetic,
+        );
+        new_gen.push(genome_to_number(&child));
+        new_ages.push(0);
+        origins.push(Origin::Child(parent1, parent2));
+        i += 1;
     }
-    let new_gen: Vec<u8> = new_gen.iter().map(|g| genome_to_number(g)).collect();
-    new_gen.into_boxed_slice()
+    (new_gen.into_boxed_slice(), new_ages, origins)
 }
 
+/// Payoff ranking T > R > P > S with 2R > T + S: defection pays whatever the other does, but
+/// mutual cooperation beats mutual defection.
 pub fn prisoners_dillemma_rules(p1move: &Decision, p2move: &Decision) -> (i32, i32) {
     use Decision::*;
     match (p1move, p2move) {
@@ -290,7 +1988,61 @@ pub fn prisoners_dillemma_rules(p1move: &Decision, p2move: &Decision) -> (i32, i
     }
 }
 
-#[derive(Clone, Copy)]
+/// Payoff ranking R > T > P > S: cooperation pays only if the other cooperates too.
+pub fn stag_hunt_rules(p1move: &Decision, p2move: &Decision) -> (i32, i32) {
+    use Decision::*;
+    match (p1move, p2move) {
+        (Cooperate, Cooperate) => (0, 0),
+        (Cooperate, Defect) => (-3, -1),
+        (Defect, Cooperate) => (-1, -3),
+        (Defect, Defect) => (-2, -2),
+    }
+}
+
+/// Payoff ranking T > R > S > P: mutual defection is the worst outcome.
+pub fn chicken_rules(p1move: &Decision, p2move: &Decision) -> (i32, i32) {
+    use Decision::*;
+    match (p1move, p2move) {
+        (Cooperate, Cooperate) => (-1, -1),
+        (Cooperate, Defect) => (-2, 0),
+        (Defect, Cooperate) => (0, -2),
+        (Defect, Defect) => (-3, -3),
+    }
+}
+
+/// Payoff ranking R > T > S > P: cooperation dominates.
+pub fn harmony_rules(p1move: &Decision, p2move: &Decision) -> (i32, i32) {
+    use Decision::*;
+    match (p1move, p2move) {
+        (Cooperate, Cooperate) => (0, 0),
+        (Cooperate, Defect) => (-2, -1),
+        (Defect, Cooperate) => (-1, -2),
+        (Defect, Defect) => (-3, -3),
+    }
+}
+
+/// Payoff ranking T > P > R > S: defection dominates and mutual defection is preferred.
+pub fn deadlock_rules(p1move: &Decision, p2move: &Decision) -> (i32, i32) {
+    use Decision::*;
+    match (p1move, p2move) {
+        (Cooperate, Cooperate) => (-2, -2),
+        (Cooperate, Defect) => (-3, 0),
+        (Defect, Cooperate) => (0, -3),
+        (Defect, Defect) => (-1, -1),
+    }
+}
+
+/// Named symmetric 2x2 games the same participants can be played under.
+pub const GAME_PRESETS: [(&str, RewardFunc); 5] = [
+    ("Prisoner's dilemma", prisoners_dillemma_rules),
+    ("Stag hunt", stag_hunt_rules),
+    ("Chicken", chicken_rules),
+    ("Harmony", harmony_rules),
+    ("Deadlock", deadlock_rules),
+];
+
+/// Move of a participant in one round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Decision {
     Cooperate,
     Defect,
@@ -307,8 +2059,248 @@ impl Not for Decision {
     }
 }
 
-pub type DecisionTable = Box<dyn Fn(Option<Decision>, Option<Decision>) -> Decision>;
+/// What a participant knows when deciding on its next move against another.
+pub struct Context<'a> {
+    /// Own previous move against the other, `None` before the first round of a matchup and
+    /// after forgetting.
+    pub own_pm: Option<Decision>,
+    /// The other's previous move as remembered, `None` whenever `own_pm` is.
+    pub other_pm: Option<Decision>,
+    /// (own move, other's move as remembered) of every round of the matchup so far, oldest
+    /// first.
+    pub history: &'a [(Decision, Decision)],
+    /// Own public image score.
+    pub own_image: i32,
+    /// The other's public image score.
+    pub other_image: i32,
+}
+
+/// Decision rule of a participant. Every participant holds its own instance of its strategy
+/// for each partner, so a strategy may keep state about the matchup it plays.
+pub trait Strategy: Send {
+    /// Decides on the next move, or returns `None` to abstain from the round.
+    fn decide(&mut self, context: &Context) -> Option<Decision>;
+
+    /// Name the participants playing this strategy are listed under.
+    fn name(&self) -> &str;
+
+    /// Clears any state kept about the matchup whenever it starts afresh, after an abstention
+    /// or when the participant forgets the previous moves.
+    fn reset(&mut self) {}
+
+    /// Decides whether to punish the other for its move `other` in the round just played, at
+    /// a cost to both that only applies once punishment is switched on with
+    /// [`Tournament::with_punishment`].
+    fn punishes(&self, _other: Decision) -> bool {
+        false
+    }
+}
+
+/// Strategy deciding from own and the other's previous move, `None` in the first round.
+pub type DecisionTable = fn(Option<Decision>, Option<Decision>) -> Decision;
+
+/// Strategy deciding from every (own, other) move of the current matchup, oldest first.
+pub type HistoryTable = fn(&[(Decision, Decision)]) -> Decision;
+
+/// How a named strategy decides on its next move.
+#[derive(Clone, Copy)]
+pub enum Policy {
+    /// From the previous moves only.
+    MemoryOne(DecisionTable),
+    /// From the full history of the matchup.
+    History(HistoryTable),
+    /// Like [`handshake`] with the given opening.
+    Handshake(&'static [Decision]),
+    /// From own and the other's public image score.
+    Reputation(ReputationTable),
+    /// From the full history of the matchup, with the option to abstain.
+    Optional(OptionalTable),
+    /// From the previous moves only, punishing every defection on top.
+    Punishing(DecisionTable),
+}
+
+/// One of the fixed players' strategies, deciding by a plain function of what it knows.
+#[derive(Clone, Copy)]
+pub struct NamedStrategy {
+    pub name: &'static str,
+    pub policy: Policy,
+}
+
+impl Strategy for NamedStrategy {
+    fn decide(&mut self, context: &Context) -> Option<Decision> {
+        match self.policy {
+            Policy::MemoryOne(table) | Policy::Punishing(table) => {
+                Some(table(context.own_pm, context.other_pm))
+            }
+            Policy::History(table) => Some(table(context.history)),
+            Policy::Handshake(signal) => Some(handshake(signal, context.history)),
+            Policy::Reputation(table) => Some(table(context.own_image, context.other_image)),
+            Policy::Optional(table) => table(context.history),
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn punishes(&self, other: Decision) -> bool {
+        matches!(self.policy, Policy::Punishing(_)) && other == Decision::Defect
+    }
+}
+
+/// Strategy of an evolved participant, cooperating or defecting as its [`Genome`] says for
+/// the last rounds of the matchup it remembers.
+///
+/// A genome of memory depth `n` has one gene per sequence of up to `n` (own, other) move
+/// pairs: first the opening, then the four single pairs CC, CD, DC and DD, then the sixteen
+/// sequences of two pairs, oldest first, and so on, for [`genome_length`]`(n)` genes in all.
+/// Memory-one genomes thus read the same as the 5-bit genomes encoded by [`genome_to_number`].
+#[derive(Clone)]
+pub struct GenomeStrategy {
+    /// The genome's number for memory-one genomes, its genes as C and D otherwise, which
+    /// evolved participants are listed under.
+    name: String,
+    /// Number of previous rounds remembered.
+    depth: usize,
+    genes: Vec<Decision>,
+}
+
+impl GenomeStrategy {
+    /// The memory-one strategy of the genome encoded as `c`.
+    pub fn new(c: u8) -> Self {
+        Self::from_genome(&number_to_genome(c), 1)
+    }
+
+    /// The strategy of `genome` remembering `depth` previous rounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the genome is not [`genome_length`]`(depth)` genes long.
+    pub fn from_genome(genome: &[bool], depth: usize) -> Self {
+        assert_eq!(
+            genome.len(),
+            genome_length(depth),
+            "genome length should match its depth"
+        );
+        GenomeStrategy {
+            name: if depth == 1 {
+                genome_to_number(&genome.to_vec().into_boxed_slice()).to_string()
+            } else {
+                genome_label(genome)
+            },
+            depth,
+            genes: genome
+                .iter()
+                .map(|&b| {
+                    if b {
+                        Decision::Cooperate
+                    } else {
+                        Decision::Defect
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Strategy for GenomeStrategy {
+    fn decide(&mut self, context: &Context) -> Option<Decision> {
+        if self.depth == 1 {
+            // The previous moves rather than the history, so that forgetting and public
+            // standing apply.
+            return Some(self.genes[context_index(context.own_pm, context.other_pm)]);
+        }
+        let recent = &context.history[context.history.len().saturating_sub(self.depth)..];
+        let offset = genome_length(recent.len()) - 4usize.pow(recent.len() as u32);
+        let sequence = recent.iter().fold(0, |acc, &(own, other)| {
+            acc * 4 + context_index(Some(own), Some(other)) - 1
+        });
+        Some(self.genes[offset + sequence])
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A match played one round at a time against a strategy, with the other side's moves given
+/// from outside, such as by a person in the window. No noise, costs or punishment apply.
+pub struct InteractiveMatch {
+    opponent: Box<dyn Strategy>,
+    payoffs: PayoffTable,
+    /// Payoff of both sides in a round the opponent sits out.
+    loner_payoff: i32,
+    /// (opponent's move, own move) of every round since the opponent last abstained, as its
+    /// strategy sees them.
+    history: Vec<(Decision, Decision)>,
+    /// Own move and the opponent's move, `None` if it abstained, of every round, oldest first.
+    pub rounds: Vec<(Decision, Option<Decision>)>,
+    /// Own score and the opponent's score.
+    pub scores: (i32, i32),
+}
+
+impl InteractiveMatch {
+    /// A match against the named strategy at index `s` of [`strategy_names`], or `None` if
+    /// there is no such strategy.
+    pub fn against_named(s: usize, payoffs: PayoffTable, loner_payoff: i32) -> Option<Self> {
+        let strategy = *named_strategies().get(s)?;
+        Some(Self::against(Box::new(strategy), payoffs, loner_payoff))
+    }
+
+    /// A match against the memory-one genome encoded as `c`.
+    pub fn against_genome(c: u8, payoffs: PayoffTable, loner_payoff: i32) -> Self {
+        Self::against(Box::new(GenomeStrategy::new(c)), payoffs, loner_payoff)
+    }
+
+    fn against(opponent: Box<dyn Strategy>, payoffs: PayoffTable, loner_payoff: i32) -> Self {
+        InteractiveMatch {
+            opponent,
+            payoffs,
+            loner_payoff,
+            history: Vec::new(),
+            rounds: Vec::new(),
+            scores: (0, 0),
+        }
+    }
+
+    /// Plays a round in which the own move is `own`, decided before the opponent's is known.
+    /// Returns the opponent's move, or `None` if it sat the round out.
+    pub fn play(&mut self, own: Decision) -> Option<Decision> {
+        let previous = self.history.last().copied().unzip();
+        let context = Context {
+            own_pm: previous.0,
+            other_pm: previous.1,
+            history: &self.history,
+            own_image: 0,
+            other_image: 0,
+        };
+        let reply = self.opponent.decide(&context);
+        match reply {
+            Some(theirs) => {
+                let (mine, others) = self.payoffs[context_index(Some(own), Some(theirs)) - 1];
+                self.scores = (self.scores.0 + mine, self.scores.1 + others);
+                self.history.push((theirs, own));
+            }
+            None => {
+                let loner = self.loner_payoff;
+                self.scores = (self.scores.0 + loner, self.scores.1 + loner);
+                self.history.clear();
+                self.opponent.reset();
+            }
+        }
+        self.rounds.push((own, reply));
+        reply
+    }
+}
+
+/// Strategy deciding from every round played in the matchup so far, returning `None` to
+/// abstain from the next one.
+pub type OptionalTable = fn(&[(Decision, Decision)]) -> Option<Decision>;
+
+/// Strategy deciding from own and the other's image score.
+pub type ReputationTable = fn(i32, i32) -> Decision;
 
+/// Cooperates first, then copies the other's previous move.
 pub fn good_tit_for_tat(
     _own_prev_move: Option<Decision>,
     other_prev_move: Option<Decision>,
@@ -323,6 +2315,7 @@ pub fn good_tit_for_tat(
     }
 }
 
+/// Defects first, then copies the other's previous move.
 pub fn sus_tit_for_tat(
     _own_prev_move: Option<Decision>,
     other_prev_move: Option<Decision>,
@@ -337,23 +2330,27 @@ pub fn sus_tit_for_tat(
     }
 }
 
+/// Always cooperates.
 pub fn naive(_own_prev_move: Option<Decision>, _other_prev_move: Option<Decision>) -> Decision {
     Decision::Cooperate
 }
 
+/// Always defects.
 pub fn evil(_own_prev_move: Option<Decision>, _other_prev_move: Option<Decision>) -> Decision {
     Decision::Defect
 }
 
+/// Cooperates with probability one half.
 pub fn random(_own_prev_move: Option<Decision>, _other_prev_move: Option<Decision>) -> Decision {
     let dist = Bernoulli::new(0.5).unwrap();
-    let res = dist.sample(&mut rand::thread_rng());
+    let res = dist.sample(&mut rng());
     match res {
         true => Decision::Cooperate,
         false => Decision::Defect,
     }
 }
 
+/// Cooperates first, then exactly when the previous moves differed.
 pub fn xor(own_prev_move: Option<Decision>, other_prev_move: Option<Decision>) -> Decision {
     use Decision::*;
     match (own_prev_move, other_prev_move) {
@@ -368,6 +2365,7 @@ pub fn xor(own_prev_move: Option<Decision>, other_prev_move: Option<Decision>) -
     }
 }
 
+/// Does the opposite of [`good_tit_for_tat`].
 pub fn opposite_tit_for_tat(
     own_prev_move: Option<Decision>,
     other_prev_move: Option<Decision>,
@@ -375,6 +2373,7 @@ pub fn opposite_tit_for_tat(
     !good_tit_for_tat(own_prev_move, other_prev_move)
 }
 
+/// Cooperates first, then exactly when the previous moves were the same.
 pub fn xnor(own_prev_move: Option<Decision>, other_prev_move: Option<Decision>) -> Decision {
     use Decision::*;
     match (own_prev_move, other_prev_move) {
@@ -404,18 +2403,568 @@ fn and(own_prev_move: Option<Decision>, other_prev_move: Option<Decision>) -> De
     }
 }
 
+/// Defects first, then exactly when both cooperated in the previous round.
 pub fn nand(own_prev_move: Option<Decision>, other_prev_move: Option<Decision>) -> Decision {
     !and(own_prev_move, other_prev_move)
 }
 
+/// Cooperates with probability 0.3.
 pub fn random_biased(
     _own_prev_move: Option<Decision>,
     _other_prev_move: Option<Decision>,
 ) -> Decision {
     let dist = Bernoulli::new(0.3).unwrap();
-    let res = dist.sample(&mut rand::thread_rng());
+    let res = dist.sample(&mut rng());
     match res {
         true => Decision::Cooperate,
         false => Decision::Defect,
     }
 }
+
+/// Chance that [`extortioner`] cooperates after the (own, other) moves CC, CD, DC and DD.
+const EXTORTION_RATES: [f64; 4] = [5.0 / 6.0, 1.0 / 6.0, 2.0 / 3.0, 0.0];
+
+/// Press and Dyson's zero-determinant extortioner with extortion factor 2, for the payoffs of
+/// [`prisoners_dillemma_rules`]: whatever the other plays, its surplus over mutual defection
+/// ends up twice the other's. Cooperates first, then as [`EXTORTION_RATES`] says.
+pub fn extortioner(own_prev_move: Option<Decision>, other_prev_move: Option<Decision>) -> Decision {
+    use Decision::*;
+    let rate = match (own_prev_move, other_prev_move) {
+        (None, None) => return Cooperate,
+        (Some(own_pm), Some(other_pm)) => match (own_pm, other_pm) {
+            (Cooperate, Cooperate) => EXTORTION_RATES[0],
+            (Cooperate, Defect) => EXTORTION_RATES[1],
+            (Defect, Cooperate) => EXTORTION_RATES[2],
+            (Defect, Defect) => EXTORTION_RATES[3],
+        },
+        (Some(_), None) | (None, Some(_)) => unreachable!("impossible move compination"),
+    };
+    match Bernoulli::new(rate).unwrap().sample(&mut rng()) {
+        true => Cooperate,
+        false => Defect,
+    }
+}
+
+/// Rounds of unconditional cooperation Tranquilizer plays to build trust.
+const TRANQUILIZER_TRUST_ROUNDS: usize = 10;
+/// Fraction of its own moves Tranquilizer keeps its defections below.
+const TRANQUILIZER_MAX_DEFECTION_RATE: f64 = 0.25;
+
+/// Cooperates to build trust, then retaliates against defections and slips in a defection
+/// after two rounds of mutual cooperation, as long as its own defection rate stays below
+/// [`TRANQUILIZER_MAX_DEFECTION_RATE`].
+pub fn tranquilizer(history: &[(Decision, Decision)]) -> Decision {
+    use Decision::*;
+    if history.len() < TRANQUILIZER_TRUST_ROUNDS {
+        return Cooperate;
+    }
+    let defections = history.iter().filter(|(own, _)| *own == Defect).count();
+    let rate = (defections + 1) as f64 / (history.len() + 1) as f64;
+    let within_budget = rate < TRANQUILIZER_MAX_DEFECTION_RATE;
+    match history[history.len() - 2..] {
+        [_, (_, Defect)] if within_budget => Defect,
+        [(Cooperate, Cooperate), (Cooperate, Cooperate)] if within_budget => Defect,
+        _ => Cooperate,
+    }
+}
+
+/// Cooperates until the other defects once, then defects for the rest of the matchup.
+pub fn grim_trigger(history: &[(Decision, Decision)]) -> Decision {
+    if history.iter().any(|&(_, other)| other == Decision::Defect) {
+        Decision::Defect
+    } else {
+        Decision::Cooperate
+    }
+}
+
+/// Cooperates until the other defects, then answers with as many defections as the other
+/// has made so far, followed by two cooperations to calm things down. Defections during such
+/// a response are counted but do not start another one.
+pub fn gradual(history: &[(Decision, Decision)]) -> Decision {
+    // Replays the matchup to find out where in a response the next round falls.
+    let (mut defections, mut punishing, mut calming) = (0, 0, 0);
+    for &(_, other) in history {
+        if punishing > 0 {
+            punishing -= 1;
+            if punishing == 0 {
+                calming = 2;
+            }
+        } else if calming > 0 {
+            calming -= 1;
+        }
+        if other == Decision::Defect {
+            defections += 1;
+            if punishing == 0 && calming == 0 {
+                punishing = defections;
+            }
+        }
+    }
+    if punishing > 0 {
+        Decision::Defect
+    } else {
+        Decision::Cooperate
+    }
+}
+
+/// Plays `signal` as an opening, then cooperates for the rest of the matchup if the other
+/// played the same opening and defects otherwise, so that copies recognise each other.
+pub fn handshake(signal: &[Decision], history: &[(Decision, Decision)]) -> Decision {
+    if let Some(&d) = signal.get(history.len()) {
+        return d;
+    }
+    let mirrored = history.iter().zip(signal).all(|((_, other), own)| other == own);
+    if mirrored {
+        Decision::Cooperate
+    } else {
+        Decision::Defect
+    }
+}
+
+/// Cooperates with anyone whose image score is not negative, whatever they did to it.
+pub fn image_scorer(_own_image: i32, other_image: i32) -> Decision {
+    if other_image >= 0 {
+        Decision::Cooperate
+    } else {
+        Decision::Defect
+    }
+}
+
+/// Never takes part, settling for the loner payoff.
+pub fn loner(_history: &[(Decision, Decision)]) -> Option<Decision> {
+    None
+}
+
+/// Plays tit for tat but walks away for good once the other has defected twice in a row.
+pub fn walk_away(history: &[(Decision, Decision)]) -> Option<Decision> {
+    use Decision::*;
+    match history {
+        [.., (_, Defect), (_, Defect)] => None,
+        [.., (_, other)] => Some(*other),
+        [] => Some(Cooperate),
+    }
+}
+
+/// The strategy a participant listed under `name` plays, a named strategy or an evolved
+/// genome as named by [`GenomeStrategy`].
+fn strategy_named(name: &str) -> Option<Box<dyn Strategy>> {
+    if let Some(s) = named_strategies().into_iter().find(|s| s.name == name) {
+        return Some(Box::new(s));
+    }
+    if let Ok(c) = name.parse::<u8>() {
+        return (usize::from(c) < GENOME_COUNT).then(|| Box::new(GenomeStrategy::new(c)) as _);
+    }
+    let genome: Genome = name
+        .chars()
+        .map(|c| match c {
+            'C' => Some(true),
+            'D' => Some(false),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    let depth = (1..).take_while(|&d| genome_length(d) <= genome.len()).last()?;
+    (genome_length(depth) == genome.len())
+        .then(|| Box::new(GenomeStrategy::from_genome(&genome, depth)) as _)
+}
+
+/// Writes the strategies of a [`Player`] by name, as [`strategy_named`] reads them back; state
+/// they keep about a matchup starts afresh.
+fn serialize_strategies<S: Serializer>(
+    strategies: &[Box<dyn Strategy>],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(strategies.iter().map(|s| s.name()))
+}
+
+fn deserialize_strategies<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Box<dyn Strategy>>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|name| {
+            strategy_named(name)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown strategy `{name}`")))
+        })
+        .collect()
+}
+
+impl Tournament {
+    /// Dumps the whole state of the tournament as JSON, finished or not: its rules, the scores
+    /// and schedule grids, and every participant with its strategy, which for an evolved one is
+    /// its genome, and its memory of every other participant. [`Tournament::from_json`] reads it
+    /// back.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a tournament serialises to JSON")
+    }
+
+    /// Writes the state of the tournament to `path` as JSON, through a temporary file so that a
+    /// crash while writing leaves the previous checkpoint intact.
+    pub fn save_checkpoint(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let partial = path.with_extension("partial");
+        fs::write(&partial, self.to_json())?;
+        fs::rename(partial, path)
+    }
+
+    /// Reads a tournament saved by [`Tournament::save_checkpoint`], to continue it. Fails if the
+    /// file is not a tournament or its grids and memories do not match its participants.
+    pub fn resume(path: impl AsRef<Path>) -> io::Result<Self> {
+        let invalid = |err| io::Error::new(io::ErrorKind::InvalidData, err);
+        Tournament::from_json(&fs::read_to_string(path)?).map_err(invalid)
+    }
+
+    /// Reads a tournament written by [`Tournament::to_json`], which plays on from where it was
+    /// dumped. Fails if a field is missing or invalid, a strategy is unknown or the grids and
+    /// memories do not match the participants.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let game: Tournament = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        game.check()?;
+        Ok(game)
+    }
+
+    /// Checks that the grids have a row per opponent and a column per player, and that every
+    /// participant remembers each one on the other side.
+    fn check(&self) -> Result<(), String> {
+        let (rows, columns) = (self.opponents.len(), self.players.len());
+        for (key, size) in [
+            ("scores", self.scores.size()),
+            ("scheduled", self.scheduled.size()),
+        ] {
+            if size != (rows, columns) {
+                return Err(format!("`{key}` should be a {rows} x {columns} grid"));
+            }
+        }
+        let sides = [(&self.players, rows), (&self.opponents, columns)];
+        for (side, others) in sides {
+            for p in side.iter() {
+                let lengths = [
+                    p.prev_move_self.len(),
+                    p.prev_move_other.len(),
+                    p.history.len(),
+                    p.forgotten.len(),
+                    p.strategies.len(),
+                ];
+                let forgotten = p.history.iter().zip(&p.forgotten).all(|(h, &f)| f <= h.len());
+                if lengths.iter().any(|&n| n != others) || !forgotten {
+                    return Err(format!("`{}` does not remember every participant", p.id));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random;
+    use rand::rngs::mock::StepRng;
+
+    /// Number of children drawn per operator and pair of parents.
+    const TRIALS: usize = 200;
+
+    /// Number of places where consecutive genes of `child` come from different parents, given
+    /// parents that differ in every gene.
+    fn switches(child: &[bool], p1: &[bool]) -> usize {
+        let from_first: Vec<bool> = child.iter().zip(p1).map(|(c, g)| c == g).collect();
+        from_first.windows(2).filter(|w| w[0] != w[1]).count()
+    }
+
+    #[test]
+    fn children_take_every_gene_from_a_parent() {
+        for depth in 1..=3 {
+            let (p1, p2) = (random_genome(depth), random_genome(depth));
+            for crossover in Crossover::ALL {
+                for _ in 0..TRIALS {
+                    let child = crossover.cross(&p1, &p2);
+                    assert_eq!(child.len(), genome_length(depth));
+                    assert!((0..child.len()).all(|i| child[i] == p1[i] || child[i] == p2[i]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn memory_one_children_are_valid_genomes() {
+        for crossover in Crossover::ALL {
+            for _ in 0..TRIALS {
+                let child = crossover.cross(&number_to_genome(0b10110), &number_to_genome(0b01011));
+                assert!((genome_to_number(&child) as usize) < GENOME_COUNT);
+                assert_eq!(number_to_genome(genome_to_number(&child)), child);
+            }
+        }
+    }
+
+    #[test]
+    fn point_crossovers_cut_at_most_where_they_say() {
+        let (p1, p2) = (vec![true; 21], vec![false; 21]);
+        for _ in 0..TRIALS {
+            assert!(switches(&Crossover::SinglePoint.cross(&p1, &p2), &p1) <= 1);
+            assert!(switches(&Crossover::TwoPoint.cross(&p1, &p2), &p1) <= 2);
+        }
+        assert_eq!(switches(&Crossover::Alternating.cross(&p1, &p2), &p1), 20);
+    }
+
+    #[test]
+    fn identical_parents_have_identical_children() {
+        let parent = random_genome(2);
+        for crossover in Crossover::ALL {
+            assert_eq!(crossover.cross(&parent, &parent), parent);
+        }
+    }
+
+    /// Population after evolving `generations` generations from the initial one with noise,
+    /// forgetting and stochastic operators, so that nearly every step draws random numbers.
+    fn evolve(generations: usize) -> Box<[u8]> {
+        let genetic = GeneticConfig {
+            selection: Selection::Tournament(3),
+            crossover: Crossover::Uniform,
+            ..Default::default()
+        };
+        let mut population = genetic.initial_population();
+        for _ in 0..generations {
+            let mut game = Tournament::from(10, prisoners_dillemma_rules, population)
+                .with_noise(0.05, 0.05)
+                .with_forgetting(0.1);
+            game.run();
+            population = get_new_generation(&game, &genetic);
+        }
+        population
+    }
+
+    #[test]
+    fn same_seed_repeats_the_evolution() {
+        random::seed(42);
+        let first = evolve(10);
+        random::seed(42);
+        assert_eq!(evolve(10), first);
+    }
+
+    #[test]
+    fn injected_generator_decides_random_moves() {
+        let strategies = named_strategies();
+        let pick = |name| *strategies.iter().find(|s| s.name == name).unwrap();
+        // A generator drawing only zeros makes every Bernoulli draw succeed, so the random
+        // strategy always cooperates.
+        let transcript = random::with_rng(StepRng::new(0, 0), || {
+            let mut game = Tournament::among(
+                10,
+                prisoners_dillemma_rules,
+                &[pick("random"), pick("evil")],
+            );
+            game.play(1, 0);
+            game.transcript(1, 0)
+        });
+        assert_eq!(
+            transcript,
+            vec![(Decision::Cooperate, Decision::Defect); 10]
+        );
+    }
+
+    #[test]
+    fn grim_trigger_forgives_what_it_forgot() {
+        use Decision::*;
+        let strategies = named_strategies();
+        let pick = |name| *strategies.iter().find(|s| s.name == name).unwrap();
+        let play = |forgetting| {
+            let mut game = Tournament::among(
+                5,
+                prisoners_dillemma_rules,
+                &[pick("grim\ntrigger"), pick("evil")],
+            )
+            .with_forgetting(forgetting);
+            game.run();
+            game.transcript(1, 0)
+        };
+        assert_eq!(play(0.0)[1..], [(Defect, Defect); 4]);
+        assert_eq!(play(1.0), [(Cooperate, Defect); 5]);
+    }
+
+    #[test]
+    fn moralist_fines_every_defection_once_punishment_is_on() {
+        let strategies = named_strategies();
+        let pick = |name| *strategies.iter().find(|s| s.name == name).unwrap();
+        let play = |punishment: bool| {
+            let game = Tournament::among(
+                5,
+                prisoners_dillemma_rules,
+                &[pick("moralist"), pick("evil")],
+            );
+            let mut game = if punishment {
+                game.with_punishment(&[], PUNISH_COST, PUNISH_FINE)
+            } else {
+                game
+            };
+            game.play(1, 0)
+        };
+        let (evil, moralist) = play(false);
+        assert_eq!(
+            play(true),
+            (evil - 5 * PUNISH_FINE, moralist - 5 * PUNISH_COST)
+        );
+    }
+
+    #[test]
+    fn rate_genes_replace_the_mutation_rate() {
+        let genetic = GeneticConfig {
+            mutation_rate: 1.0,
+            ..GeneticConfig::default()
+        };
+        let population = genetic.initial_population();
+        let mut game = Tournament::from(10, prisoners_dillemma_rules, population.clone())
+            .with_rate_genes(&vec![0; population.len()]);
+        game.run();
+        for (child, origin) in breed_traced(&game, &genetic) {
+            if let Origin::Child(p1, p2) = origin {
+                let (p1, p2) = (
+                    number_to_genome(population[p1]),
+                    number_to_genome(population[p2]),
+                );
+                assert_eq!(child, genetic.crossover.cross(&p1, &p2));
+            }
+        }
+    }
+
+    #[test]
+    fn threads_do_not_change_the_outcome() {
+        let population = GeneticConfig {
+            population_size: 30,
+            ..GeneticConfig::default()
+        }
+        .initial_population();
+        let play = |workers| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(workers).build().unwrap();
+            pool.install(|| {
+                random::seed(7);
+                let mut game =
+                    Tournament::with_round_robin(10, prisoners_dillemma_rules, &population, &[])
+                        .with_noise(0.1, 0.1)
+                        .with_forgetting(0.1);
+                game.run();
+                game.to_json()
+            })
+        };
+        assert_eq!(play(1), play(4));
+    }
+
+    #[test]
+    fn json_dump_reads_back() {
+        let mut game = Tournament::with_custom_players(
+            10,
+            prisoners_dillemma_rules,
+            GeneticConfig::default().initial_population(),
+            &[("mine".to_string(), 9)],
+        )
+        .with_noise(0.05, 0.0)
+        .with_random_pairing(5);
+        game.run();
+        let dump = game.to_json();
+        let restored = Tournament::from_json(&dump).unwrap();
+        assert_eq!(restored.to_json(), dump);
+        assert_eq!(restored.scores, game.scores);
+    }
+
+    #[test]
+    fn checkpoint_resumes_the_tournament() {
+        let mut game = Tournament::from(
+            10,
+            prisoners_dillemma_rules,
+            GeneticConfig::default().initial_population(),
+        );
+        game.run();
+        let path = std::env::temp_dir().join(format!("checkpoint-{}.json", std::process::id()));
+        game.save_checkpoint(&path).unwrap();
+        let resumed = Tournament::resume(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            resumed.select_fittest_and_bestscore(5).unwrap(),
+            game.select_fittest_and_bestscore(5).unwrap()
+        );
+    }
+
+    #[test]
+    fn checkpoint_with_missing_memories_is_rejected() {
+        let game = Tournament::from(
+            10,
+            prisoners_dillemma_rules,
+            GeneticConfig::default().initial_population(),
+        );
+        let mut state = serde_json::to_value(&game).unwrap();
+        state["players"][0]["forgotten"] = serde_json::json!([]);
+        let path = std::env::temp_dir().join(format!("truncated-{}.json", std::process::id()));
+        fs::write(&path, state.to_string()).unwrap();
+        let resumed = Tournament::resume(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(resumed.is_err_and(|err| err.kind() == io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn observer_follows_every_round() {
+        let population = GeneticConfig::default().initial_population();
+        let mut game =
+            Tournament::with_custom_players(12, prisoners_dillemma_rules, population, &[]);
+        let mut totals = Vec::new();
+        game.run_observed(|game| totals.push(game.standings().iter().map(|s| s.score).sum()));
+        let played: i32 =
+            game.matchups().iter().flat_map(|(_, games)| games.iter().map(|m| m.own_score)).sum();
+        assert_eq!(totals.len(), 12);
+        assert_eq!(totals.last(), Some(&played));
+    }
+
+    #[test]
+    fn champions_replace_the_first_individuals() {
+        let genetic = GeneticConfig {
+            population_size: 4,
+            ..GeneticConfig::default()
+        };
+        assert_eq!(&*genetic.seeded_population(&[9, 9]), &[9, 9, 2, 3]);
+        assert_eq!(genetic.seeded_population(&[7; 10]).len(), 4);
+    }
+
+    #[test]
+    fn tit_for_tat_answers_the_previous_move() {
+        use Decision::*;
+        let payoffs = payoff_table(prisoners_dillemma_rules);
+        let mut game = InteractiveMatch::against_named(0, payoffs, -2).unwrap();
+        let replies: Vec<_> = [Defect, Cooperate, Cooperate].map(|m| game.play(m)).into();
+        assert_eq!(replies, [Some(Cooperate), Some(Defect), Some(Cooperate)]);
+        // Defecting on a cooperator pays 0 and the sucker -3, mutual cooperation -1 each.
+        assert_eq!(game.scores, (-4, -4));
+        assert_eq!(game.rounds.len(), 3);
+    }
+
+    #[test]
+    fn tit_for_tat_reads_as_a_decision_table() {
+        assert_eq!(
+            describe_genome(0b11010),
+            "opening move: C; after CC: C; after CD: D; after DC: C; after DD: D"
+        );
+    }
+
+    #[test]
+    fn empty_population_breeds_nothing() {
+        let mut game =
+            Tournament::with_custom_players(10, prisoners_dillemma_rules, Box::new([]), &[]);
+        game.run();
+        assert_eq!(game.select_fittest_and_bestscore(5), None);
+        for selection in [
+            Selection::Truncation,
+            Selection::RouletteWheel,
+            Selection::Tournament(3),
+        ] {
+            let genetic = GeneticConfig {
+                selection,
+                ..GeneticConfig::default()
+            };
+            assert!(breed(&game, &genetic).is_empty());
+        }
+        let genetic = GeneticConfig {
+            generation_size: 0,
+            elitism: 2,
+            ..GeneticConfig::default()
+        };
+        assert_eq!(
+            breed_population(&vec![random_genome(1); 4], &[1.0; 4], &genetic).len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, BufReader, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File the metadata of every finished run is appended to, one tab-separated line per run.
+pub const HISTORY_PATH: &str = "run_history.tsv";
+/// Directory the series of every finished run is written to.
+const RESULTS_DIR: &str = "runs";
+
+/// Metadata of a finished simulation run.
+#[derive(Clone)]
+pub struct RunRecord {
+    /// Seconds since the Unix epoch at which the run finished.
+    pub timestamp: u64,
+    /// Configuration the run was started with, in the app's `key=value` format.
+    pub config: String,
+    /// Number of generations simulated.
+    pub generations: usize,
+    /// Score and genome of the best individual of the last generation.
+    pub mvp: Option<(i32, u8)>,
+    /// File the run's per-generation series was exported to.
+    pub results: String,
+}
+
+impl RunRecord {
+    /// Creates the record of a run finishing now, whose results go to a file named after the
+    /// time.
+    pub fn now(config: String, generations: usize, mvp: Option<(i32, u8)>) -> Self {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        RunRecord {
+            timestamp,
+            config,
+            generations,
+            mvp,
+            results: format!("{RESULTS_DIR}/run-{timestamp}.csv"),
+        }
+    }
+
+    /// Short hash of the configuration, equal for runs started with the same settings.
+    pub fn config_hash(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.config.hash(&mut hasher);
+        format!("{:08x}", hasher.finish() as u32)
+    }
+
+    /// The time the run finished at as `YYYY-MM-DD hh:mm` in UTC.
+    pub fn time(&self) -> String {
+        let (days, seconds) = (self.timestamp / 86400, self.timestamp % 86400);
+        // Civil date from days since the epoch, after Howard Hinnant's algorithm.
+        let z = days as i64 + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + (month <= 2) as i64;
+        format!(
+            "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60
+        )
+    }
+
+    fn to_line(&self) -> String {
+        let (score, genome) = match self.mvp {
+            Some((score, genome)) => (score.to_string(), genome.to_string()),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        [
+            self.timestamp.to_string(),
+            self.generations.to_string(),
+            score,
+            genome,
+            self.results.clone(),
+            self.config.clone(),
+        ]
+        .join("\t")
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let timestamp = fields.next()?.parse().ok()?;
+        let generations = fields.next()?.parse().ok()?;
+        let score = fields.next()?.parse().ok();
+        let genome = fields.next()?.parse().ok();
+        Some(RunRecord {
+            timestamp,
+            generations,
+            mvp: score.zip(genome),
+            results: fields.next()?.to_string(),
+            config: fields.next()?.to_string(),
+        })
+    }
+}
+
+/// Reads every run recorded so far, oldest first, skipping lines that cannot be read.
+pub fn load() -> Vec<RunRecord> {
+    let Ok(file) = File::open(HISTORY_PATH) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| RunRecord::from_line(&line))
+        .collect()
+}
+
+/// Runs that ended with a best individual, highest score first: the hall of fame.
+pub fn hall_of_fame(records: &[RunRecord]) -> Vec<&RunRecord> {
+    let mut ranked: Vec<&RunRecord> = records.iter().filter(|r| r.mvp.is_some()).collect();
+    ranked.sort_by_key(|r| std::cmp::Reverse(r.mvp.map(|(score, _)| score)));
+    ranked
+}
+
+/// Up to `n` distinct genomes from the top of the hall of fame.
+pub fn champions(records: &[RunRecord], n: usize) -> Vec<u8> {
+    let mut genomes = Vec::new();
+    for (_, genome) in hall_of_fame(records).iter().filter_map(|r| r.mvp) {
+        if genomes.len() == n {
+            break;
+        }
+        if !genomes.contains(&genome) {
+            genomes.push(genome);
+        }
+    }
+    genomes
+}
+
+/// Appends a run to the history, creating the results directory its series goes to.
+pub fn append(record: &RunRecord) -> io::Result<()> {
+    fs::create_dir_all(RESULTS_DIR)?;
+    let mut handle = OpenOptions::new().create(true).append(true).open(HISTORY_PATH)?;
+    writeln!(handle, "{}", record.to_line())
+}
+
+/// Opens a file with the program the operating system associates with it.
+pub fn open(path: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    command.arg(path).spawn().map(|_| ())
+}
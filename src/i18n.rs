@@ -0,0 +1,424 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Languages the user interface can be shown in.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Language {
+    English,
+    Japanese,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Japanese];
+
+    /// Name of the language in the language itself.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Japanese => "日本語",
+        }
+    }
+}
+
+/// Language the user interface is currently shown in, as a [`Language`] discriminant.
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::Japanese,
+        _ => Language::English,
+    }
+}
+
+/// Translates an English user interface string into the current language, keeping the
+/// English text when there is no translation for it.
+pub fn tr(text: &'static str) -> &'static str {
+    match language() {
+        Language::English => text,
+        Language::Japanese => japanese(text).unwrap_or(text),
+    }
+}
+
+/// Translates `template` like [`tr`] and replaces each `{}` in it by the next of `args`, for
+/// messages that carry values. Translations keep the `{}` in the order of the English.
+pub fn tr_fill(template: &'static str, args: &[&dyn Display]) -> String {
+    let mut parts = tr(template).split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (part, arg) in parts.zip(args) {
+        text.push_str(&arg.to_string());
+        text.push_str(part);
+    }
+    text
+}
+
+fn japanese(text: &str) -> Option<&'static str> {
+    Some(match text {
+        " generations/s" => " 世代/秒",
+        "#Games Per Gen" => "世代あたりの対戦数",
+        "#Generations" => "世代数",
+        "#Parents Per Gen" => "世代あたりの親の数",
+        "#Population" => "個体数",
+        "#Replaced Per Gen" => "世代あたりの入れ替え数",
+        "95% CI" => "95%信頼区間",
+        "Add" => "追加",
+        "Add population" => "集団を追加",
+        "Against itself, per round" => "自分自身との対戦、1ラウンドあたり",
+        "All players" => "全プレイヤー",
+        "Alternate with" => "交互に行うゲーム",
+        "Alternating" => "交互",
+        "Analytic" => "解析値",
+        "Apply to next run" => "次の実行に適用",
+        "Average fitness of evolved individuals by how each gene responds." => {
+            "各遺伝子の応答ごとの進化個体の平均適応度。"
+        }
+        "Axelrod 1980 round-robin" => "アクセルロッド1980年総当たり戦",
+        "Beats components" => "勝つ成分",
+        "Behaviour" => "行動",
+        "Best" => "最高",
+        "Best response" => "最適反応",
+        "Best winning score" => "優勝時の最高スコア",
+        "Bracket" => "トーナメント表",
+        "Bradley-Terry ratings over all generations since the last reset." => {
+            "最後のリセット以降の全世代にわたるブラッドリー・テリー評価。"
+        }
+        "Breaches" => "契約違反",
+        "Can be invaded" => "侵入されうる",
+        "Cheap talk" => "チープトーク",
+        "Choose an opponent and start a new match, then pick your move every round." => "相手を選んで新しい対戦を始め、毎ラウンド自分の手を選んでください。",
+        "Clean" => "ノイズなし",
+        "Co-evolution" => "共進化",
+        "Completed all {} generations" => "全{}世代を完了",
+        "Consensus" => "合意度",
+        "Constant" => "一定",
+        "Config" => "設定",
+        "Contracts" => "契約",
+        "Converged at generation {}" => "世代{}で収束",
+        "Cooperate" => "協力",
+        "Cooperate at the start and after each outcome, own move first:" => {
+            "最初と各結果の後に協力する(自分の手が先):"
+        }
+        "Cooperates" => "協力する",
+        "Cooperation" => "協力率",
+        "Cooperation cost" => "協力のコスト",
+        "Cooperation rate of the winner" => "勝者の協力率",
+        "Crossover" => "交叉",
+        "Cumulative" => "累積",
+        "Custom strategies" => "カスタム戦略",
+        "Decreasing" => "減少",
+        "Defect" => "裏切り",
+        "Defects" => "裏切る",
+        "Difference" => "差",
+        "Dismiss" => "無視",
+        "Distinct genomes (share)" => "異なるゲノムの割合",
+        "Distinct genomes in the latest generation" => "最新世代の異なるゲノム数",
+        "Diversity" => "多様性",
+        "Dominance" => "優位関係",
+        "Dominance is transitive: there is no cycle of head-to-head wins." => {
+            "優位関係は推移的です:直接対決の勝利に循環はありません。"
+        }
+        "ESS genomes: {}" => "ESSのゲノム: {}",
+        "Elitism" => "エリート保存数",
+        "Enters every named strategy together with a noisy copy of itself." => {
+            "各戦略をノイズのある複製と一緒に参加させます。"
+        }
+        "Environments" => "環境",
+        "Every finished simulation is listed here with its configuration." => {
+            "終了したシミュレーションがその設定とともにここに表示されます。"
+        }
+        "Evolution" => "進化",
+        "Evolutionarily stable" => "進化的に安定",
+        "Evolvability" => "進化可能性",
+        "Evolved" => "進化個体",
+        "Evolved genome" => "進化したゲノム",
+        "Evolves a population in every environment above and compares their fitness." => {
+            "上の各環境で集団を進化させ、適応度を比較します。"
+        }
+        "Execution noise" => "実行ノイズ",
+        "Export bracket" => "トーナメント表を書き出す",
+        "Export chart" => "グラフを書き出す",
+        "Export cheap talk" => "チープトークを書き出す",
+        "Export contracts" => "契約を書き出す",
+        "Export forgetting report" => "忘却レポートを書き出す",
+        "Export genotypes" => "遺伝子型を書き出す",
+        "Export green-beard" => "緑ひげを書き出す",
+        "Export kin selection" => "血縁選択を書き出す",
+        "Export memory depth" => "記憶の深さを書き出す",
+        "Export meta-tournament" => "メタトーナメントを書き出す",
+        "Export mixed openings" => "混合初手を書き出す",
+        "Export noise report" => "ノイズレポートを書き出す",
+        "Export populations" => "集団を書き出す",
+        "Export punishment" => "罰を書き出す",
+        "Export results" => "結果を書き出す",
+        "Export round-robin" => "総当たり戦を書き出す",
+        "Export self-adaptive mutation" => "自己適応的突然変異を書き出す",
+        "Export sensitivity" => "感度を書き出す",
+        "Export series" => "時系列を書き出す",
+        "Export statistics" => "統計を書き出す",
+        "Export variants" => "変種を書き出す",
+        "Final" => "決勝",
+        "Finished" => "終了時刻",
+        "Fixed" => "固定",
+        "Fixed players" => "固定プレイヤー",
+        "Fixed seed" => "シードを固定",
+        "Forgetful" => "忘れっぽい",
+        "Forgetting" => "忘却",
+        "Founder lineages left" => "残っている創始者の系統",
+        "Fraction" => "割合",
+        "Full round robin" => "完全な総当たり",
+        "Games" => "ゲーム",
+        "Gene" => "遺伝子",
+        "Generations" => "世代数",
+        "Generations present" => "存在した世代数",
+        "Genes" => "遺伝子",
+        "Genetic algorithm" => "遺伝的アルゴリズム",
+        "Genome" => "ゲノム",
+        "Genome counts per generation, genome 0 at the top." => "世代ごとのゲノム数(上がゲノム0)。",
+        "Genome {} against itself: {} per round, cooperating {}% of the time." => "ゲノム{}同士: 1ラウンドあたり{}、{}%の確率で協力。",
+        "Gini" => "ジニ係数",
+        "Green beards" => "緑ひげ",
+        "Hall of fame" => "殿堂",
+        "Heatmap" => "ヒートマップ",
+        "History" => "履歴",
+        "Image" => "評判",
+        "Import config" => "設定を読み込む",
+        "Indirect reciprocity" => "間接互恵",
+        "Individuals" => "個体数",
+        "Individuals born" => "生まれた個体数",
+        "Individuals in the latest generation" => "最新世代の個体数",
+        "Inequality" => "格差",
+        "Instances and cost per round per strategy" => "戦略ごとの個体数とラウンドあたりのコスト",
+        "Kin" => "血縁",
+        "Kin pairings" => "血縁同士の対戦",
+        "Kind" => "種類",
+        "Language" => "言語",
+        "Leaderboard" => "順位表",
+        "Lifespan" => "寿命",
+        "Limit lifespan" => "寿命を制限",
+        "Limit speed" => "速度を制限",
+        "Line patterns" => "線の模様",
+        "Lineage" => "系譜",
+        "Load run" => "実行を読み込む",
+        "Loner payoff" => "単独者の利得",
+        "Loners" => "単独者",
+        "Loss" => "損失",
+        "Losses" => "敗北",
+        "Lower quartile" => "第1四分位数",
+        "MVP" => "最優秀",
+        "Majority tag" => "多数派のタグ",
+        "Markov" => "マルコフ",
+        "Mean" => "平均",
+        "Mean best" => "最高得点の平均",
+        "Mean mutation rate" => "平均突然変異率",
+        "Mean rank" => "平均順位",
+        "Mean score" => "平均得点",
+        "Median" => "中央値",
+        "Members" => "メンバー",
+        "Memory cost" => "記憶のコスト",
+        "Memory depth" => "記憶の深さ",
+        "Mixed openers" => "混合初手",
+        "Most individuals" => "最多個体数",
+        "Mutation rate" => "突然変異率",
+        "Neutrally stable" => "中立的に安定",
+        "Neutrally stable: {}" => "中立的に安定: {}",
+        "New match" => "新しい対戦",
+        "Noise" => "ノイズ",
+        "Noise schedule" => "ノイズの推移",
+        "Noisy" => "ノイズあり",
+        "Noisy for" => "ノイズ期間",
+        "Noisy tit-for-tat breakdown" => "ノイズによるしっぺ返しの崩壊",
+        "Not a prisoner's dilemma" => "囚人のジレンマではありません",
+        "Not converged" => "未収束",
+        "Observing" => "観察率",
+        "One change per line, e.g. `50 T=1` or `20 T+=-1`." => {
+            "1行に1つの変更。例: `50 T=1` や `20 T+=-1`。"
+        }
+        "Open results" => "結果を開く",
+        "Opening cooperation" => "初手の協力率",
+        "Openings" => "初手",
+        "Opponent" => "対戦相手",
+        "Opponents" => "対戦相手",
+        "Oscillating" => "振動",
+        "Out of budget" => "予算切れ",
+        "Own payoff after each outcome, own move first:" => "各結果での自分の利得(自分の手が先):",
+        "Own score" => "自分の得点",
+        "Palette" => "配色",
+        "Parameter" => "パラメータ",
+        "Participants grouped by cooperation rate at start and after CC/CD/DC/DD." => {
+            "最初とCC/CD/DC/DDの後の協力率でまとめた参加者。"
+        }
+        "Partner choice" => "相手の選択",
+        "Partners per individual" => "個体あたりの相手数",
+        "Past champions" => "過去のチャンピオン",
+        "Pause" => "一時停止",
+        "Paused before generation {}" => "世代{}の前で一時停止",
+        "Payoff schedule" => "利得の予定",
+        "Payoffs" => "利得",
+        "Per opponent" => "相手あたり",
+        "Per round" => "ラウンドあたり",
+        "Per round and opponent" => "ラウンドと相手あたり",
+        "Per-round score of each fixed player (column) against each opponent (row)." => {
+            "各固定プレイヤー(列)の各対戦相手(行)に対するラウンドあたりの得点。"
+        }
+        "Perception noise" => "知覚ノイズ",
+        "Perfect recall" => "完全な記憶",
+        "Period" => "周期",
+        "Plateau length" => "停滞の長さ",
+        "Play" => "対戦",
+        "Player" => "プレイヤー",
+        "Players sampled" => "抽出するプレイヤー数",
+        "Plays every named strategy against every other one, without evolution." => {
+            "進化なしで、すべての戦略を互いに対戦させます。"
+        }
+        "Plays the fixed players under every game preset and compares their ranks." => {
+            "固定プレイヤーを各ゲームで対戦させ、順位を比較します。"
+        }
+        "Population" => "集団",
+        "Punishers" => "罰する者",
+        "Punishment" => "罰",
+        "Quartiles" => "四分位数",
+        "Random" => "ランダム",
+        "Rank" => "順位",
+        "Rank CI" => "順位の信頼区間",
+        "Rate" => "割合",
+        "Rating" => "評価",
+        "Ratings" => "評価",
+        "Ratings appear once a generation has been simulated." => {
+            "1世代をシミュレートすると評価が表示されます。"
+        }
+        "Red arrows are part of a cycle of head-to-head wins." => {
+            "赤い矢印は直接対決の勝利の循環の一部です。"
+        }
+        "Refused" => "拒否",
+        "Relatedness r" => "血縁度 r",
+        "Relative fitness" => "相対適応度",
+        "Reload config" => "設定を再読み込み",
+        "Remove" => "削除",
+        "Reset" => "リセット",
+        "Resource budget" => "資源予算",
+        "Resume" => "再開",
+        "Rotating pool" => "入れ替わる相手",
+        "Roulette wheel" => "ルーレット選択",
+        "Round robin" => "総当たり戦",
+        "Round {}" => "第{}ラウンド",
+        "Rounds" => "ラウンド",
+        "Run cheap talk" => "チープトークを実行",
+        "Run contracts" => "契約を実行",
+        "Run forgetting report" => "忘却レポートを実行",
+        "Run green-beard" => "緑ひげを実行",
+        "Run kin selection" => "血縁選択を実行",
+        "Run knockout" => "勝ち抜き戦を実行",
+        "Run loner dynamics" => "単独者の動態を実行",
+        "Run memory depth" => "記憶の深さを実行",
+        "Run meta-tournament" => "メタトーナメントを実行",
+        "Run mixed openings" => "混合初手を実行",
+        "Run noise report" => "ノイズレポートを実行",
+        "Run noisy variants" => "ノイズ変種を実行",
+        "Run populations" => "集団を実行",
+        "Run punishment" => "罰を実行",
+        "Run round-robin" => "総当たり戦を実行",
+        "Run self-adaptive mutation" => "自己適応的突然変異を実行",
+        "Running" => "実行中",
+        "Runs" => "実行回数",
+        "Runs won" => "優勝した実行",
+        "Save an animation of the run" => "実行のアニメーションを保存",
+        "Save run" => "実行を保存",
+        "Scenarios" => "シナリオ",
+        "Score" => "得点",
+        "Score axis:" => "得点軸:",
+        "Score difference" => "得点差",
+        "Score per round in the latest tournament" => "最新のトーナメントでの1ラウンドあたりのスコア",
+        "Scores" => "得点",
+        "Season length" => "季節の長さ",
+        "Seasons" => "季節",
+        "Seed" => "シード",
+        "Seed of the last run" => "前回の実行のシード",
+        "Selection" => "選択",
+        "Self-play" => "集団内対戦",
+        "Semi-finals" => "準決勝",
+        "Sensitivity" => "感度",
+        "Series:" => "系列:",
+        "Share" => "割合",
+        "Shift" => "変化量",
+        "Signal C" => "協力を宣言",
+        "Signal-responsive" => "宣言に反応",
+        "Signers" => "署名者",
+        "Simulate" => "シミュレート",
+        "Simulate batch" => "一括シミュレート",
+        "Simulated" => "シミュレーション値",
+        "Single-point" => "一点交叉",
+        "Social learning" => "社会学習",
+        "Stacked" => "積み上げ",
+        "Start" => "最初",
+        "Standard" => "標準",
+        "Standings" => "順位",
+        "Stationary CC {}, CD {}, DC {}, DD {}" => "定常分布 CC {}、CD {}、DC {}、DD {}",
+        "Std" => "標準偏差",
+        "Std dev" => "標準偏差",
+        "Steady state" => "定常状態",
+        "Step one generation" => "1世代進める",
+        "Steps" => "ステップ",
+        "Stop when converged" => "収束したら停止",
+        "Stopped at generation {}: the population went extinct" => "世代{}で停止: 集団が絶滅",
+        "Stopped early at generation {}: best score and diversity plateaued for {} generations" => "世代{}で早期停止: 最高スコアと多様性が{}世代にわたって停滞",
+        "Strategy" => "戦略",
+        "Strongly connected components, from dominant to dominated:" => {
+            "強連結成分(優位から劣位の順):"
+        }
+        "Swiss pairing" => "スイス式",
+        "Switching" => "切り替え率",
+        "Tags" => "タグ",
+        "The GA winner {} {}." => "GAの勝者{}{}。",
+        "The ancestry of the winning genome is traced here when the genetic algorithm breeds the population." => "遺伝的アルゴリズムで集団を交配すると、勝者のゲノムの系譜がここに表示されます。",
+        "The best genome of every finished simulation is ranked here." => {
+            "終了したシミュレーションごとの最良のゲノムがここに順位付けされます。"
+        }
+        "The config file changed." => "設定ファイルが変更されました。",
+        "The dominance graph appears once a generation has been simulated." => {
+            "1世代をシミュレートすると優位グラフが表示されます。"
+        }
+        "The heatmap appears once a generation has been simulated." => {
+            "1世代をシミュレートするとヒートマップが表示されます。"
+        }
+        "The sensitivity analysis runs on the final generation of a simulation." => {
+            "感度分析はシミュレーションの最終世代で行われます。"
+        }
+        "Tournament" => "トーナメント選択",
+        "Tournament size" => "トーナメントの大きさ",
+        "Tournaments" => "トーナメント",
+        "Truncation" => "切り捨て選択",
+        "Trust a C signal" => "協力の宣言を信頼",
+        "Two-point" => "二点交叉",
+        "Uniform" => "一様交叉",
+        "Upper quartile" => "第3四分位数",
+        "Variants" => "変種",
+        "W/L/D" => "勝/敗/分",
+        "Wealth-based birth and death" => "富による誕生と死",
+        "Winner" => "勝者",
+        "Wins" => "勝利",
+        "Withdrawn" => "途中で除去",
+        "Worst" => "最低",
+        "Worst rank" => "最低順位",
+        "You" => "あなた",
+        "Zero-determinant extortion" => "ゼロ行列式戦略による搾取",
+        "after CC" => "CCの後",
+        "after CD" => "CDの後",
+        "after DC" => "DCの後",
+        "after DD" => "DDの後",
+        "born in generation" => "誕生した世代",
+        "can be invaded" => "は侵入されうる",
+        "cooperating" => "協力率",
+        "founder" => "初期個体",
+        "generation" => "世代",
+        "is evolutionarily stable" => "は進化的に安定",
+        "is neutrally stable" => "は中立的に安定",
+        "mutated genes" => "変異した遺伝子",
+        "opening move" => "初手",
+        "runs" => "回",
+        "±1 std dev" => "±1 標準偏差",
+        _ => return None,
+    })
+}
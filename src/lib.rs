@@ -0,0 +1,52 @@
+//! Iterated prisoner's dilemma tournaments between named strategies and memory-one genomes,
+//! and the evolution of those genomes by a genetic algorithm, usable without the GUI.
+//!
+//! The engine lives in [`gametheory`] and is re-exported at the crate root. A generation is a
+//! [`Tournament`] between the fixed players and a population of genomes; its fittest genomes
+//! breed the next one:
+//!
+//! ```no_run
+//! use gametheory::{get_new_generation, prisoners_dillemma_rules, GeneticConfig, Tournament};
+//!
+//! let genetic = GeneticConfig::default();
+//! let mut population = genetic.initial_population();
+//! for _ in 0..100 {
+//!     let mut game = Tournament::from(10, prisoners_dillemma_rules, population);
+//!     game.run();
+//!     if let Some((_, best_score)) = game.select_fittest_and_bestscore(1) {
+//!         println!("best score {best_score}");
+//!     }
+//!     population = get_new_generation(&game, &genetic);
+//! }
+//! ```
+//!
+//! The other modules build on the engine: [`analysis`] for Markov chain expectations and
+//! stability, [`demography`] and [`experiments`] for alternative dynamics and ready-made
+//! studies, [`ratings`] for pairwise ratings and [`stats`] for per-generation statistics and
+//! CSV reports. [`simulation::simulate`] runs a whole configuration read by [`settings`], as
+//! the window and the command line do. Tournaments serialise with serde, and [`Tournament::to_json`] dumps one as JSON.
+
+/// Markov chain expectations, behavioural clusters, stability and payoff sensitivity.
+pub mod analysis;
+/// Populations living off their payoffs instead of being selected by rank.
+pub mod demography;
+/// Ready-made studies, each returning one row per level or generation.
+pub mod experiments;
+/// Players, strategies, tournaments and the genetic algorithm.
+pub mod gametheory;
+/// Finished runs, kept across sessions, and the champions they bred.
+pub mod history;
+/// Ancestry of the individuals bred by the genetic algorithm.
+pub mod lineage;
+/// The engine's seedable source of randomness.
+pub mod random;
+/// Pairwise outcomes, Bradley-Terry ratings and the dominance graph.
+pub mod ratings;
+/// Settings of a simulation run and the configuration files they are read from.
+pub mod settings;
+/// The simulation loop that evolves a population under [`settings::Settings`].
+pub mod simulation;
+/// Per-generation and per-strategy statistics and their CSV reports.
+pub mod stats;
+
+pub use gametheory::*;
@@ -0,0 +1,133 @@
+//! Ancestry of the individuals of a run, so that the emergence of a winning genome can be
+//! followed back through the generations.
+
+use crate::gametheory::Origin;
+
+/// An individual that lived during a run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ancestor {
+    /// Encoded memory-one genome.
+    pub genome: u8,
+    /// Generation the individual was born in, 0 for the founders.
+    pub born: u32,
+    /// Ids of the parents, or `None` for a founder.
+    pub parents: Option<(usize, usize)>,
+}
+
+/// Every individual of a run with its parents, identified by the order of their birth.
+/// Survivors keep their id from one generation to the next.
+#[derive(Clone, Debug, Default)]
+pub struct Lineage {
+    individuals: Vec<Ancestor>,
+    /// Ids of the current generation, by index in the population.
+    current: Vec<usize>,
+    /// Id of the fittest individual of the latest tournament.
+    pub champion: Option<usize>,
+}
+
+impl Lineage {
+    /// Starts the lineage of a run from its initial population.
+    pub fn new(population: &[u8]) -> Self {
+        let individuals = population
+            .iter()
+            .map(|&genome| Ancestor {
+                genome,
+                born: 0,
+                parents: None,
+            })
+            .collect();
+        Lineage {
+            individuals,
+            current: (0..population.len()).collect(),
+            champion: None,
+        }
+    }
+
+    /// Replaces the current generation by `population`, born in `generation`, whose members
+    /// come from the current one as `origins` says.
+    pub fn advance(&mut self, population: &[u8], origins: &[Origin], generation: u32) {
+        self.current = population
+            .iter()
+            .zip(origins)
+            .map(|(&genome, &origin)| match origin {
+                Origin::Survivor(i) => self.current[i],
+                Origin::Child(p1, p2) => {
+                    self.individuals.push(Ancestor {
+                        genome,
+                        born: generation,
+                        parents: Some((self.current[p1], self.current[p2])),
+                    });
+                    self.individuals.len() - 1
+                }
+            })
+            .collect();
+    }
+
+    /// Id of the member of the current generation at `index` in the population.
+    pub fn id(&self, index: usize) -> Option<usize> {
+        self.current.get(index).copied()
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Ancestor> {
+        self.individuals.get(id)
+    }
+
+    /// Number of individuals born so far, founders included.
+    pub fn len(&self) -> usize {
+        self.individuals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.individuals.is_empty()
+    }
+
+    /// Bits of the genome of `id` that neither parent has, which must have mutated. A mutation
+    /// of a gene in which the parents differ cannot be told from crossover.
+    pub fn mutations(&self, id: usize) -> u8 {
+        let Some(&Ancestor {
+            genome,
+            parents: Some((p1, p2)),
+            ..
+        }) = self.get(id)
+        else {
+            return 0;
+        };
+        (genome ^ self.individuals[p1].genome) & (genome ^ self.individuals[p2].genome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gametheory::{
+        get_traced_generation, prisoners_dillemma_rules, GeneticConfig, Tournament,
+    };
+
+    #[test]
+    fn lineage_follows_the_parents_of_every_child() {
+        let genetic = GeneticConfig {
+            mutation_rate: 0.0,
+            ..GeneticConfig::default()
+        };
+        let population = genetic.initial_population();
+        let mut lineage = Lineage::new(&population);
+        let mut game = Tournament::from(10, prisoners_dillemma_rules, population.clone());
+        game.run();
+        let (next, origins) = get_traced_generation(&game, &genetic);
+        lineage.advance(&next, &origins, 1);
+        let mut children = 0;
+        for (i, &origin) in origins.iter().enumerate() {
+            let id = lineage.id(i).unwrap();
+            let individual = lineage.get(id).unwrap();
+            match origin {
+                Origin::Survivor(j) => assert_eq!((id, next[i]), (j, population[j])),
+                Origin::Child(p1, p2) => {
+                    children += 1;
+                    assert_eq!((individual.parents, individual.born), (Some((p1, p2)), 1));
+                    assert_eq!(lineage.mutations(id), 0);
+                }
+            }
+        }
+        assert_eq!(lineage.len(), population.len() + children);
+    }
+}
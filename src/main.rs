@@ -1,15 +1,45 @@
-mod gametheory;
+mod animation;
+mod chart;
+mod config;
+mod i18n;
+mod style;
 
-use eframe::{egui, Error};
+use analysis::{MemoryOne, Stability};
+use config::ConfigFile;
+use eframe::egui;
 use egui::mutex::Mutex;
 use egui::{Color32, FontData, FontFamily, FontId, RichText, TextStyle};
-use egui_plot::{Line, Plot, PlotPoints};
-use gametheory::{prisoners_dillemma_rules, Tournament};
+use egui_plot::{Bar, BarChart, Line, Plot, PlotPoints};
+use experiments::{
+    AdaptiveRow, BracketMatch, ContractRow, DepthRow, EnvironmentRow, ForgettingRow, KinRow,
+    MetaRow, NoiseRow, OpeningRow, PunishRow, RoundRobinRow, Setup, SoloForgettingRow, TagRow,
+    TalkRow, VariantRow,
+};
+use gametheory::history::{self, RunRecord};
+use gametheory::lineage::Lineage;
+use gametheory::settings::{
+    load_config, Mixing, NoiseSchedule, Pairing, PayoffChange, Settings, UpdateRule,
+};
+use gametheory::simulation::{simulate, simulate_batch, Control, Controller, Progress, Status};
+use gametheory::{analysis, experiments, prisoners_dillemma_rules, ratings, stats, GAME_PRESETS};
+use i18n::{tr, tr_fill, Language};
+use ratings::PairwiseTable;
+use serde::{Deserialize, Serialize};
+use stats::{BitAssociation, GenerationRecord, Normalization, StatsRecorder, StrategyStats};
 use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::ExitCode;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
 use std::sync::Arc;
+use std::time::Duration;
+use style::{Palette, PlotStyle};
 
-use crate::gametheory::get_new_generation;
+use gametheory::{
+    describe_genome, genome_label, genome_to_number, number_to_genome, payoff_table,
+    strategy_names, Crossover, Decision, InteractiveMatch, PayoffMatrix, Selection, GENOME_COUNT,
+    MAX_DEPTH, STRATEGY_COUNT,
+};
 
 // Comes from https://github.com/WINSDK/bite/blob/38ddb5d8f6ee7e46496a2c10d335c2128aceb125/gui/src/panels/source_code.rs#L302
 // This was written by Nicolas but sits in a different codebase.
@@ -69,15 +99,321 @@ fn show_columns<R>(
     result
 }
 
+#[derive(PartialEq)]
+enum View {
+    Evolution,
+    Leaderboard,
+    Ratings,
+    Behaviour,
+    Markov,
+    Population,
+    Sensitivity,
+    Noise,
+    Forgetting,
+    Genes,
+    Dominance,
+    Heatmap,
+    Meta,
+    Tags,
+    Kin,
+    Loners,
+    Punishment,
+    Contracts,
+    Talk,
+    RoundRobin,
+    Bracket,
+    Environments,
+    Variants,
+    Openings,
+    Memory,
+    Adaptive,
+    History,
+    HallOfFame,
+    Lineage,
+    Play,
+}
+
+/// Most champions of earlier runs that can be placed in the initial population.
+const MAX_CHAMPIONS: usize = 10;
+
+/// Height of a match in the bracket view, including the space below it.
+const BRACKET_SLOT: f32 = 52.0;
+/// Width of a match in the bracket view.
+const BRACKET_WIDTH: f32 = 160.0;
+
+/// Series of the evolution plots that can be shown or hidden.
+#[derive(Clone, Copy, PartialEq)]
+enum Series {
+    Best,
+    Mean,
+    Quartiles,
+    Cooperation,
+    Diversity,
+    Gini,
+}
+
+impl Series {
+    /// Every series, in the order of their discriminants.
+    const ALL: [Series; 6] = [
+        Series::Best,
+        Series::Mean,
+        Series::Quartiles,
+        Series::Cooperation,
+        Series::Diversity,
+        Series::Gini,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Series::Best => "Best",
+            Series::Mean => "Mean",
+            Series::Quartiles => "Quartiles",
+            Series::Cooperation => "Cooperation",
+            Series::Diversity => "Diversity",
+            Series::Gini => "Gini",
+        }
+    }
+
+    /// Whether the series is drawn before it is toggled, which holds for those the plots
+    /// always drew.
+    fn shown_by_default(self) -> bool {
+        !matches!(self, Series::Mean | Series::Diversity)
+    }
+}
+
+/// Column the live standings are sorted by.
+#[derive(Clone, Copy, PartialEq)]
+enum StandingsColumn {
+    Name,
+    /// Fixed players before evolved opponents.
+    Kind,
+    Score,
+}
+
+/// Opponent of a person in the play view.
+#[derive(Clone, Copy, PartialEq)]
+enum Rival {
+    /// A named strategy, by index.
+    Named(usize),
+    Genome(u8),
+}
+
+/// Generations per second when the speed is first limited, slow enough to follow each one.
+const SLOW_SPEED: f64 = 2.0;
+
+/// Number of distinct genomes at or below which the population counts as converged.
+const DIVERSITY_THRESHOLD: usize = 2;
+
+/// Ready-made configurations that can be loaded in one click from the scenarios menu.
+#[derive(Clone, Copy)]
+enum Scenario {
+    /// Every named strategy meets every other over 200 rounds, as in Axelrod's first
+    /// tournament.
+    Axelrod,
+    /// Every named strategy next to a variant of itself that trembles, showing how errors
+    /// turn tit-for-tat's retaliation into long feuds.
+    NoisyTitForTat,
+    /// A zero-determinant extortioner against a few classic strategies: it outscores those
+    /// that cooperate with it head to head, yet comes last in the round-robin.
+    Extortion,
+}
+
+impl Scenario {
+    const ALL: [Scenario; 3] = [
+        Scenario::Axelrod,
+        Scenario::NoisyTitForTat,
+        Scenario::Extortion,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Scenario::Axelrod => "Axelrod 1980 round-robin",
+            Scenario::NoisyTitForTat => "Noisy tit-for-tat breakdown",
+            Scenario::Extortion => "Zero-determinant extortion",
+        }
+    }
+
+    /// Replaces the whole configuration of `app`, including the fixed players and payoffs, and
+    /// switches to the view the scenario is shown in.
+    fn load(self, app: &mut App) {
+        app.settings = Settings::default();
+        app.copies = [1; STRATEGY_COUNT];
+        app.costs = [0; STRATEGY_COUNT];
+        app.swiss_rounds = None;
+        app.custom_players.clear();
+        app.payoff_schedule.clear();
+        match self {
+            Scenario::Axelrod => {
+                app.settings.game_count = experiments::DUEL_ROUNDS;
+                app.view = View::RoundRobin;
+            }
+            Scenario::NoisyTitForTat => {
+                app.settings.noise = NOISY_SCENARIO_NOISE;
+                app.variant_noise = NOISY_SCENARIO_NOISE;
+                app.view = View::Variants;
+            }
+            Scenario::Extortion => {
+                let names = strategy_names();
+                let chosen = names.iter().map(|n| EXTORTION_SCENARIO_PLAYERS.contains(n));
+                for (s, chosen) in chosen.enumerate() {
+                    app.settings.strategies[s] = chosen;
+                    app.copies[s] = usize::from(chosen);
+                }
+                app.settings.game_count = experiments::DUEL_ROUNDS;
+                app.view = View::RoundRobin;
+            }
+        }
+    }
+}
+
+/// Execution noise of the noisy tit-for-tat scenario.
+const NOISY_SCENARIO_NOISE: f64 = 0.05;
+
+/// Named strategies of the extortion scenario.
+const EXTORTION_SCENARIO_PLAYERS: [&str; 5] = [
+    "extortioner",
+    "trusting\nt4t",
+    "naive",
+    "evil",
+    "grim\ntrigger",
+];
+
+/// Populations evolved side by side under different environments, with their labels.
+type Environments = Vec<(String, Vec<EnvironmentRow>)>;
+
+/// Results the simulation thread shares with the UI.
+#[derive(Clone, Default)]
+struct Shared {
+    /// What the latest simulation run published.
+    run: Progress,
+    /// Remarks on recording the latest run, shown after its status.
+    note: Arc<Mutex<String>>,
+    /// Results of the latest noise-robustness experiment.
+    noise: Arc<Mutex<Vec<NoiseRow>>>,
+    /// Results of the latest forgetting experiment.
+    forgetting: Arc<Mutex<Vec<ForgettingRow>>>,
+    /// Ranks of the named strategies with and without forgetting alone.
+    solo_forgetting: Arc<Mutex<Vec<SoloForgettingRow>>>,
+    /// Results of the latest meta-tournament across game presets.
+    meta: Arc<Mutex<Vec<MetaRow>>>,
+    /// Ranking table of the latest round-robin among the named strategies.
+    round_robin: Arc<Mutex<Vec<RoundRobinRow>>>,
+    /// Populations evolved side by side in the latest environment comparison, with their labels.
+    environments: Arc<Mutex<Environments>>,
+    /// Results of the latest mixed-opening experiment, one row per generation.
+    openings: Arc<Mutex<Vec<OpeningRow>>>,
+    /// Scores of the named strategies and their noisy variants in the latest comparison.
+    variants: Arc<Mutex<Vec<VariantRow>>>,
+    /// Rounds of the latest single-elimination bracket, the final last.
+    bracket: Arc<Mutex<Vec<Vec<BracketMatch>>>>,
+    /// Results of the latest green-beard experiment, one row per generation.
+    tags: Arc<Mutex<Vec<TagRow>>>,
+    /// Results of the latest kin-selection experiment, one row per generation.
+    kin: Arc<Mutex<Vec<KinRow>>>,
+    /// Shares of cooperators, defectors and loners per step of the latest loner experiment.
+    loners: Arc<Mutex<Vec<[f64; 3]>>>,
+    /// Results of the latest punishment experiment, one row per generation.
+    punishment: Arc<Mutex<Vec<PunishRow>>>,
+    /// Results of the latest contract experiment, one row per generation.
+    contracts: Arc<Mutex<Vec<ContractRow>>>,
+    /// Results of the latest cheap-talk experiment, one row per generation.
+    talk: Arc<Mutex<Vec<TalkRow>>>,
+    /// Results of the latest memory-depth experiment, one row per generation.
+    memory_depth: Arc<Mutex<Vec<DepthRow>>>,
+    /// Results of the latest self-adaptive mutation experiment, one row per generation.
+    adaptive: Arc<Mutex<Vec<AdaptiveRow>>>,
+    /// Every finished simulation run, oldest first, as stored in [`history::HISTORY_PATH`].
+    history: Arc<Mutex<Vec<RunRecord>>>,
+}
+
+/// A finished run as written by [`App::save_run`].
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    /// Settings the run was started with, as [`Settings::encode`] writes them.
+    config: String,
+    /// Payoff schedule the run was started with.
+    schedule: String,
+    series: StatsRecorder,
+    /// Genomes of the final generation.
+    population: Vec<u8>,
+    mvp: Option<u8>,
+    regimes: Vec<(u32, String)>,
+    status: Status,
+    /// Remarks on recording the run.
+    note: String,
+}
+
 struct App {
-    ys: Arc<Mutex<Vec<i32>>>,
+    shared: Shared,
     simulating: Arc<AtomicBool>,
-    gen_count: u32,
-    game_count: u32,
+    settings: Settings,
+    view: View,
+    normalization: Normalization,
+    /// Weight of a relative's payoff in the kin-selection experiment.
+    relatedness: f64,
+    /// Seed the latest simulation ran with.
+    last_seed: Option<u64>,
+    /// Settings and payoff schedule the latest simulation was started with.
+    run_config: Option<(Settings, String)>,
+    /// File runs are saved to and loaded from.
+    snapshot_path: String,
+    /// Number of runs with consecutive seeds simulated by a batch.
+    batch_runs: u64,
+    /// Sends commands to the latest simulation, dropped to stop it.
+    control: Option<Sender<Control>>,
+    /// Whether the latest simulation was told to pause.
+    paused: bool,
+    /// Most generations simulated per second, or `None` to simulate as fast as possible.
+    speed: Option<f64>,
+    /// Column the live standings are sorted by, and whether in descending order.
+    standings_order: (StandingsColumn, bool),
+    /// Whether the heatmap shows how much more a player scores than its opponent, rather
+    /// than its own score.
+    heatmap_difference: bool,
+    /// Whether the population plot stacks the genome counts on top of each other.
+    stacked_population: bool,
+    /// Whether a run writes an animation of its generations when it finishes.
+    animate: bool,
+    /// Whether each of the evolution plot's [`Series`] is drawn.
+    shown_series: [bool; Series::ALL.len()],
+    /// Number of Swiss-system rounds to play among the named strategies instead of a full
+    /// round-robin.
+    swiss_rounds: Option<u32>,
+    /// Execution noise of the noisy variants of the named strategies.
+    variant_noise: f64,
+    /// Game preset and execution noise of every population in the environment comparison.
+    environments: Vec<(usize, f64)>,
+    /// Number of instances of every named strategy in the round-robin.
+    copies: [usize; STRATEGY_COUNT],
+    /// Cost every instance of a named strategy pays per round in the round-robin.
+    costs: [i32; STRATEGY_COUNT],
+    /// Payoff changes at scheduled generations, one `generation payoff=value` per line.
+    payoff_schedule: String,
+    /// Name and genome of every strategy built in the app, added to the fixed players.
+    custom_players: Vec<(String, u8)>,
+    /// Name and responses of the strategy being built, in genome order.
+    custom_draft: (String, [bool; 5]),
+    /// Colours and line styles of the plots.
+    plot_style: PlotStyle,
+    /// Configuration file given on the command line, watched for changes.
+    config: Option<ConfigFile>,
+    /// Settings and payoff schedule of a changed configuration file, waiting to be applied.
+    pending_config: Option<(Settings, String)>,
+    /// Path of the configuration file to import, typed in the settings panel.
+    import_path: String,
+    export_status: String,
+    /// Area of the window, in points, to save from the screenshot requested by "Export chart".
+    screenshot: Option<egui::Rect>,
+    /// Genome shown in the genome inspector, opened by clicking a genome.
+    inspected: Option<u8>,
+    /// Opponent of the next match in the play view.
+    rival: Rival,
+    /// Match a person is playing in the play view.
+    human_match: Option<InteractiveMatch>,
 }
 
 impl App {
-    fn new(cc: &eframe::CreationContext) -> Self {
+    fn new(cc: &eframe::CreationContext, config: Option<String>) -> Self {
         let font = FontData::from_static(include_bytes!("../PixelMplus12.ttf"));
         let fonts = egui::FontDefinitions {
             font_data: BTreeMap::from([("pixelmplus".to_string(), font)]),
@@ -94,115 +430,3130 @@ impl App {
         cc.egui_ctx.set_fonts(fonts);
         cc.egui_ctx.style_mut(|s| s.text_styles = text_styles);
 
-        Self {
-            ys: Default::default(),
+        let shared = Shared::default();
+        *shared.history.lock() = history::load();
+
+        let mut app = Self {
+            shared,
             simulating: Arc::new(AtomicBool::new(false)),
-            gen_count: 100,
-            game_count: 10,
+            settings: Settings::default(),
+            view: View::Evolution,
+            normalization: Normalization::Raw,
+            relatedness: 0.5,
+            last_seed: None,
+            run_config: None,
+            snapshot_path: "run.json".to_string(),
+            batch_runs: 10,
+            control: None,
+            paused: false,
+            speed: None,
+            standings_order: (StandingsColumn::Score, true),
+            heatmap_difference: false,
+            stacked_population: false,
+            animate: false,
+            shown_series: Series::ALL.map(Series::shown_by_default),
+            swiss_rounds: None,
+            environments: vec![(0, 0.0), (1, 0.0)],
+            variant_noise: experiments::VARIANT_NOISE,
+            copies: [1; STRATEGY_COUNT],
+            costs: [0; STRATEGY_COUNT],
+            payoff_schedule: String::new(),
+            custom_players: Vec::new(),
+            custom_draft: (String::new(), [false; 5]),
+            plot_style: PlotStyle {
+                palette: Palette::Standard,
+                patterned: false,
+            },
+            config: config.map(ConfigFile::new),
+            pending_config: None,
+            import_path: String::new(),
+            export_status: String::new(),
+            screenshot: None,
+            inspected: None,
+            rival: Rival::Named(0),
+            human_match: None,
+        };
+        app.poll_config(true);
+        app
+    }
+
+    /// Checks the configuration file for changes. Unless `apply` is set, new parameters wait
+    /// until they are accepted so that the settings of the next run never change unnoticed.
+    fn poll_config(&mut self, apply: bool) {
+        let Some(config) = &mut self.config else {
+            return;
+        };
+        let path = config.path.display().to_string();
+        let parsed = match config.changed() {
+            None => return,
+            Some(text) => {
+                text.map_err(|err| err.to_string()).and_then(|t| load_config(&config.path, &t))
+            }
+        };
+        match parsed {
+            Ok((settings, schedule)) if apply => {
+                self.settings = settings;
+                self.payoff_schedule = schedule;
+                self.export_status = format!("loaded {path}");
+            }
+            Ok(config) => self.pending_config = Some(config),
+            Err(err) => self.export_status = format!("cannot read {path}: {err}"),
+        }
+    }
+
+    /// Writes the latest run to [`App::snapshot_path`]: the settings and payoff schedule it
+    /// was started with, its series, its final generation and why it ended.
+    fn save_run(&self) -> Result<(), String> {
+        let (settings, schedule) = self.run_config.as_ref().ok_or("nothing to save yet")?;
+        let shared = &self.shared;
+        let regimes = shared.run.regimes.lock().unwrap().clone();
+        let snapshot = Snapshot {
+            config: settings.encode(),
+            schedule: schedule.clone(),
+            series: shared.run.recorder.lock().unwrap().clone(),
+            population: shared.run.population.lock().unwrap().clone(),
+            mvp: *shared.run.mvp.lock().unwrap(),
+            regimes,
+            status: *shared.run.status.lock().unwrap(),
+            note: shared.note.lock().clone(),
+        };
+        let json = serde_json::to_string(&snapshot).map_err(|err| err.to_string())?;
+        std::fs::write(&self.snapshot_path, json).map_err(|err| err.to_string())
+    }
+
+    /// Restores a run written by [`App::save_run`], putting its settings back in the panel.
+    fn load_run(&mut self) -> Result<(), String> {
+        let text = std::fs::read_to_string(&self.snapshot_path).map_err(|err| err.to_string())?;
+        let snapshot: Snapshot = serde_json::from_str(&text).map_err(|err| err.to_string())?;
+        let settings =
+            Settings::decode(&snapshot.config).map_err(|err| format!("invalid `config`: {err}"))?;
+        let Snapshot {
+            schedule,
+            series: recorder,
+            population,
+            mvp,
+            regimes,
+            status,
+            note,
+            ..
+        } = snapshot;
+
+        self.reset_game();
+        *self.shared.run.recorder.lock().unwrap() = recorder;
+        *self.shared.run.population.lock().unwrap() = population;
+        *self.shared.run.mvp.lock().unwrap() = mvp;
+        *self.shared.run.regimes.lock().unwrap() = regimes;
+        *self.shared.run.status.lock().unwrap() = status;
+        *self.shared.note.lock() = note;
+        self.settings = settings;
+        self.payoff_schedule = schedule.clone();
+        self.last_seed = settings.seed;
+        self.run_config = Some((settings, schedule));
+        Ok(())
+    }
+
+    /// Settings of a new run, seeded so that it can be repeated and remembered as the
+    /// configuration of the latest run, their seed, and the controller through which the run
+    /// is paused.
+    fn start_run(&mut self) -> (Settings, u64, Controller) {
+        let seed = self.settings.seed.unwrap_or_else(rand::random);
+        self.last_seed = Some(seed);
+        let settings = Settings {
+            seed: Some(seed),
+            ..self.settings
+        };
+        self.run_config = Some((settings, self.payoff_schedule.clone()));
+        self.shared.run.batch.lock().unwrap().clear();
+        let (sender, commands) = mpsc::channel();
+        // Nothing can have dropped the receiver yet.
+        let _ = sender.send(Control::Delay(self.delay()));
+        self.control = Some(sender);
+        self.paused = false;
+        (
+            settings,
+            seed,
+            Controller::new(self.simulating.clone(), commands),
+        )
+    }
+
+    /// Least time between two generations at the chosen speed.
+    fn delay(&self) -> Duration {
+        self.speed.map_or(Duration::ZERO, |g| Duration::from_secs_f64(1.0 / g))
+    }
+
+    fn send(&mut self, command: Control) {
+        match command {
+            Control::Pause | Control::Step => self.paused = true,
+            Control::Resume => self.paused = false,
+            Control::Delay(_) => {}
+        }
+        if let Some(control) = &self.control {
+            // The simulation may have finished and dropped its end of the channel.
+            let _ = control.send(command);
         }
     }
 
     fn reset_game(&mut self) {
         self.simulating.store(false, Ordering::Relaxed);
-        self.ys.lock().clear();
+        self.control = None;
+        self.paused = false;
+        self.shared.run.recorder.lock().unwrap().clear();
+        self.shared.run.stats.lock().unwrap().clear();
+        *self.shared.run.outcomes.lock().unwrap() = PairwiseTable::default();
+        self.shared.run.clusters.lock().unwrap().clear();
+        self.shared.run.cross_check.lock().unwrap().clear();
+        *self.shared.run.mvp.lock().unwrap() = None;
+        self.shared.run.stability.lock().unwrap().clear();
+        *self.shared.run.status.lock().unwrap() = Status::Idle;
+        self.shared.note.lock().clear();
+        *self.shared.run.genes.lock().unwrap() = BitAssociation::default();
+        *self.shared.run.lineage.lock().unwrap() = Lineage::default();
+        self.shared.run.sensitivity.lock().unwrap().clear();
+        self.shared.run.population.lock().unwrap().clear();
+        self.shared.run.batch.lock().unwrap().clear();
+        self.shared.run.standings.lock().unwrap().clear();
+        *self.shared.run.score_grid.lock().unwrap() = None;
     }
 
     fn show_plot(&mut self, ui: &mut egui::Ui) {
-        let points = self
-            .ys
-            .lock()
-            .clone()
-            .into_iter()
-            .zip(0..self.gen_count)
-            .map(|(y, x)| [x as f64, y as f64])
+        let recorder = self.shared.run.recorder.lock().unwrap().clone();
+        match stats::convergence_generation(&recorder.frequencies(), DIVERSITY_THRESHOLD) {
+            Some(gen) => ui.label(tr_fill("Converged at generation {}", &[&gen])),
+            None => ui.label(tr("Not converged")),
+        };
+        let status = *self.shared.run.status.lock().unwrap();
+        ui.label(format!(
+            "{}{}",
+            status_text(status),
+            self.shared.note.lock()
+        ));
+        ui.horizontal(|ui| {
+            ui.label(tr("Score axis:"));
+            for n in [Normalization::Raw, Normalization::PerRoundPerOpponent] {
+                ui.selectable_value(&mut self.normalization, n, tr(n.label()));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Series:"));
+            for s in Series::ALL {
+                ui.checkbox(&mut self.shown_series[s as usize], tr(s.label()));
+            }
+        });
+        let export = ui
+            .horizontal(|ui| {
+                let clicked = ui.button(tr("Export chart")).clicked();
+                ui.label(&self.export_status);
+                clicked
+            })
+            .inner;
+        let shown = |s: Series| self.shown_series[s as usize];
+
+        let series = |value: &dyn Fn(&GenerationRecord) -> f64| -> Vec<[f64; 2]> {
+            recorder.generations.iter().enumerate().map(|(x, g)| [x as f64, value(g)]).collect()
+        };
+        let scaled = |g: &GenerationRecord, v: f64| recorder.normalize(g, v, self.normalization);
+        let style = self.plot_style;
+        let styled = |name: &str, k: usize, color: Color32, points: Vec<[f64; 2]>| chart::Series {
+            name: name.to_string(),
+            color: style.color(k, color),
+            style: style.line_style(k),
+            points,
+        };
+        let batch = self.shared.run.batch.lock().unwrap().clone();
+        // Batch scores are scaled like those of the latest run, which shares its settings.
+        let scaled_batch = |gen: usize, v: f64| match recorder.generations.get(gen) {
+            Some(g) => scaled(g, v),
+            None => v,
+        };
+        let runs = batch.first().map_or(0, |g| g.runs);
+
+        let mut scores = Vec::new();
+        if shown(Series::Best) && batch.is_empty() {
+            scores.push(styled(
+                tr("Best"),
+                0,
+                Color32::LIGHT_BLUE,
+                series(&|g| scaled(g, g.best as f64)),
+            ));
+        } else if shown(Series::Best) {
+            scores.push(styled(
+                &format!("{} ({runs} {})", tr("Mean best"), tr("runs")),
+                0,
+                Color32::LIGHT_BLUE,
+                batch
+                    .iter()
+                    .enumerate()
+                    .map(|(x, g)| [x as f64, scaled_batch(x, g.mean)])
+                    .collect(),
+            ));
+        }
+        if shown(Series::Mean) {
+            scores.push(styled(
+                tr("Mean"),
+                1,
+                Color32::LIGHT_YELLOW,
+                series(&|g| scaled(g, g.mean)),
+            ));
+        }
+        if shown(Series::Quartiles) {
+            for (q, name) in [(1, "Lower quartile"), (2, "Median"), (3, "Upper quartile")] {
+                scores.push(chart::Series {
+                    name: tr(name).to_string(),
+                    color: Color32::GRAY,
+                    style: if q == 2 {
+                        egui_plot::LineStyle::Solid
+                    } else {
+                        egui_plot::LineStyle::dashed_loose()
+                    },
+                    points: series(&|g| scaled(g, g.quartiles[q])),
+                });
+            }
+        }
+        // One quad per step, as the plot only fills convex polygons correctly.
+        let band: Vec<egui_plot::Polygon> = batch
+            .windows(2)
+            .enumerate()
+            .filter(|_| shown(Series::Best))
+            .map(|(x, pair)| {
+                let edge = |i: usize, sign: f64| {
+                    let g = pair[i];
+                    [
+                        (x + i) as f64,
+                        scaled_batch(x + i, g.mean + sign * g.variance.sqrt()),
+                    ]
+                };
+                egui_plot::Polygon::new(vec![
+                    edge(0, -1.0),
+                    edge(1, -1.0),
+                    edge(1, 1.0),
+                    edge(0, 1.0),
+                ])
+                .fill_color(Color32::LIGHT_BLUE.gamma_multiply(0.2))
+                .stroke(egui::Stroke::NONE)
+                .name(tr("±1 std dev"))
+            })
             .collect();
 
-        let points = PlotPoints::new(points);
-        let price = Line::new(points).color(Color32::LIGHT_BLUE);
+        let mut rates = Vec::new();
+        if shown(Series::Gini) {
+            rates.push(styled(
+                tr("Gini"),
+                0,
+                Color32::LIGHT_RED,
+                series(&|g| g.gini),
+            ));
+        }
+        if shown(Series::Cooperation) {
+            rates.push(styled(
+                tr("Cooperation"),
+                1,
+                Color32::LIGHT_GREEN,
+                series(&|g| g.cooperation),
+            ));
+        }
+        if self.settings.ostracism {
+            rates.push(styled(
+                tr("Refused"),
+                2,
+                Color32::GOLD,
+                series(&|g| g.refusals),
+            ));
+        }
+        if self.settings.cooperation_cost.is_some() {
+            rates.push(styled(
+                tr("Out of budget"),
+                3,
+                Color32::BROWN,
+                series(&|g| g.forced),
+            ));
+        }
+        if shown(Series::Diversity) {
+            rates.push(styled(
+                tr("Distinct genomes (share)"),
+                4,
+                Color32::KHAKI,
+                series(&|g| stats::diversity(&g.counts) as f64 / GENOME_COUNT as f64),
+            ));
+        }
+
+        let regimes = self.shared.run.regimes.lock().unwrap().clone();
+        let regime_lines = || {
+            regimes.iter().map(|(generation, name)| {
+                egui_plot::VLine::new(*generation as f64).color(Color32::DARK_GRAY).name(name)
+            })
+        };
+        let score_label = format!("{} ({})", tr("Score"), tr(self.normalization.label()));
 
-        Plot::new("Evolution")
-            .x_axis_label("Tournaments")
-            .y_axis_label("Score")
+        let evolution = Plot::new("Evolution")
+            .height(ui.available_height() * 0.7)
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(score_label.clone())
             .allow_zoom(false)
             .allow_drag(false)
             .show_x(true)
             .show_y(true)
+            .legend(egui_plot::Legend::default())
+            .show(ui, |plot_ui| {
+                for quad in band {
+                    plot_ui.polygon(quad);
+                }
+                for s in &scores {
+                    plot_ui.line(s.line());
+                }
+                for line in regime_lines() {
+                    plot_ui.vline(line);
+                }
+            });
+
+        let inequality = Plot::new("Inequality")
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(tr("Rate"))
+            .allow_zoom(false)
+            .allow_drag(false)
+            .include_y(0.0)
+            .include_y(1.0)
+            .legend(egui_plot::Legend::default())
             .show(ui, |plot_ui| {
-                plot_ui.line(price);
+                for s in &rates {
+                    plot_ui.line(s.line());
+                }
+                for line in regime_lines() {
+                    plot_ui.vline(line);
+                }
             });
+
+        if export {
+            let markers: Vec<(f64, String)> = regimes
+                .iter()
+                .map(|(generation, name)| (*generation as f64, name.clone()))
+                .collect();
+            let charts = [
+                chart::Chart {
+                    title: tr("Evolution").to_string(),
+                    x_label: tr("Tournaments").to_string(),
+                    y_label: score_label,
+                    series: scores,
+                    include_y: Vec::new(),
+                    markers: markers.clone(),
+                },
+                chart::Chart {
+                    title: tr("Inequality").to_string(),
+                    x_label: tr("Tournaments").to_string(),
+                    y_label: tr("Rate").to_string(),
+                    series: rates,
+                    include_y: vec![0.0, 1.0],
+                    markers,
+                },
+            ];
+            self.export_status = match chart::write_svg("evolution.svg", &charts) {
+                Ok(()) => "wrote evolution.svg".to_string(),
+                Err(err) => format!("export failed: {err}"),
+            };
+            // The PNG is a screenshot of the window, cropped to the plots once it arrives.
+            self.screenshot = Some(evolution.response.rect.union(inequality.response.rect));
+            ui.ctx().send_viewport_cmd(egui::ViewportCommand::Screenshot);
+        }
     }
 
-    fn show_left(&mut self, ui: &mut egui::Ui) {
-        ui.label(RichText::new(format!("#Generations: {}", self.gen_count)).size(14.0));
-        ui.add(egui::widgets::Slider::new(&mut self.gen_count, 100..=300).show_value(false));
+    fn show_standings(&mut self, ui: &mut egui::Ui) {
+        let mut standings = self.shared.run.standings.lock().unwrap().clone();
+        let (column, descending) = self.standings_order;
+        match column {
+            StandingsColumn::Name => standings.sort_by(|a, b| a.name.cmp(&b.name)),
+            StandingsColumn::Kind => standings.sort_by_key(|s| s.evolved),
+            StandingsColumn::Score => standings.sort_by_key(|s| s.score),
+        }
+        if descending {
+            standings.reverse();
+        }
+
+        ui.strong(tr("Standings"));
+        egui::ScrollArea::vertical().id_source("standings").show(ui, |ui| {
+            egui::Grid::new("standings").striped(true).show(ui, |ui| {
+                for (header, c) in [
+                    ("Strategy", StandingsColumn::Name),
+                    ("Kind", StandingsColumn::Kind),
+                    ("Score", StandingsColumn::Score),
+                ] {
+                    let arrow = match (c == column, descending) {
+                        (false, _) => "",
+                        (true, false) => " ⏶",
+                        (true, true) => " ⏷",
+                    };
+                    let label = RichText::new(format!("{}{arrow}", tr(header))).strong();
+                    if ui.selectable_label(c == column, label).clicked() {
+                        // A new column sorts scores best first, names and kinds alphabetically.
+                        self.standings_order = if c == column {
+                            (c, !descending)
+                        } else {
+                            (c, c == StandingsColumn::Score)
+                        };
+                    }
+                }
+                ui.end_row();
+                for s in &standings {
+                    match s.name.parse() {
+                        Ok(genome) if s.evolved => genome_link(ui, genome, &mut self.inspected),
+                        _ => {
+                            ui.label(s.name.replace('\n', " "));
+                        }
+                    }
+                    ui.label(tr(if s.evolved { "Evolved" } else { "Fixed" }));
+                    ui.label(s.score.to_string());
+                    ui.end_row();
+                }
+            });
+        });
+    }
 
-        ui.label(RichText::new(format!("#Games Per Gen: {}", self.game_count)).size(14.0));
-        ui.add(egui::widgets::Slider::new(&mut self.game_count, 10..=100).show_value(false));
+    fn show_leaderboard(&mut self, ui: &mut egui::Ui) {
+        let mut stats = self.shared.run.stats.lock().unwrap().clone();
+        stats.sort_by_key(|s| -s.total);
+        let normalization = self.normalization;
 
-        if ui.button("Simulate").clicked() {
-            let ctx = ui.ctx().clone();
-            let xs = self.ys.clone();
-            let sim = self.simulating.clone();
-            let game_count = self.game_count;
-            let gen_count = self.gen_count;
-            std::thread::spawn(move || simulate(ctx, xs, sim, game_count, gen_count));
+        ui.horizontal(|ui| {
+            if ui.button(tr("Export results")).clicked() {
+                // Only the fixed players, which is what graph_results.py expects.
+                let player_count = self.shared.run.recorder.lock().unwrap().player_count;
+                let fixed: Vec<StrategyStats> = self
+                    .shared
+                    .run
+                    .stats
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .take(player_count)
+                    .cloned()
+                    .collect();
+                self.export_status =
+                    match stats::write_results("tournament_results.txt", &fixed, normalization) {
+                        Ok(()) => "wrote tournament_results.txt".to_string(),
+                        Err(err) => format!("export failed: {err}"),
+                    };
+            }
+            if ui.button(tr("Export statistics")).clicked() {
+                self.export_status = match stats::write_strategy_stats("strategy_stats.csv", &stats)
+                {
+                    Ok(()) => "wrote strategy_stats.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("leaderboard").striped(true).show(ui, |ui| {
+                for header in [
+                    "Strategy",
+                    "Score",
+                    "W/L/D",
+                    "Mean",
+                    "95% CI",
+                    "Rank CI",
+                    "Std",
+                    "Median",
+                    "Per round",
+                    "Best",
+                    "Worst",
+                    "Image",
+                ] {
+                    ui.strong(tr(header));
+                }
+                ui.end_row();
+                for s in &stats {
+                    ui.label(s.name.replace('\n', " "));
+                    ui.label(format!("{:.2}", s.score(normalization)));
+                    let r = s.record;
+                    ui.label(format!("{}/{}/{}", r.wins, r.losses, r.draws));
+                    ui.label(format!("{:.2}", s.mean));
+                    let mean_ci = s.mean_ci.map(|(lo, hi)| format!("[{lo:.2}, {hi:.2}]"));
+                    ui.label(mean_ci.unwrap_or_else(|| "-".to_string()));
+                    let rank_ci = s.rank_ci.map(|(best, worst)| format!("{best}-{worst}"));
+                    ui.label(rank_ci.unwrap_or_else(|| "-".to_string()));
+                    ui.label(format!("{:.2}", s.std_dev));
+                    ui.label(format!("{:.1}", s.median));
+                    ui.label(format!("{:.3}", s.per_round));
+                    let (best, best_score) = &s.best_matchup;
+                    ui.label(format!("{} ({best_score})", best.replace('\n', " ")));
+                    let (worst, worst_score) = &s.worst_matchup;
+                    ui.label(format!("{} ({worst_score})", worst.replace('\n', " ")));
+                    ui.label(s.image.to_string());
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn show_ratings(&mut self, ui: &mut egui::Ui) {
+        let outcomes = self.shared.run.outcomes.lock().unwrap().clone();
+        if outcomes.is_empty() {
+            ui.label(tr("Ratings appear once a generation has been simulated."));
+            return;
         }
 
-        if ui.button("Reset").clicked() {
-            self.reset_game();
+        ui.label(tr(
+            "Bradley-Terry ratings over all generations since the last reset.",
+        ));
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("ratings").striped(true).show(ui, |ui| {
+                ui.strong("#");
+                ui.strong(tr("Strategy"));
+                ui.strong(tr("Rating"));
+                ui.end_row();
+                for (rank, (name, rating)) in outcomes.bradley_terry().iter().enumerate() {
+                    ui.label((rank + 1).to_string());
+                    ui.label(name.replace('\n', " "));
+                    ui.label(format!("{rating:.0}"));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn show_behaviour(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr(
+            "Participants grouped by cooperation rate at start and after CC/CD/DC/DD.",
+        ));
+        let clusters = self.shared.run.clusters.lock().unwrap().clone();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("clusters").striped(true).show(ui, |ui| {
+                for header in ["Start", "CC", "CD", "DC", "DD", "Members"] {
+                    ui.strong(tr(header));
+                }
+                ui.end_row();
+                for cluster in &clusters {
+                    for rate in cluster.centroid {
+                        match rate {
+                            Some(rate) => ui.label(format!("{rate:.2}")),
+                            None => ui.label("-"),
+                        };
+                    }
+                    let members: Vec<String> =
+                        cluster.members.iter().map(|m| m.replace('\n', " ")).collect();
+                    ui.label(members.join(", "));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn show_markov(&mut self, ui: &mut egui::Ui) {
+        if let Some(mvp) = *self.shared.run.mvp.lock().unwrap() {
+            let strategy = MemoryOne::from_genome(mvp);
+            let res = analysis::analyse_pair(
+                &strategy,
+                &strategy,
+                &analysis::payoffs_of(PayoffMatrix::new(self.settings.payoffs)),
+            );
+            ui.label(tr_fill(
+                "Genome {} against itself: {} per round, cooperating {}% of the time.",
+                &[
+                    &mvp,
+                    &format!("{:.3}", res.payoffs.0),
+                    &format!("{:.0}", res.cooperation.0 * 100.0),
+                ],
+            ));
+            let stationary = res.stationary.map(|p| format!("{p:.2}"));
+            let [cc, cd, dc, dd] = &stationary;
+            ui.label(tr_fill(
+                "Stationary CC {}, CD {}, DC {}, DD {}",
+                &[cc, cd, dc, dd],
+            ));
         }
 
-        ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
-            ui.add(egui::Image::new(egui::include_image!("../felix.png")));
+        let stability = self.shared.run.stability.lock().unwrap().clone();
+        if !stability.is_empty() {
+            let with = |kind: Stability| -> Vec<String> {
+                (0..GENOME_COUNT).filter(|&g| stability[g] == kind).map(|g| g.to_string()).collect()
+            };
+            ui.label(tr_fill(
+                "ESS genomes: {}",
+                &[&with(Stability::Strict).join(", ")],
+            ));
+            ui.label(tr_fill(
+                "Neutrally stable: {}",
+                &[&with(Stability::Neutral).join(", ")],
+            ));
+            if let Some(mvp) = *self.shared.run.mvp.lock().unwrap() {
+                let verdict = match stability[mvp as usize] {
+                    Stability::Strict => "is evolutionarily stable",
+                    Stability::Neutral => "is neutrally stable",
+                    Stability::Unstable => "can be invaded",
+                };
+                ui.label(tr_fill("The GA winner {} {}.", &[&mvp, &tr(verdict)]));
+            }
+        }
+
+        let checks = self.shared.run.cross_check.lock().unwrap().clone();
+        egui::Grid::new("markov").striped(true).show(ui, |ui| {
+            for header in ["Strategy", "Simulated", "Analytic", "Cooperation"] {
+                ui.strong(tr(header));
+            }
+            ui.end_row();
+            for c in &checks {
+                ui.label(c.name.replace('\n', " "));
+                ui.label(format!("{:.3}", c.simulated));
+                ui.label(format!("{:.3}", c.analytic));
+                ui.label(format!("{:.0}%", c.cooperation * 100.0));
+                ui.end_row();
+            }
         });
     }
 
-    fn show(&mut self, ui: &mut egui::Ui) {
-        show_columns(ui, 0.2, |lui, rui| {
-            self.show_left(lui);
-            self.show_plot(rui);
+    fn show_population(&mut self, ui: &mut egui::Ui) {
+        let frequencies = self.shared.run.recorder.lock().unwrap().frequencies();
+        let present: Vec<usize> =
+            (0..GENOME_COUNT).filter(|&g| frequencies.iter().any(|counts| counts[g] > 0)).collect();
+        let lines: Vec<Line> = present
+            .iter()
+            .map(|&g| {
+                let points: PlotPoints = frequencies
+                    .iter()
+                    .enumerate()
+                    .map(|(x, counts)| [x as f64, counts[g] as f64])
+                    .collect();
+                Line::new(points).name(g.to_string())
+            })
+            .collect();
+
+        if ui.button(tr("Export genotypes")).clicked() {
+            self.export_status = match stats::write_genotypes("genotypes.csv", &frequencies) {
+                Ok(()) => "wrote genotypes.csv".to_string(),
+                Err(err) => format!("export failed: {err}"),
+            };
+        }
+        ui.label(&self.export_status);
+        ui.checkbox(&mut self.stacked_population, tr("Stacked"));
+
+        // Bars one generation wide, each stacked on the genome below, read as a stacked area.
+        let mut layers: Vec<BarChart> = Vec::new();
+        if self.stacked_population {
+            for (k, &g) in present.iter().enumerate() {
+                let bars = frequencies
+                    .iter()
+                    .enumerate()
+                    .map(|(x, counts)| Bar::new(x as f64, counts[g] as f64))
+                    .collect();
+                let mut layer =
+                    self.plot_style.bars(BarChart::new(bars).width(1.0).name(g.to_string()), k);
+                if let Some(below) = layers.last() {
+                    layer = layer.stack_on(&[below]);
+                }
+                layers.push(layer);
+            }
+        }
+
+        Plot::new("Population")
+            .height(ui.available_height() * 0.5)
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(tr("Individuals"))
+            .legend(egui_plot::Legend::default())
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                if self.stacked_population {
+                    for layer in layers {
+                        plot_ui.bar_chart(layer);
+                    }
+                } else {
+                    for (k, line) in lines.into_iter().enumerate() {
+                        plot_ui.line(self.plot_style.line(line, k));
+                    }
+                }
+            });
+
+        let latest = frequencies.last().copied().unwrap_or([0; GENOME_COUNT]);
+        ui.label(format!(
+            "{}: {}",
+            tr("Distinct genomes in the latest generation"),
+            stats::diversity(&latest)
+        ));
+        let bars = latest
+            .iter()
+            .enumerate()
+            .map(|(g, &count)| {
+                let genes = genome_label(&number_to_genome(g as u8));
+                Bar::new(g as f64, count as f64).name(format!("{g} ({genes})"))
+            })
+            .collect();
+        Plot::new("Genome histogram")
+            .height(ui.available_height() * 0.4)
+            .x_axis_label(tr("Genome"))
+            .y_axis_label(tr("Individuals"))
+            .allow_zoom(false)
+            .allow_drag(false)
+            .include_x(-0.5)
+            .include_x(GENOME_COUNT as f64 - 0.5)
+            .include_y(0.0)
+            .show(ui, |plot_ui| {
+                let chart = BarChart::new(bars).width(0.8).color(Color32::LIGHT_BLUE);
+                plot_ui.bar_chart(self.plot_style.bars(chart, 0));
+                if plot_ui.response().clicked() {
+                    let bar = plot_ui.pointer_coordinate().map(|p| p.x.round());
+                    if let Some(g) = bar.filter(|g| (0.0..GENOME_COUNT as f64).contains(g)) {
+                        self.inspected = Some(g as u8);
+                    }
+                }
+            });
+
+        ui.label(tr("Genome counts per generation, genome 0 at the top."));
+        let (rect, _) = ui.allocate_exact_size(ui.available_size(), egui::Sense::hover());
+        if frequencies.is_empty() {
+            return;
+        }
+        let painter = ui.painter_at(rect);
+        let cell = egui::vec2(
+            rect.width() / frequencies.len() as f32,
+            rect.height() / GENOME_COUNT as f32,
+        );
+        let max = frequencies.iter().flatten().copied().max().unwrap_or(1).max(1);
+        for (x, counts) in frequencies.iter().enumerate() {
+            for (g, &count) in counts.iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+                let min = rect.min + egui::vec2(x as f32 * cell.x, g as f32 * cell.y);
+                painter.rect_filled(
+                    egui::Rect::from_min_size(min, cell),
+                    0.0,
+                    self.plot_style.heat(count as f64 / max as f64),
+                );
+            }
+        }
+    }
+
+    fn show_sensitivity(&mut self, ui: &mut egui::Ui) {
+        let rows = self.shared.run.sensitivity.lock().unwrap().clone();
+        if rows.is_empty() {
+            ui.label(tr(
+                "The sensitivity analysis runs on the final generation of a simulation.",
+            ));
+            return;
+        }
+
+        if ui.button(tr("Export sensitivity")).clicked() {
+            self.export_status = match stats::write_sensitivity("sensitivity.csv", &rows) {
+                Ok(()) => "wrote sensitivity.csv".to_string(),
+                Err(err) => format!("export failed: {err}"),
+            };
+        }
+
+        let bars: Vec<Bar> = analysis::tornado(&rows)
+            .iter()
+            .enumerate()
+            .map(|(i, &(name, low, high))| {
+                Bar::new(i as f64, high - low).base_offset(low).name(name)
+            })
+            .collect();
+        Plot::new("Sensitivity")
+            .height(ui.available_height() * 0.4)
+            .x_axis_label(tr("Cooperation rate of the winner"))
+            .y_axis_formatter(|mark, _, _| {
+                let i = mark.value.round() as usize;
+                match analysis::PAYOFF_PARAMETERS.get(i) {
+                    Some(name) if (mark.value - i as f64).abs() < 1e-6 => name.to_string(),
+                    _ => String::new(),
+                }
+            })
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(BarChart::new(bars).horizontal().color(Color32::LIGHT_BLUE));
+            });
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("sensitivity").striped(true).show(ui, |ui| {
+                for header in ["Parameter", "Shift", "Winner", "Score", "Cooperation"] {
+                    ui.strong(tr(header));
+                }
+                ui.end_row();
+                for r in &rows {
+                    ui.label(r.parameter);
+                    ui.label(format!("{:+.1}", r.delta));
+                    ui.label(r.winner.replace('\n', " "));
+                    ui.label(format!("{:.3}", r.winner_score));
+                    ui.label(format!("{:.0}%", r.cooperation * 100.0));
+                    ui.end_row();
+                }
+            });
         });
     }
-}
 
-fn simulate(
-    ctx: egui::Context,
-    ys: Arc<Mutex<Vec<i32>>>,
-    sim: Arc<AtomicBool>,
-    game_count: u32,
-    gen_count: u32,
-) {
-    let mut gen = (0..20).collect::<Vec<u8>>().into_boxed_slice();
+    fn show_noise(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run noise report")).clicked() {
+                let ctx = ui.ctx().clone();
+                let noise = self.shared.noise.clone();
+                let setup = self.settings.setup();
+                noise.lock().clear();
+                std::thread::spawn(move || {
+                    *noise.lock() = experiments::noise_robustness(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export noise report")).clicked() {
+                let rows = self.shared.noise.lock().clone();
+                self.export_status = match stats::write_noise_report("noise.csv", &rows) {
+                    Ok(()) => "wrote noise.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+
+        let rows = self.shared.noise.lock().clone();
+        if rows.is_empty() {
+            ui.label(
+                "Evolves the population once per combination of execution and perception noise.",
+            );
+            return;
+        }
+        let winners: Vec<String> = rows
+            .iter()
+            .map(|r| format!("{}/{}: {}", r.execution, r.perception, r.winner))
+            .collect();
+        ui.label(format!(
+            "Evolved winner per execution/perception noise: {}",
+            winners.join(", ")
+        ));
 
-    sim.store(true, Ordering::Relaxed);
-    ys.lock().clear();
+        // Ranks as execution noise grows, without perception noise.
+        let without_perception: Vec<&NoiseRow> =
+            rows.iter().filter(|r| r.perception == 0.0).collect();
+        let names: Vec<String> = rows[0].ranks.iter().map(|(name, _)| name.clone()).collect();
+        Plot::new("Noise")
+            .x_axis_label(tr("Execution noise"))
+            .y_axis_label(tr("Rank"))
+            .legend(egui_plot::Legend::default())
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                for (k, name) in names.iter().enumerate() {
+                    let points: PlotPoints = without_perception
+                        .iter()
+                        .map(|r| [r.execution, r.ranks[k].1 as f64])
+                        .collect();
+                    plot_ui.line(
+                        self.plot_style.line(Line::new(points).name(name.replace('\n', " ")), k),
+                    );
+                }
+            });
+    }
 
-    for _ in 0..gen_count {
-        let mut game = Tournament::from(game_count, prisoners_dillemma_rules, gen);
-        game.run();
-        let (fittest, mvp_score) = game.select_ten_fittest_and_bestscore();
-        let _mvp = &fittest[0];
+    fn show_forgetting(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run forgetting report")).clicked() {
+                let ctx = ui.ctx().clone();
+                let forgetting = self.shared.forgetting.clone();
+                let solo = self.shared.solo_forgetting.clone();
+                let setup = self.settings.setup();
+                forgetting.lock().clear();
+                std::thread::spawn(move || {
+                    *solo.lock() = experiments::solo_forgetting(setup.game_count);
+                    *forgetting.lock() = experiments::forgetting_report(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export forgetting report")).clicked() {
+                let rows = self.shared.forgetting.lock().clone();
+                let solo = self.shared.solo_forgetting.lock().clone();
+                self.export_status = match stats::write_forgetting_report("forgetting.csv", &rows)
+                    .and_then(|()| stats::write_solo_forgetting("solo_forgetting.csv", &solo))
+                {
+                    Ok(()) => "wrote forgetting.csv and solo_forgetting.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
 
-        if !sim.load(Ordering::Relaxed) {
+        let rows = self.shared.forgetting.lock().clone();
+        if rows.is_empty() {
+            ui.label(
+                "Evolves the population once per probability of forgetting the previous moves.",
+            );
             return;
         }
+        let winners: Vec<String> =
+            rows.iter().map(|r| format!("{}: {}", r.forgetting, r.winner)).collect();
+        ui.label(format!(
+            "Evolved winner per forgetting level: {}",
+            winners.join(", ")
+        ));
 
-        ys.lock().push(mvp_score);
-        ctx.request_repaint();
+        ui.label(format!(
+            "Rank among the named strategies when only that strategy forgets {:.0}% of the time:",
+            experiments::SOLO_FORGETTING * 100.0
+        ));
+        egui::Grid::new("solo forgetting").striped(true).show(ui, |ui| {
+            for header in ["Strategy", "Perfect recall", "Forgetful"] {
+                ui.strong(tr(header));
+            }
+            ui.end_row();
+            for r in self.shared.solo_forgetting.lock().iter() {
+                ui.label(r.name.replace('\n', " "));
+                ui.label(r.recall_rank.to_string());
+                ui.label(r.forgetful_rank.to_string());
+                ui.end_row();
+            }
+        });
 
-        gen = get_new_generation(fittest);
+        let names: Vec<String> = rows[0].ranks.iter().map(|(name, _)| name.clone()).collect();
+        Plot::new("Forgetting")
+            .x_axis_label(tr("Forgetting"))
+            .y_axis_label(tr("Rank"))
+            .legend(egui_plot::Legend::default())
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                for (k, name) in names.iter().enumerate() {
+                    let points: PlotPoints =
+                        rows.iter().map(|r| [r.forgetting, r.ranks[k].1 as f64]).collect();
+                    plot_ui.line(
+                        self.plot_style.line(Line::new(points).name(name.replace('\n', " ")), k),
+                    );
+                }
+            });
     }
 
-    sim.store(false, Ordering::Relaxed);
-}
+    fn show_genes(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr(
+            "Average fitness of evolved individuals by how each gene responds.",
+        ));
+        let means = self.shared.run.genes.lock().unwrap().means();
+        let fmt = |v: Option<f64>| v.map_or("-".to_string(), |v| format!("{v:.1}"));
+        egui::Grid::new("genes").striped(true).show(ui, |ui| {
+            for header in ["Gene", "Defects", "Cooperates", "Difference"] {
+                ui.strong(tr(header));
+            }
+            ui.end_row();
+            for (context, (defect, cooperate)) in stats::GENE_CONTEXTS.iter().zip(means) {
+                ui.label(*context);
+                ui.label(fmt(defect));
+                ui.label(fmt(cooperate));
+                ui.label(fmt(cooperate.zip(defect).map(|(c, d)| c - d)));
+                ui.end_row();
+            }
+        });
+    }
 
-impl eframe::App for App {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| self.show(ui));
+    fn show_heatmap(&mut self, ui: &mut egui::Ui) {
+        let Some(grid) = self.shared.run.score_grid.lock().unwrap().clone() else {
+            ui.label(tr(
+                "The heatmap appears once a generation has been simulated.",
+            ));
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.heatmap_difference, false, tr("Own score"));
+            ui.selectable_value(&mut self.heatmap_difference, true, tr("Score difference"));
+        });
+        ui.label(tr(
+            "Per-round score of each fixed player (column) against each opponent (row).",
+        ));
+
+        let rounds = grid.rounds.max(1) as f64;
+        let value = |(opponent, player): (i32, i32)| {
+            let value = if self.heatmap_difference {
+                player - opponent
+            } else {
+                player
+            };
+            value as f64 / rounds
+        };
+        let values = grid.scores.iter().flatten().map(|&s| value(s));
+        let (low, high) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), v| {
+            (low.min(v), high.max(v))
+        });
+        // Differences are centred on zero, so that even matchups get the middle colour.
+        let (low, high) = if self.heatmap_difference {
+            let extent = low.abs().max(high.abs());
+            (-extent, extent)
+        } else {
+            (low, high)
+        };
+        let level = |v: f64| {
+            if high > low {
+                (v - low) / (high - low)
+            } else {
+                0.5
+            }
+        };
+
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), egui::Sense::hover());
+        let (rows, columns) = (grid.scores.rows(), grid.scores.cols());
+        if rows == 0 || columns == 0 {
+            return;
+        }
+        let painter = ui.painter_at(rect);
+        let cell = egui::vec2(rect.width() / columns as f32, rect.height() / rows as f32);
+        for ((i, j), score) in grid.scores.indexed_iter() {
+            let Some(score) = *score else {
+                continue;
+            };
+            let min = rect.min + egui::vec2(j as f32 * cell.x, i as f32 * cell.y);
+            painter.rect_filled(
+                egui::Rect::from_min_size(min, cell),
+                0.0,
+                self.plot_style.heat(level(value(score))),
+            );
+        }
+        if let Some(pos) = response.hover_pos() {
+            let offset = pos - rect.min;
+            let (i, j) = ((offset.y / cell.y) as usize, (offset.x / cell.x) as usize);
+            if let Some(Some((opponent, player))) = grid.scores.get(i, j) {
+                response.on_hover_text(format!(
+                    "{} {}: {:.2}\n{} {}: {:.2}",
+                    tr("Player"),
+                    grid.players[j].replace('\n', " "),
+                    *player as f64 / rounds,
+                    tr("Opponent"),
+                    grid.opponents[i].replace('\n', " "),
+                    *opponent as f64 / rounds,
+                ));
+            }
+        }
+    }
+
+    fn show_dominance(&mut self, ui: &mut egui::Ui) {
+        let outcomes = self.shared.run.outcomes.lock().unwrap().clone();
+        if outcomes.is_empty() {
+            ui.label(tr(
+                "The dominance graph appears once a generation has been simulated.",
+            ));
+            return;
+        }
+
+        let graph = outcomes.dominance();
+        let components = graph.components();
+        let condensation = graph.condensation(&components);
+        let cyclic: Vec<bool> = {
+            let mut cyclic = vec![false; graph.names.len()];
+            for c in components.iter().filter(|c| c.len() > 1) {
+                for &v in c {
+                    cyclic[v] = true;
+                }
+            }
+            cyclic
+        };
+        if graph.is_transitive() {
+            ui.label(tr(
+                "Dominance is transitive: there is no cycle of head-to-head wins.",
+            ));
+        } else {
+            ui.label(tr("Red arrows are part of a cycle of head-to-head wins."));
+        }
+
+        let size = ui.available_width().min(ui.available_height() * 0.6);
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        let radius = size * 0.4;
+        let n = graph.names.len();
+        let position = |i: usize| {
+            let angle = std::f32::consts::TAU * i as f32 / n as f32;
+            rect.center() + radius * egui::vec2(angle.cos(), angle.sin())
+        };
+        for &(a, b) in &graph.edges {
+            let (from, to) = (position(a), position(b));
+            let dir = (to - from).normalized();
+            let (colour, width) = if cyclic[a] && cyclic[b] {
+                self.plot_style.highlight()
+            } else {
+                (Color32::GRAY, 1.0)
+            };
+            painter.arrow(
+                from + dir * 6.0,
+                to - from - dir * 12.0,
+                egui::Stroke::new(width, colour),
+            );
+        }
+        for (i, name) in graph.names.iter().enumerate() {
+            painter.circle_filled(position(i), 4.0, Color32::LIGHT_BLUE);
+            painter.text(
+                position(i),
+                egui::Align2::CENTER_BOTTOM,
+                name.replace('\n', " "),
+                FontId::proportional(12.0),
+                ui.visuals().text_color(),
+            );
+        }
+
+        ui.label(tr(
+            "Strongly connected components, from dominant to dominated:",
+        ));
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("components").striped(true).show(ui, |ui| {
+                ui.strong("#");
+                ui.strong(tr("Members"));
+                ui.strong(tr("Beats components"));
+                ui.end_row();
+                for (c, members) in components.iter().enumerate() {
+                    let names: Vec<String> =
+                        members.iter().map(|&v| graph.names[v].replace('\n', " ")).collect();
+                    let beats: Vec<String> = condensation
+                        .iter()
+                        .filter(|(a, _)| *a == c)
+                        .map(|(_, b)| (b + 1).to_string())
+                        .collect();
+                    ui.label((c + 1).to_string());
+                    ui.label(names.join(", "));
+                    ui.label(beats.join(", "));
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn show_meta(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run meta-tournament")).clicked() {
+                let ctx = ui.ctx().clone();
+                let meta = self.shared.meta.clone();
+                let setup = self.settings.setup();
+                std::thread::spawn(move || {
+                    *meta.lock() = experiments::meta_tournament(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export meta-tournament")).clicked() {
+                let rows = self.shared.meta.lock().clone();
+                self.export_status = match stats::write_meta_tournament("games.csv", &rows) {
+                    Ok(()) => "wrote games.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rows = self.shared.meta.lock().clone();
+        if rows.is_empty() {
+            ui.label(tr(
+                "Plays the fixed players under every game preset and compares their ranks.",
+            ));
+            return;
+        }
+        egui::Grid::new("meta").striped(true).show(ui, |ui| {
+            ui.strong(tr("Strategy"));
+            for (name, _) in GAME_PRESETS {
+                ui.strong(name);
+            }
+            ui.strong(tr("Mean rank"));
+            ui.strong(tr("Worst rank"));
+            ui.end_row();
+            for r in &rows {
+                ui.label(r.name.replace('\n', " "));
+                for rank in &r.ranks {
+                    ui.label(rank.to_string());
+                }
+                ui.label(format!("{:.1}", r.robustness));
+                ui.label(r.worst_rank.to_string());
+                ui.end_row();
+            }
+        });
+    }
+
+    fn show_round_robin(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let mut swiss = self.swiss_rounds.is_some();
+            if ui.checkbox(&mut swiss, tr("Swiss pairing")).changed() {
+                self.swiss_rounds = swiss.then_some(experiments::SWISS_ROUNDS);
+            }
+            if let Some(rounds) = &mut self.swiss_rounds {
+                ui.label(format!("{}: {rounds}", tr("Rounds")));
+                ui.add(egui::widgets::Slider::new(rounds, 1..=16).show_value(false));
+            }
+            if ui.button(tr("Run round-robin")).clicked() {
+                let ctx = ui.ctx().clone();
+                let round_robin = self.shared.round_robin.clone();
+                let setup = self.settings.setup();
+                let swiss_rounds = self.swiss_rounds;
+                let copies = self.copies;
+                let costs = self.costs;
+                std::thread::spawn(move || {
+                    let repetitions = experiments::ROUND_ROBIN_REPETITIONS;
+                    *round_robin.lock() = match swiss_rounds {
+                        Some(rounds) => experiments::swiss(&setup, rounds, repetitions),
+                        None => experiments::round_robin(&setup, repetitions, &copies, &costs),
+                    };
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export round-robin")).clicked() {
+                let rows = self.shared.round_robin.lock().clone();
+                self.export_status = match stats::write_round_robin("round_robin.csv", &rows) {
+                    Ok(()) => "wrote round_robin.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+        if self.swiss_rounds.is_none() {
+            ui.collapsing(tr("Instances and cost per round per strategy"), |ui| {
+                egui::Grid::new("copies").show(ui, |ui| {
+                    let entries =
+                        strategy_names().into_iter().zip(&mut self.copies).zip(&mut self.costs);
+                    for (k, ((name, copies), cost)) in entries.enumerate() {
+                        ui.label(name.replace('\n', " "));
+                        ui.add(egui::DragValue::new(copies).clamp_range(0..=20));
+                        ui.add(egui::DragValue::new(cost).clamp_range(0..=3));
+                        if k % 3 == 2 {
+                            ui.end_row();
+                        }
+                    }
+                });
+            });
+        }
+
+        let rows = self.shared.round_robin.lock().clone();
+        if rows.is_empty() {
+            ui.label(tr(
+                "Plays every named strategy against every other one, without evolution.",
+            ));
+            return;
+        }
+        egui::Grid::new("round_robin").striped(true).show(ui, |ui| {
+            ui.strong(tr("Rank"));
+            ui.strong(tr("Strategy"));
+            ui.strong(tr("Mean score"));
+            ui.strong(tr("Std dev"));
+            ui.strong(tr("Wins"));
+            ui.strong(tr("Losses"));
+            ui.end_row();
+            for (rank, r) in rows.iter().enumerate() {
+                ui.label((rank + 1).to_string());
+                ui.label(r.name.replace('\n', " "));
+                ui.label(format!("{:.3}", r.score));
+                ui.label(format!("{:.3}", r.spread));
+                ui.label(r.wins.to_string());
+                ui.label(r.losses.to_string());
+                ui.end_row();
+            }
+        });
+    }
+
+    fn show_bracket(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run knockout")).clicked() {
+                let ctx = ui.ctx().clone();
+                let bracket = self.shared.bracket.clone();
+                let setup = self.settings.setup();
+                std::thread::spawn(move || {
+                    *bracket.lock() = experiments::knockout(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export bracket")).clicked() {
+                let rounds = self.shared.bracket.lock().clone();
+                self.export_status = match stats::write_bracket("bracket.csv", &rounds) {
+                    Ok(()) => "wrote bracket.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rounds = self.shared.bracket.lock().clone();
+        if rounds.is_empty() {
+            ui.label(
+                "Seeds the named strategies into a knockout bracket; the higher score advances.",
+            );
+            return;
+        }
+        let entry = |name: &str, score: Option<i32>, won: bool| {
+            let text = match score {
+                Some(score) => format!("{} ({score})", name.replace('\n', " ")),
+                None => name.replace('\n', " "),
+            };
+            if won {
+                RichText::new(text).strong()
+            } else {
+                RichText::new(text).weak()
+            }
+        };
+        egui::ScrollArea::both().show(ui, |ui| {
+            ui.horizontal_top(|ui| {
+                for (round, matches) in rounds.iter().enumerate() {
+                    ui.vertical(|ui| {
+                        ui.strong(match rounds.len() - round {
+                            1 => tr("Final").to_string(),
+                            2 => tr("Semi-finals").to_string(),
+                            _ => tr_fill("Round {}", &[&(round + 1)]),
+                        });
+                        // Line each match up between the two matches feeding into it.
+                        let spacing = BRACKET_SLOT * ((1 << round) - 1) as f32;
+                        ui.add_space(spacing / 2.0);
+                        for m in matches {
+                            ui.group(|ui| {
+                                ui.set_width(BRACKET_WIDTH);
+                                match &m.second {
+                                    Some(second) => {
+                                        ui.label(entry(&m.first, Some(m.scores.0), m.first_wins));
+                                        ui.label(entry(second, Some(m.scores.1), !m.first_wins));
+                                    }
+                                    None => {
+                                        ui.label(entry(&m.first, None, true));
+                                        ui.label(entry("bye", None, false));
+                                    }
+                                }
+                            });
+                            ui.add_space(spacing);
+                        }
+                    });
+                }
+            });
+        });
+    }
+
+    fn show_environments(&mut self, ui: &mut egui::Ui) {
+        let mut removed = None;
+        for (k, (preset, noise)) in self.environments.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_source(("environment", k))
+                    .selected_text(GAME_PRESETS[*preset].0)
+                    .show_ui(ui, |ui| {
+                        for (p, (name, _)) in GAME_PRESETS.iter().enumerate() {
+                            ui.selectable_value(preset, p, *name);
+                        }
+                    });
+                ui.label(format!("{}: {:.0}%", tr("Noise"), *noise * 100.0));
+                ui.add(egui::widgets::Slider::new(noise, 0.0..=0.2).show_value(false));
+                if ui.button(tr("Remove")).clicked() {
+                    removed = Some(k);
+                }
+            });
+        }
+        if let Some(k) = removed {
+            self.environments.remove(k);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button(tr("Add population")).clicked() {
+                self.environments.push((0, 0.0));
+            }
+            if ui.button(tr("Run populations")).clicked() {
+                let setup = self.settings.setup();
+                let labels = self.environments.iter().map(|&(preset, noise)| {
+                    format!("{} {:.0}%", GAME_PRESETS[preset].0, noise * 100.0)
+                });
+                *self.shared.environments.lock() = labels.map(|l| (l, Vec::new())).collect();
+                for (k, &(preset, noise)) in self.environments.iter().enumerate() {
+                    let ctx = ui.ctx().clone();
+                    let environments = self.shared.environments.clone();
+                    let environment = Setup {
+                        payoffs: payoff_table(GAME_PRESETS[preset].1),
+                        execution_noise: noise,
+                        ..setup
+                    };
+                    std::thread::spawn(move || {
+                        let rows = experiments::evolve_in(&environment);
+                        if let Some(slot) = environments.lock().get_mut(k) {
+                            slot.1 = rows;
+                        }
+                        ctx.request_repaint();
+                    });
+                }
+            }
+            if ui.button(tr("Export populations")).clicked() {
+                let populations = self.shared.environments.lock().clone();
+                self.export_status =
+                    match stats::write_environments("environments.csv", &populations) {
+                        Ok(()) => "wrote environments.csv".to_string(),
+                        Err(err) => format!("export failed: {err}"),
+                    };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let populations = self.shared.environments.lock().clone();
+        if populations.is_empty() {
+            ui.label(tr(
+                "Evolves a population in every environment above and compares their fitness.",
+            ));
+            return;
+        }
+        let series = |rows: &[EnvironmentRow], value: fn(&EnvironmentRow) -> f64| -> PlotPoints {
+            rows.iter().enumerate().map(|(x, r)| [x as f64, value(r)]).collect()
+        };
+        Plot::new("Environments")
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(tr("Relative fitness"))
+            .legend(egui_plot::Legend::default())
+            .include_y(0.0)
+            .include_y(1.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                for (k, (label, rows)) in populations.iter().enumerate() {
+                    let line = Line::new(series(rows, |r| r.fitness)).name(label);
+                    plot_ui.line(self.plot_style.line(line, k));
+                }
+            });
+    }
+
+    fn show_variants(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "{}: {:.0}%",
+                tr("Noise"),
+                self.variant_noise * 100.0
+            ));
+            ui.add(
+                egui::widgets::Slider::new(&mut self.variant_noise, 0.01..=0.2).show_value(false),
+            );
+            if ui.button(tr("Run noisy variants")).clicked() {
+                let ctx = ui.ctx().clone();
+                let variants = self.shared.variants.clone();
+                let (game_count, noise) = (self.settings.game_count, self.variant_noise);
+                std::thread::spawn(move || {
+                    *variants.lock() = experiments::noisy_variants(
+                        game_count,
+                        noise,
+                        experiments::ROUND_ROBIN_REPETITIONS,
+                    );
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export variants")).clicked() {
+                let rows = self.shared.variants.lock().clone();
+                self.export_status = match stats::write_variants("variants.csv", &rows) {
+                    Ok(()) => "wrote variants.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rows = self.shared.variants.lock().clone();
+        if rows.is_empty() {
+            ui.label(tr(
+                "Enters every named strategy together with a noisy copy of itself.",
+            ));
+            return;
+        }
+        egui::Grid::new("variants").striped(true).show(ui, |ui| {
+            for header in ["Strategy", "Clean", "Noisy", "Loss"] {
+                ui.strong(tr(header));
+            }
+            ui.end_row();
+            for r in &rows {
+                ui.label(r.name.replace('\n', " "));
+                ui.label(format!("{:.3}", r.clean));
+                ui.label(format!("{:.3}", r.noisy));
+                ui.label(format!("{:.3}", r.clean - r.noisy));
+                ui.end_row();
+            }
+        });
+    }
+
+    fn show_openings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run mixed openings")).clicked() {
+                let ctx = ui.ctx().clone();
+                let openings = self.shared.openings.clone();
+                let setup = self.settings.setup();
+                std::thread::spawn(move || {
+                    *openings.lock() = experiments::mixed_openings(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export mixed openings")).clicked() {
+                let rows = self.shared.openings.lock().clone();
+                self.export_status = match stats::write_opening_report("openings.csv", &rows) {
+                    Ok(()) => "wrote openings.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rows = self.shared.openings.lock().clone();
+        if rows.is_empty() {
+            ui.label(format!(
+                "Evolves organisms that open with cooperation at one of {} probability levels.",
+                experiments::OPENING_LEVELS + 1
+            ));
+            return;
+        }
+        let series = |value: fn(&OpeningRow) -> f64| -> PlotPoints {
+            rows.iter().enumerate().map(|(x, r)| [x as f64, value(r)]).collect()
+        };
+        Plot::new("Openings")
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(tr("Fraction"))
+            .legend(egui_plot::Legend::default())
+            .include_y(0.0)
+            .include_y(1.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.opening)).name(tr("Opening cooperation")),
+                    0,
+                ));
+                plot_ui.line(
+                    self.plot_style
+                        .line(Line::new(series(|r| r.mixed)).name(tr("Mixed openers")), 1),
+                );
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.cooperation)).name(tr("Cooperation")),
+                    2,
+                ));
+            });
+    }
+
+    fn show_tags(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run green-beard")).clicked() {
+                let ctx = ui.ctx().clone();
+                let tags = self.shared.tags.clone();
+                let setup = self.settings.setup();
+                std::thread::spawn(move || {
+                    *tags.lock() = experiments::green_beard(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export green-beard")).clicked() {
+                let rows = self.shared.tags.lock().clone();
+                self.export_status = match stats::write_tag_report("tags.csv", &rows) {
+                    Ok(()) => "wrote tags.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rows = self.shared.tags.lock().clone();
+        if rows.is_empty() {
+            ui.label(format!(
+                "Evolves organisms carrying one of {} tags and a gene to cooperate with \
+                 anyone wearing the same tag.",
+                experiments::TAG_COUNT
+            ));
+            return;
+        }
+        let series = |value: fn(&TagRow) -> f64| -> PlotPoints {
+            rows.iter().enumerate().map(|(x, r)| [x as f64, value(r)]).collect()
+        };
+        Plot::new("Tags")
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(tr("Fraction"))
+            .legend(egui_plot::Legend::default())
+            .include_y(0.0)
+            .include_y(1.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.green_beards)).name(tr("Green beards")),
+                    0,
+                ));
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.majority_tag)).name(tr("Majority tag")),
+                    1,
+                ));
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.cooperation)).name(tr("Cooperation")),
+                    2,
+                ));
+            });
+    }
+
+    fn show_memory_depth(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run memory depth")).clicked() {
+                let ctx = ui.ctx().clone();
+                let memory_depth = self.shared.memory_depth.clone();
+                let setup = self.settings.setup();
+                std::thread::spawn(move || {
+                    *memory_depth.lock() = experiments::memory_depth(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export memory depth")).clicked() {
+                let rows = self.shared.memory_depth.lock().clone();
+                self.export_status = match stats::write_depth_report("memory_depth.csv", &rows) {
+                    Ok(()) => "wrote memory_depth.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rows = self.shared.memory_depth.lock().clone();
+        let Some(last) = rows.last() else {
+            ui.label(format!(
+                "Evolves genomes remembering the last rounds of every matchup, with {} genes at \
+                 depth {}.",
+                gametheory::genome_length(self.settings.genetic.depth),
+                self.settings.genetic.depth
+            ));
+            return;
+        };
+        ui.label(format!(
+            "Fittest genome of the last generation, {:.2} per round: {}",
+            last.best, last.winner
+        ));
+        let series = |value: fn(&DepthRow) -> f64| -> PlotPoints {
+            rows.iter().enumerate().map(|(x, r)| [x as f64, value(r)]).collect()
+        };
+        Plot::new("Memory depth")
+            .x_axis_label(tr("Tournaments"))
+            .legend(egui_plot::Legend::default())
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                plot_ui
+                    .line(self.plot_style.line(Line::new(series(|r| r.best)).name(tr("Best")), 0));
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.cooperation)).name(tr("Cooperation")),
+                    1,
+                ));
+            });
+    }
+
+    fn show_adaptive(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run self-adaptive mutation")).clicked() {
+                let ctx = ui.ctx().clone();
+                let adaptive = self.shared.adaptive.clone();
+                let setup = self.settings.setup();
+                std::thread::spawn(move || {
+                    *adaptive.lock() = experiments::self_adaptive_mutation(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export self-adaptive mutation")).clicked() {
+                let rows = self.shared.adaptive.lock().clone();
+                self.export_status = match stats::write_adaptive_report("adaptive.csv", &rows) {
+                    Ok(()) => "wrote adaptive.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rows = self.shared.adaptive.lock().clone();
+        if rows.is_empty() {
+            ui.label(format!(
+                "Evolves organisms whose own genes set their offspring's mutation rate, one of {} \
+                 levels from 0% to {:.0}%.",
+                gametheory::MUTATION_RATES.len(),
+                gametheory::MUTATION_RATES[gametheory::MUTATION_RATES.len() - 1] * 100.0
+            ));
+            return;
+        }
+        let series = |value: fn(&AdaptiveRow) -> f64| -> PlotPoints {
+            rows.iter().enumerate().map(|(x, r)| [x as f64, value(r)]).collect()
+        };
+        Plot::new("Evolvability")
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(tr("Fraction"))
+            .legend(egui_plot::Legend::default())
+            .include_y(0.0)
+            .include_y(1.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.mutation_rate)).name(tr("Mean mutation rate")),
+                    0,
+                ));
+                plot_ui.line(
+                    self.plot_style
+                        .line(Line::new(series(|r| r.consensus)).name(tr("Consensus")), 1),
+                );
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.cooperation)).name(tr("Cooperation")),
+                    2,
+                ));
+            });
+    }
+
+    fn show_kin(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}: {:.2}", tr("Relatedness r"), self.relatedness));
+            ui.add(egui::widgets::Slider::new(&mut self.relatedness, 0.0..=1.0).show_value(false));
+            if ui.button(tr("Run kin selection")).clicked() {
+                let ctx = ui.ctx().clone();
+                let kin = self.shared.kin.clone();
+                let (setup, relatedness) = (self.settings.setup(), self.relatedness);
+                std::thread::spawn(move || {
+                    *kin.lock() = experiments::kin_selection(&setup, relatedness);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export kin selection")).clicked() {
+                let rows = self.shared.kin.lock().clone();
+                self.export_status = match stats::write_kin_report("kin.csv", &rows) {
+                    Ok(()) => "wrote kin.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rows = self.shared.kin.lock().clone();
+        if rows.is_empty() {
+            ui.label(
+                "Evolves a population playing itself, where fitness includes a fraction r of \
+                 the payoff of opponents sharing a founder.",
+            );
+            return;
+        }
+        if let Some(last) = rows.last() {
+            ui.label(format!(
+                "{}: {}",
+                tr("Founder lineages left"),
+                last.lineages
+            ));
+        }
+        let series = |value: fn(&KinRow) -> f64| -> PlotPoints {
+            rows.iter().enumerate().map(|(x, r)| [x as f64, value(r)]).collect()
+        };
+        Plot::new("Kin")
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(tr("Fraction"))
+            .legend(egui_plot::Legend::default())
+            .include_y(0.0)
+            .include_y(1.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.kin_pairs)).name(tr("Kin pairings")),
+                    0,
+                ));
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.cooperation)).name(tr("Cooperation")),
+                    1,
+                ));
+            });
+    }
+
+    fn show_loners(&mut self, ui: &mut egui::Ui) {
+        if ui.button(tr("Run loner dynamics")).clicked() {
+            *self.shared.loners.lock() = experiments::loner_dynamics(self.settings.gen_count * 10);
+        }
+        let rows = self.shared.loners.lock().clone();
+        if rows.is_empty() {
+            ui.label(
+                "Replicator dynamics of cooperators, defectors and loners in the optional \
+                 public goods game, which chase each other in a cycle.",
+            );
+            return;
+        }
+        Plot::new("Loners")
+            .x_axis_label(tr("Steps"))
+            .y_axis_label(tr("Share"))
+            .legend(egui_plot::Legend::default())
+            .include_y(0.0)
+            .include_y(1.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                for (k, name) in experiments::LONER_TYPES.iter().enumerate() {
+                    let points: PlotPoints =
+                        rows.iter().enumerate().map(|(x, r)| [x as f64, r[k]]).collect();
+                    plot_ui.line(self.plot_style.line(Line::new(points).name(*name), k));
+                }
+            });
+    }
+
+    fn show_punishment(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run punishment")).clicked() {
+                let ctx = ui.ctx().clone();
+                let punishment = self.shared.punishment.clone();
+                let setup = self.settings.setup();
+                std::thread::spawn(move || {
+                    *punishment.lock() = experiments::punishment(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export punishment")).clicked() {
+                let rows = self.shared.punishment.lock().clone();
+                self.export_status = match stats::write_punish_report("punishment.csv", &rows) {
+                    Ok(()) => "wrote punishment.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rows = self.shared.punishment.lock().clone();
+        if rows.is_empty() {
+            ui.label(format!(
+                "Evolves organisms with a gene to pay {} to fine a defector {} after each round.",
+                gametheory::PUNISH_COST,
+                gametheory::PUNISH_FINE
+            ));
+            return;
+        }
+        let series = |value: fn(&PunishRow) -> f64| -> PlotPoints {
+            rows.iter().enumerate().map(|(x, r)| [x as f64, value(r)]).collect()
+        };
+        Plot::new("Punishment")
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(tr("Fraction"))
+            .legend(egui_plot::Legend::default())
+            .include_y(0.0)
+            .include_y(1.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                plot_ui.line(
+                    self.plot_style
+                        .line(Line::new(series(|r| r.punishers)).name(tr("Punishers")), 0),
+                );
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.cooperation)).name(tr("Cooperation")),
+                    1,
+                ));
+            });
+    }
+
+    fn show_contracts(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run contracts")).clicked() {
+                let ctx = ui.ctx().clone();
+                let contracts = self.shared.contracts.clone();
+                let setup = self.settings.setup();
+                std::thread::spawn(move || {
+                    *contracts.lock() = experiments::contracts(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export contracts")).clicked() {
+                let rows = self.shared.contracts.lock().clone();
+                self.export_status = match stats::write_contract_report("contracts.csv", &rows) {
+                    Ok(()) => "wrote contracts.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rows = self.shared.contracts.lock().clone();
+        if rows.is_empty() {
+            ui.label(format!(
+                "Evolves organisms with a gene to sign contracts binding both sides to cooperate \
+                 for {} rounds, at a penalty of {} per defection.",
+                gametheory::CONTRACT_ROUNDS,
+                gametheory::CONTRACT_PENALTY
+            ));
+            return;
+        }
+        let series = |value: fn(&ContractRow) -> f64| -> PlotPoints {
+            rows.iter().enumerate().map(|(x, r)| [x as f64, value(r)]).collect()
+        };
+        Plot::new("Contracts")
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(tr("Fraction"))
+            .legend(egui_plot::Legend::default())
+            .include_y(0.0)
+            .include_y(1.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                plot_ui.line(
+                    self.plot_style.line(Line::new(series(|r| r.signers)).name(tr("Signers")), 0),
+                );
+                plot_ui.line(
+                    self.plot_style.line(Line::new(series(|r| r.breaches)).name(tr("Breaches")), 1),
+                );
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.cooperation)).name(tr("Cooperation")),
+                    2,
+                ));
+            });
+    }
+
+    fn show_talk(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr("Run cheap talk")).clicked() {
+                let ctx = ui.ctx().clone();
+                let talk = self.shared.talk.clone();
+                let setup = self.settings.setup();
+                std::thread::spawn(move || {
+                    *talk.lock() = experiments::cheap_talk(&setup);
+                    ctx.request_repaint();
+                });
+            }
+            if ui.button(tr("Export cheap talk")).clicked() {
+                let rows = self.shared.talk.lock().clone();
+                self.export_status = match stats::write_talk_report("talk.csv", &rows) {
+                    Ok(()) => "wrote talk.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+            }
+        });
+        ui.label(&self.export_status);
+
+        let rows = self.shared.talk.lock().clone();
+        if rows.is_empty() {
+            ui.label(
+                "Evolves organisms that announce a non-binding move before every match and open \
+                 in reply to the other's announcement.",
+            );
+            return;
+        }
+        let series = |value: fn(&TalkRow) -> f64| -> PlotPoints {
+            rows.iter().enumerate().map(|(x, r)| [x as f64, value(r)]).collect()
+        };
+        Plot::new("Cheap talk")
+            .x_axis_label(tr("Tournaments"))
+            .y_axis_label(tr("Fraction"))
+            .legend(egui_plot::Legend::default())
+            .include_y(0.0)
+            .include_y(1.0)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .show(ui, |plot_ui| {
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.cooperative_signals)).name(tr("Signal C")),
+                    0,
+                ));
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.trusting)).name(tr("Trust a C signal")),
+                    1,
+                ));
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.responsive)).name(tr("Signal-responsive")),
+                    2,
+                ));
+                plot_ui.line(self.plot_style.line(
+                    Line::new(series(|r| r.cooperation)).name(tr("Cooperation")),
+                    3,
+                ));
+            });
+    }
+
+    fn show_history(&mut self, ui: &mut egui::Ui) {
+        let history = self.shared.history.lock().clone();
+        if history.is_empty() {
+            ui.label(tr(
+                "Every finished simulation is listed here with its configuration.",
+            ));
+            return;
+        }
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("history").striped(true).show(ui, |ui| {
+                for header in ["Finished", "Config", "Generations", "MVP", "Score", "", ""] {
+                    ui.strong(tr(header));
+                }
+                ui.end_row();
+                for record in history.iter().rev() {
+                    ui.label(record.time());
+                    ui.label(record.config_hash()).on_hover_text(&record.config);
+                    ui.label(record.generations.to_string());
+                    match record.mvp {
+                        Some((score, genome)) => {
+                            genome_link(ui, genome, &mut self.inspected);
+                            ui.label(score.to_string());
+                        }
+                        None => {
+                            ui.label("-");
+                            ui.label("-");
+                        }
+                    }
+                    if ui.button(tr("Reload config")).clicked() {
+                        self.export_status = match Settings::decode(&record.config) {
+                            Ok(settings) => {
+                                self.settings = settings;
+                                format!("reloaded config {}", record.config_hash())
+                            }
+                            Err(err) => {
+                                format!("cannot read config {}: {err}", record.config_hash())
+                            }
+                        };
+                    }
+                    if ui.button(tr("Open results")).clicked() {
+                        self.export_status = match history::open(&record.results) {
+                            Ok(()) => format!("opened {}", record.results),
+                            Err(err) => format!("cannot open {}: {err}", record.results),
+                        };
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn show_hall_of_fame(&mut self, ui: &mut egui::Ui) {
+        let history = self.shared.history.lock().clone();
+        let ranked = history::hall_of_fame(&history);
+        if ranked.is_empty() {
+            ui.label(tr(
+                "The best genome of every finished simulation is ranked here.",
+            ));
+            return;
+        }
+        egui::ScrollArea::vertical().id_source("hall of fame").show(ui, |ui| {
+            egui::Grid::new("hall of fame").striped(true).show(ui, |ui| {
+                for header in ["Genome", "Genes", "Score", "Seed", "Config", "Finished"] {
+                    ui.strong(tr(header));
+                }
+                ui.end_row();
+                for record in ranked {
+                    let Some((score, genome)) = record.mvp else {
+                        continue;
+                    };
+                    genome_link(ui, genome, &mut self.inspected);
+                    ui.monospace(genome_label(&number_to_genome(genome)));
+                    ui.label(score.to_string());
+                    let seed = Settings::decode(&record.config).ok().and_then(|s| s.seed);
+                    ui.label(seed.map_or("-".to_string(), |s| s.to_string()));
+                    ui.label(record.config_hash()).on_hover_text(&record.config);
+                    ui.label(record.time());
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    /// The genome inspector: the decision table of `genome` and its record so far.
+    fn show_genome(&mut self, ui: &mut egui::Ui, genome: u8) {
+        let genes = genome_label(&number_to_genome(genome));
+        egui::Grid::new("decision table").striped(true).show(ui, |ui| {
+            for (context, gene) in stats::GENE_CONTEXTS.iter().zip(genes.chars()) {
+                ui.label(tr(context));
+                ui.strong(tr(if gene == 'C' { "Cooperate" } else { "Defect" }));
+                ui.end_row();
+            }
+        });
+        ui.separator();
+
+        let frequencies = self.shared.run.recorder.lock().unwrap().frequencies();
+        let counts: Vec<u32> = frequencies.iter().map(|c| c[genome as usize]).collect();
+        if let (Some(&latest), Some(population)) = (counts.last(), frequencies.last()) {
+            ui.label(format!(
+                "{}: {latest} / {}",
+                tr("Individuals in the latest generation"),
+                population.iter().sum::<u32>()
+            ));
+            let present = counts.iter().filter(|&&c| c > 0).count();
+            ui.label(format!(
+                "{}: {present} / {}",
+                tr("Generations present"),
+                counts.len()
+            ));
+            if let Some((generation, peak)) = counts.iter().enumerate().max_by_key(|&(_, c)| c) {
+                ui.label(format!(
+                    "{}: {peak} ({} {generation})",
+                    tr("Most individuals"),
+                    tr("generation")
+                ));
+            }
+        }
+
+        let name = genome.to_string();
+        let (total, rounds) = self
+            .shared
+            .run
+            .stats
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.name == name)
+            .fold((0, 0), |(total, rounds), s| {
+                (total + s.total, rounds + s.games as u32 * s.rounds)
+            });
+        if rounds > 0 {
+            ui.label(format!(
+                "{}: {:.3}",
+                tr("Score per round in the latest tournament"),
+                total as f64 / rounds as f64
+            ));
+        }
+
+        let wins: Vec<i32> = self
+            .shared
+            .history
+            .lock()
+            .iter()
+            .filter_map(|r| r.mvp)
+            .filter(|&(_, g)| g == genome)
+            .map(|(score, _)| score)
+            .collect();
+        ui.label(format!("{}: {}", tr("Runs won"), wins.len()));
+        if let Some(best) = wins.iter().max() {
+            ui.label(format!("{}: {best}", tr("Best winning score")));
+        }
+
+        if let Some(&stability) = self.shared.run.stability.lock().unwrap().get(genome as usize) {
+            ui.label(tr(match stability {
+                Stability::Strict => "Evolutionarily stable",
+                Stability::Neutral => "Neutrally stable",
+                Stability::Unstable => "Can be invaded",
+            }));
+        }
+        let strategy = MemoryOne::from_genome(genome);
+        let payoffs = analysis::payoffs_of(PayoffMatrix::new(self.settings.payoffs));
+        let res = analysis::analyse_pair(&strategy, &strategy, &payoffs);
+        ui.label(format!(
+            "{}: {:.3}, {} {:.0}%",
+            tr("Against itself, per round"),
+            res.payoffs.0,
+            tr("cooperating"),
+            res.cooperation.0 * 100.0
+        ));
+    }
+
+    fn show_lineage(&mut self, ui: &mut egui::Ui) {
+        let lineage = self.shared.run.lineage.lock().unwrap().clone();
+        let Some(champion) = lineage.champion else {
+            ui.label(tr(
+                "The ancestry of the winning genome is traced here when the genetic algorithm breeds the population.",
+            ));
+            return;
+        };
+        ui.label(format!("{}: {}", tr("Individuals born"), lineage.len()));
+        egui::ScrollArea::vertical().id_source("lineage").show(ui, |ui| {
+            show_ancestor(ui, &lineage, champion, 0, &mut self.inspected);
+        });
+    }
+
+    fn show_play(&mut self, ui: &mut egui::Ui) {
+        let names = strategy_names();
+        ui.horizontal(|ui| {
+            let selected = match self.rival {
+                Rival::Named(s) => names[s].replace('\n', " "),
+                Rival::Genome(g) => format!("{} {g}", tr("Genome")),
+            };
+            egui::ComboBox::from_label(tr("Opponent")).selected_text(selected).show_ui(ui, |ui| {
+                for (s, name) in names.iter().enumerate() {
+                    ui.selectable_value(&mut self.rival, Rival::Named(s), name.replace('\n', " "));
+                }
+                // The winner of the latest run is the natural evolved opponent.
+                let genome = match self.rival {
+                    Rival::Genome(g) => g,
+                    Rival::Named(_) => self.shared.run.mvp.lock().unwrap().unwrap_or(0),
+                };
+                ui.selectable_value(&mut self.rival, Rival::Genome(genome), tr("Evolved genome"));
+            });
+            if let Rival::Genome(g) = &mut self.rival {
+                let genome = egui::DragValue::new(g).clamp_range(0..=GENOME_COUNT - 1);
+                ui.add(genome).on_hover_text(describe_genome(*g));
+            }
+            if ui.button(tr("New match")).clicked() {
+                let (payoffs, loner) = (self.settings.payoffs, self.settings.loner_payoff);
+                self.human_match = match self.rival {
+                    Rival::Named(s) => InteractiveMatch::against_named(s, payoffs, loner),
+                    Rival::Genome(g) => Some(InteractiveMatch::against_genome(g, payoffs, loner)),
+                };
+            }
+        });
+        let Some(game) = &mut self.human_match else {
+            ui.label(tr(
+                "Choose an opponent and start a new match, then pick your move every round.",
+            ));
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            for (label, decision) in [
+                ("Cooperate", Decision::Cooperate),
+                ("Defect", Decision::Defect),
+            ] {
+                if ui.button(tr(label)).clicked() {
+                    game.play(decision);
+                }
+            }
+        });
+        ui.label(format!(
+            "{}: {}   {}: {}   {}: {}",
+            tr("Rounds"),
+            game.rounds.len(),
+            tr("You"),
+            game.scores.0,
+            tr("Opponent"),
+            game.scores.1
+        ));
+        let letter = |m: Option<Decision>| match m {
+            Some(Decision::Cooperate) => RichText::new("C").color(Color32::LIGHT_GREEN),
+            Some(Decision::Defect) => RichText::new("D").color(Color32::LIGHT_RED),
+            // The opponent sat the round out.
+            None => RichText::new("-"),
+        };
+        egui::ScrollArea::horizontal().stick_to_right(true).show(ui, |ui| {
+            egui::Grid::new("moves").show(ui, |ui| {
+                ui.strong(tr("You"));
+                for &(own, _) in &game.rounds {
+                    ui.label(letter(Some(own)).monospace());
+                }
+                ui.end_row();
+                ui.strong(tr("Opponent"));
+                for &(_, theirs) in &game.rounds {
+                    ui.label(letter(theirs).monospace());
+                }
+                ui.end_row();
+            });
+        });
+    }
+
+    fn show_right(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.view, View::Evolution, tr("Evolution"));
+            ui.selectable_value(&mut self.view, View::Leaderboard, tr("Leaderboard"));
+            ui.selectable_value(&mut self.view, View::Ratings, tr("Ratings"));
+            ui.selectable_value(&mut self.view, View::Behaviour, tr("Behaviour"));
+            ui.selectable_value(&mut self.view, View::Markov, tr("Markov"));
+            ui.selectable_value(&mut self.view, View::Population, tr("Population"));
+            ui.selectable_value(&mut self.view, View::Sensitivity, tr("Sensitivity"));
+            ui.selectable_value(&mut self.view, View::Noise, tr("Noise"));
+            ui.selectable_value(&mut self.view, View::Forgetting, tr("Forgetting"));
+            ui.selectable_value(&mut self.view, View::Genes, tr("Genes"));
+            ui.selectable_value(&mut self.view, View::Dominance, tr("Dominance"));
+            ui.selectable_value(&mut self.view, View::Heatmap, tr("Heatmap"));
+            ui.selectable_value(&mut self.view, View::Meta, tr("Games"));
+            ui.selectable_value(&mut self.view, View::Tags, tr("Tags"));
+            ui.selectable_value(&mut self.view, View::Kin, tr("Kin"));
+            ui.selectable_value(&mut self.view, View::Loners, tr("Loners"));
+            ui.selectable_value(&mut self.view, View::Punishment, tr("Punishment"));
+            ui.selectable_value(&mut self.view, View::Contracts, tr("Contracts"));
+            ui.selectable_value(&mut self.view, View::Talk, tr("Cheap talk"));
+            ui.selectable_value(&mut self.view, View::RoundRobin, tr("Round robin"));
+            ui.selectable_value(&mut self.view, View::Bracket, tr("Bracket"));
+            ui.selectable_value(&mut self.view, View::Environments, tr("Environments"));
+            ui.selectable_value(&mut self.view, View::Variants, tr("Variants"));
+            ui.selectable_value(&mut self.view, View::Openings, tr("Openings"));
+            ui.selectable_value(&mut self.view, View::Memory, tr("Memory depth"));
+            ui.selectable_value(&mut self.view, View::Adaptive, tr("Evolvability"));
+            ui.selectable_value(&mut self.view, View::History, tr("History"));
+            ui.selectable_value(&mut self.view, View::HallOfFame, tr("Hall of fame"));
+            ui.selectable_value(&mut self.view, View::Lineage, tr("Lineage"));
+            ui.selectable_value(&mut self.view, View::Play, tr("Play"));
+        });
+        match self.view {
+            View::Evolution => show_columns(ui, 0.75, |lui, rui| {
+                self.show_plot(lui);
+                self.show_standings(rui);
+            }),
+            View::Leaderboard => self.show_leaderboard(ui),
+            View::Ratings => self.show_ratings(ui),
+            View::Behaviour => self.show_behaviour(ui),
+            View::Markov => self.show_markov(ui),
+            View::Population => self.show_population(ui),
+            View::Sensitivity => self.show_sensitivity(ui),
+            View::Noise => self.show_noise(ui),
+            View::Forgetting => self.show_forgetting(ui),
+            View::Genes => self.show_genes(ui),
+            View::Dominance => self.show_dominance(ui),
+            View::Heatmap => self.show_heatmap(ui),
+            View::Meta => self.show_meta(ui),
+            View::Tags => self.show_tags(ui),
+            View::Kin => self.show_kin(ui),
+            View::Loners => self.show_loners(ui),
+            View::Punishment => self.show_punishment(ui),
+            View::Contracts => self.show_contracts(ui),
+            View::Talk => self.show_talk(ui),
+            View::RoundRobin => self.show_round_robin(ui),
+            View::Bracket => self.show_bracket(ui),
+            View::Environments => self.show_environments(ui),
+            View::Variants => self.show_variants(ui),
+            View::Openings => self.show_openings(ui),
+            View::Memory => self.show_memory_depth(ui),
+            View::Adaptive => self.show_adaptive(ui),
+            View::History => self.show_history(ui),
+            View::HallOfFame => self.show_hall_of_fame(ui),
+            View::Lineage => self.show_lineage(ui),
+            View::Play => self.show_play(ui),
+        }
+    }
+
+    fn show_left(&mut self, ui: &mut egui::Ui) {
+        let mut language = i18n::language();
+        egui::ComboBox::from_label(tr("Language")).selected_text(language.name()).show_ui(
+            ui,
+            |ui| {
+                for l in Language::ALL {
+                    ui.selectable_value(&mut language, l, l.name());
+                }
+            },
+        );
+        i18n::set_language(language);
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label(tr("Palette"))
+                .selected_text(tr(self.plot_style.palette.label()))
+                .show_ui(ui, |ui| {
+                    for p in Palette::ALL {
+                        ui.selectable_value(&mut self.plot_style.palette, p, tr(p.label()));
+                    }
+                });
+            ui.checkbox(&mut self.plot_style.patterned, tr("Line patterns"));
+        });
+
+        if let Some((settings, schedule)) = self.pending_config.clone() {
+            ui.colored_label(Color32::YELLOW, tr("The config file changed."));
+            ui.horizontal(|ui| {
+                if ui.button(tr("Apply to next run")).clicked() {
+                    self.settings = settings;
+                    self.payoff_schedule = schedule;
+                    self.pending_config = None;
+                } else if ui.button(tr("Dismiss")).clicked() {
+                    self.pending_config = None;
+                }
+            });
+        }
+
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.import_path);
+            if ui.button(tr("Import config")).clicked() {
+                let path = self.import_path.trim();
+                let loaded = std::fs::read_to_string(path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|text| load_config(Path::new(path), &text));
+                self.export_status = match loaded {
+                    Ok((settings, schedule)) => {
+                        self.settings = settings;
+                        self.payoff_schedule = schedule;
+                        format!("loaded {path}")
+                    }
+                    Err(err) => format!("cannot read {path}: {err}"),
+                };
+            }
+        });
+
+        ui.menu_button(tr("Scenarios"), |ui| {
+            for scenario in Scenario::ALL {
+                if ui.button(tr(scenario.label())).clicked() {
+                    self.reset_game();
+                    scenario.load(self);
+                    ui.close_menu();
+                }
+            }
+        });
+
+        ui.label(
+            RichText::new(format!(
+                "{}: {}",
+                tr("#Generations"),
+                self.settings.gen_count
+            ))
+            .size(14.0),
+        );
+        ui.add(
+            egui::widgets::Slider::new(&mut self.settings.gen_count, 100..=300).show_value(false),
+        );
+
+        ui.label(
+            RichText::new(format!(
+                "{}: {}",
+                tr("#Games Per Gen"),
+                self.settings.game_count
+            ))
+            .size(14.0),
+        );
+        ui.add(
+            egui::widgets::Slider::new(&mut self.settings.game_count, 10..=100).show_value(false),
+        );
+
+        let genetic = &mut self.settings.genetic;
+        ui.label(
+            RichText::new(format!(
+                "{}: {}",
+                tr("#Population"),
+                genetic.population_size
+            ))
+            .size(14.0),
+        );
+        ui.add(egui::widgets::Slider::new(&mut genetic.population_size, 4..=64).show_value(false));
+        ui.label(
+            RichText::new(format!(
+                "{}: {}",
+                tr("#Parents Per Gen"),
+                genetic.generation_size
+            ))
+            .size(14.0),
+        );
+        ui.add(
+            egui::widgets::Slider::new(&mut genetic.generation_size, 1..=genetic.population_size)
+                .show_value(false),
+        );
+        genetic.generation_size = genetic.generation_size.min(genetic.population_size);
+        let mut steady_state = genetic.steady_state.is_some();
+        if ui.checkbox(&mut steady_state, tr("Steady state")).changed() {
+            genetic.steady_state = steady_state.then_some(2);
+        }
+        match &mut genetic.steady_state {
+            Some(replaced) => {
+                ui.label(
+                    RichText::new(format!("{}: {replaced}", tr("#Replaced Per Gen"))).size(14.0),
+                );
+                ui.add(
+                    egui::widgets::Slider::new(replaced, 1..=genetic.population_size)
+                        .show_value(false),
+                );
+            }
+            None => {
+                ui.label(
+                    RichText::new(format!("{}: {}", tr("Elitism"), genetic.elitism)).size(14.0),
+                );
+                ui.add(
+                    egui::widgets::Slider::new(&mut genetic.elitism, 0..=genetic.population_size)
+                        .show_value(false),
+                );
+            }
+        }
+        genetic.elitism = genetic.elitism.min(genetic.population_size);
+        egui::ComboBox::from_label(tr("Selection"))
+            .selected_text(tr(genetic.selection.label()))
+            .show_ui(ui, |ui| {
+                for selection in [
+                    Selection::Truncation,
+                    Selection::RouletteWheel,
+                    Selection::Rank,
+                    Selection::Tournament(3),
+                ] {
+                    ui.selectable_value(&mut genetic.selection, selection, tr(selection.label()));
+                }
+            });
+        if let Selection::Tournament(k) = &mut genetic.selection {
+            ui.label(RichText::new(format!("{}: {k}", tr("Tournament size"))).size(14.0));
+            ui.add(egui::widgets::Slider::new(k, 2..=8).show_value(false));
+        }
+        egui::ComboBox::from_label(tr("Crossover"))
+            .selected_text(tr(genetic.crossover.label()))
+            .show_ui(ui, |ui| {
+                for crossover in Crossover::ALL {
+                    ui.selectable_value(&mut genetic.crossover, crossover, tr(crossover.label()));
+                }
+            });
+        ui.label(
+            RichText::new(format!(
+                "{}: {:.0}%",
+                tr("Mutation rate"),
+                genetic.mutation_rate * 100.0
+            ))
+            .size(14.0),
+        );
+        ui.add(egui::widgets::Slider::new(&mut genetic.mutation_rate, 0.0..=0.5).show_value(false));
+        ui.label(RichText::new(format!("{}: {}", tr("Memory depth"), genetic.depth)).size(14.0));
+        ui.add(egui::widgets::Slider::new(&mut genetic.depth, 1..=MAX_DEPTH).show_value(false));
+        ui.label(
+            RichText::new(format!(
+                "{}: {}",
+                tr("Past champions"),
+                self.settings.champions
+            ))
+            .size(14.0),
+        );
+        ui.add(
+            egui::widgets::Slider::new(&mut self.settings.champions, 0..=MAX_CHAMPIONS)
+                .show_value(false),
+        );
+
+        ui.radio_value(
+            &mut self.settings.update_rule,
+            UpdateRule::Genetic,
+            tr("Genetic algorithm"),
+        );
+        if ui
+            .radio(
+                matches!(self.settings.update_rule, UpdateRule::BestResponse(_)),
+                tr("Best response"),
+            )
+            .clicked()
+        {
+            self.settings.update_rule = UpdateRule::BestResponse(0.1);
+        }
+        if let UpdateRule::BestResponse(fraction) = &mut self.settings.update_rule {
+            ui.label(
+                RichText::new(format!("{}: {:.0}%", tr("Switching"), *fraction * 100.0)).size(14.0),
+            );
+            ui.add(egui::widgets::Slider::new(fraction, 0.05..=1.0).show_value(false));
+        }
+        ui.radio_value(
+            &mut self.settings.update_rule,
+            UpdateRule::Demographic,
+            tr("Wealth-based birth and death"),
+        );
+        if ui
+            .radio(
+                matches!(self.settings.update_rule, UpdateRule::SocialLearning(_)),
+                tr("Social learning"),
+            )
+            .clicked()
+        {
+            self.settings.update_rule = UpdateRule::SocialLearning(0.2);
+        }
+        if let UpdateRule::SocialLearning(observation) = &mut self.settings.update_rule {
+            ui.label(
+                RichText::new(format!("{}: {:.0}%", tr("Observing"), *observation * 100.0))
+                    .size(14.0),
+            );
+            ui.add(egui::widgets::Slider::new(observation, 0.05..=1.0).show_value(false));
+        }
+
+        egui::ComboBox::from_label(tr("Scores"))
+            .selected_text(tr(self.normalization.label()))
+            .show_ui(ui, |ui| {
+                for n in Normalization::ALL {
+                    ui.selectable_value(&mut self.normalization, n, tr(n.label()));
+                }
+            });
+
+        let mut fixed_seed = self.settings.seed.is_some();
+        if ui.checkbox(&mut fixed_seed, tr("Fixed seed")).changed() {
+            self.settings.seed = fixed_seed.then_some(self.last_seed.unwrap_or_default());
+        }
+        if let Some(seed) = &mut self.settings.seed {
+            ui.add(egui::DragValue::new(seed));
+        }
+        if let Some(seed) = self.last_seed {
+            ui.label(format!("{}: {seed}", tr("Seed of the last run")));
+        }
+
+        let mut early_stop = self.settings.early_stop.is_some();
+        if ui.checkbox(&mut early_stop, tr("Stop when converged")).changed() {
+            self.settings.early_stop = early_stop.then_some(20);
+        }
+        if let Some(k) = &mut self.settings.early_stop {
+            ui.label(RichText::new(format!("{}: {k}", tr("Plateau length"))).size(14.0));
+            ui.add(egui::widgets::Slider::new(k, 5..=50).show_value(false));
+        }
+
+        ui.label(
+            RichText::new(format!(
+                "{}: {}",
+                tr("Memory cost"),
+                self.settings.memory_cost
+            ))
+            .size(14.0),
+        );
+        ui.add(egui::widgets::Slider::new(&mut self.settings.memory_cost, 0..=2).show_value(false));
+
+        let mut budgeted = self.settings.cooperation_cost.is_some();
+        if ui.checkbox(&mut budgeted, tr("Resource budget")).changed() {
+            self.settings.cooperation_cost = budgeted.then_some(1);
+        }
+        if let Some(cost) = &mut self.settings.cooperation_cost {
+            ui.label(RichText::new(format!("{}: {cost}", tr("Cooperation cost"))).size(14.0));
+            ui.add(egui::widgets::Slider::new(cost, 1..=3).show_value(false));
+        }
+
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.settings.pairing, Pairing::All, tr("All players"));
+            if ui
+                .radio(
+                    matches!(self.settings.pairing, Pairing::Random(_)),
+                    tr("Random"),
+                )
+                .clicked()
+            {
+                self.settings.pairing = Pairing::Random(5);
+            }
+            if ui
+                .radio(
+                    matches!(self.settings.pairing, Pairing::RotatingPool(_)),
+                    tr("Rotating pool"),
+                )
+                .clicked()
+            {
+                self.settings.pairing = Pairing::RotatingPool(8);
+            }
+        });
+        match &mut self.settings.pairing {
+            Pairing::Random(k) => {
+                ui.label(
+                    RichText::new(format!("{}: {k}", tr("Partners per individual"))).size(14.0),
+                );
+                ui.add(egui::widgets::Slider::new(k, 1..=16).show_value(false));
+            }
+            Pairing::RotatingPool(m) => {
+                ui.label(RichText::new(format!("{}: {m}", tr("Players sampled"))).size(14.0));
+                ui.add(egui::widgets::Slider::new(m, 1..=16).show_value(false));
+            }
+            Pairing::All => {}
+        }
+
+        let mut mortal = self.settings.lifespan.is_some();
+        if ui.checkbox(&mut mortal, tr("Limit lifespan")).changed() {
+            self.settings.lifespan = mortal.then_some(5);
+        }
+        if let Some(lifespan) = &mut self.settings.lifespan {
+            ui.label(RichText::new(format!("{}: {lifespan}", tr("Lifespan"))).size(14.0));
+            ui.add(egui::widgets::Slider::new(lifespan, 1..=20).show_value(false));
+        }
+
+        ui.label(
+            RichText::new(format!(
+                "{}: {:.0}%",
+                tr("Execution noise"),
+                self.settings.noise * 100.0
+            ))
+            .size(14.0),
+        );
+        ui.add(egui::widgets::Slider::new(&mut self.settings.noise, 0.0..=0.2).show_value(false));
+        egui::ComboBox::from_label(tr("Noise schedule"))
+            .selected_text(tr(self.settings.noise_schedule.label()))
+            .show_ui(ui, |ui| {
+                for schedule in [
+                    NoiseSchedule::Constant,
+                    NoiseSchedule::Decreasing,
+                    NoiseSchedule::Oscillating(20),
+                    NoiseSchedule::Withdrawn(0.5),
+                ] {
+                    ui.selectable_value(
+                        &mut self.settings.noise_schedule,
+                        schedule,
+                        tr(schedule.label()),
+                    );
+                }
+            });
+        match &mut self.settings.noise_schedule {
+            NoiseSchedule::Oscillating(period) => {
+                ui.label(RichText::new(format!("{}: {period}", tr("Period"))).size(14.0));
+                ui.add(egui::widgets::Slider::new(period, 5..=100).show_value(false));
+            }
+            NoiseSchedule::Withdrawn(fraction) => {
+                ui.label(
+                    RichText::new(format!("{}: {:.0}%", tr("Noisy for"), *fraction * 100.0))
+                        .size(14.0),
+                );
+                ui.add(egui::widgets::Slider::new(fraction, 0.1..=0.9).show_value(false));
+            }
+            _ => {}
+        }
+
+        ui.label(
+            RichText::new(format!(
+                "{}: {:.0}%",
+                tr("Perception noise"),
+                self.settings.perception_noise * 100.0
+            ))
+            .size(14.0),
+        );
+        ui.add(
+            egui::widgets::Slider::new(&mut self.settings.perception_noise, 0.0..=0.2)
+                .show_value(false),
+        );
+
+        ui.label(
+            RichText::new(format!(
+                "Forgetting: {:.0}%",
+                self.settings.forgetting * 100.0
+            ))
+            .size(14.0),
+        );
+        ui.add(
+            egui::widgets::Slider::new(&mut self.settings.forgetting, 0.0..=0.5).show_value(false),
+        );
+
+        let mut seasonal = self.settings.seasons.is_some();
+        if ui.checkbox(&mut seasonal, tr("Seasons")).changed() {
+            self.settings.seasons = seasonal.then_some((20, 1));
+        }
+        if let Some((length, preset)) = &mut self.settings.seasons {
+            ui.label(RichText::new(format!("{}: {length}", tr("Season length"))).size(14.0));
+            ui.add(egui::widgets::Slider::new(length, 5..=50).show_value(false));
+            egui::ComboBox::from_label(tr("Alternate with"))
+                .selected_text(GAME_PRESETS[*preset].0)
+                .show_ui(ui, |ui| {
+                    for (p, (name, _)) in GAME_PRESETS.iter().enumerate().skip(1) {
+                        ui.selectable_value(preset, p, *name);
+                    }
+                });
+        }
+
+        egui::ComboBox::from_label(tr("Opponents"))
+            .selected_text(tr(self.settings.mixing.label()))
+            .show_ui(ui, |ui| {
+                for mixing in Mixing::ALL {
+                    ui.selectable_value(&mut self.settings.mixing, mixing, tr(mixing.label()));
+                }
+            });
+        ui.checkbox(&mut self.settings.reputation, tr("Indirect reciprocity"));
+        ui.checkbox(&mut self.settings.ostracism, tr("Partner choice"));
+
+        ui.label(
+            RichText::new(format!(
+                "{}: {}",
+                tr("Loner payoff"),
+                self.settings.loner_payoff
+            ))
+            .size(14.0),
+        );
+        ui.add(
+            egui::widgets::Slider::new(&mut self.settings.loner_payoff, -3..=0).show_value(false),
+        );
+
+        ui.collapsing(tr("Fixed players"), |ui| {
+            for (name, enabled) in strategy_names().into_iter().zip(&mut self.settings.strategies) {
+                ui.checkbox(enabled, name.replace('\n', " "));
+            }
+        });
+
+        ui.collapsing(tr("Custom strategies"), |ui| {
+            ui.label(tr(
+                "Cooperate at the start and after each outcome, own move first:",
+            ));
+            let (name, genes) = &mut self.custom_draft;
+            ui.horizontal(|ui| {
+                for (gene, label) in genes.iter_mut().zip(["Start", "CC", "CD", "DC", "DD"]) {
+                    ui.checkbox(gene, label);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(name);
+                if ui.add_enabled(!name.trim().is_empty(), egui::Button::new(tr("Add"))).clicked() {
+                    let genome = genome_to_number(&genes.to_vec().into_boxed_slice());
+                    self.custom_players.push((name.trim().to_string(), genome));
+                    name.clear();
+                }
+            });
+            let mut removed = None;
+            for (k, (name, genome)) in self.custom_players.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{name} ({genome})"));
+                    if ui.small_button(tr("Remove")).clicked() {
+                        removed = Some(k);
+                    }
+                });
+            }
+            if let Some(k) = removed {
+                self.custom_players.remove(k);
+            }
+        });
+
+        ui.collapsing(tr("Payoffs"), |ui| {
+            ui.label(tr("Own payoff after each outcome, own move first:"));
+            let mut values = self.settings.payoffs.map(|(own, _)| own);
+            ui.horizontal(|ui| {
+                for (value, label) in values.iter_mut().zip(["CC", "CD", "DC", "DD"]) {
+                    ui.label(label);
+                    ui.add(egui::DragValue::new(value).clamp_range(-20..=20));
+                }
+            });
+            let [r, s, t, p] = values;
+            self.settings.payoffs = PayoffMatrix::symmetric(r, s, t, p).outcomes();
+            if let Err(err) = PayoffMatrix::prisoners_dilemma(r, s, t, p) {
+                ui.colored_label(
+                    Color32::YELLOW,
+                    format!("{}: {err}", tr("Not a prisoner's dilemma")),
+                );
+            }
+            if ui.button(tr("Reset")).clicked() {
+                self.settings.payoffs = payoff_table(prisoners_dillemma_rules);
+            }
+        });
+
+        ui.collapsing(tr("Payoff schedule"), |ui| {
+            ui.label(tr("One change per line, e.g. `50 T=1` or `20 T+=-1`."));
+            ui.text_edit_multiline(&mut self.payoff_schedule);
+        });
+        let schedule = PayoffChange::parse_schedule(&self.payoff_schedule);
+        if let Err(err) = &schedule {
+            ui.colored_label(Color32::RED, err);
+        }
+        let checked = self.settings.check();
+        if let Err(err) = &checked {
+            ui.colored_label(Color32::RED, err);
+        }
+        let runnable = schedule.is_ok() && checked.is_ok();
+
+        if ui.add_enabled(runnable, egui::Button::new(tr("Simulate"))).clicked() {
+            let ctx = ui.ctx().clone();
+            let shared = self.shared.clone();
+            let (settings, _, mut control) = self.start_run();
+            let schedule = schedule.clone().unwrap_or_default();
+            let custom = self.custom_players.clone();
+            let animate = self.animate;
+            std::thread::spawn(move || {
+                let repaint = || ctx.request_repaint();
+                simulate(
+                    &shared.run,
+                    &mut control,
+                    settings,
+                    &schedule,
+                    &custom,
+                    repaint,
+                );
+                record_run(&shared, settings);
+                if animate {
+                    record_animation(&shared);
+                    ctx.request_repaint();
+                }
+            });
+        }
+        ui.checkbox(&mut self.animate, tr("Save an animation of the run"));
+
+        ui.horizontal(|ui| {
+            ui.label(tr("Runs"));
+            ui.add(egui::DragValue::new(&mut self.batch_runs).clamp_range(2..=1000));
+            let batch = egui::Button::new(tr("Simulate batch"));
+            if ui.add_enabled(runnable, batch).clicked() {
+                let ctx = ui.ctx().clone();
+                let shared = self.shared.clone();
+                let (settings, seed, mut control) = self.start_run();
+                let schedule = schedule.unwrap_or_default();
+                let custom = self.custom_players.clone();
+                let seeds = seed..seed.saturating_add(self.batch_runs);
+                std::thread::spawn(move || {
+                    let repaint = || ctx.request_repaint();
+                    let run = &shared.run;
+                    simulate_batch(
+                        run,
+                        &mut control,
+                        settings,
+                        seeds,
+                        &schedule,
+                        &custom,
+                        repaint,
+                    );
+                });
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let running = self.simulating.load(Ordering::Relaxed);
+            let (label, command) = if self.paused {
+                ("Resume", Control::Resume)
+            } else {
+                ("Pause", Control::Pause)
+            };
+            if ui.add_enabled(running, egui::Button::new(tr(label))).clicked() {
+                self.send(command);
+            }
+            let step = egui::Button::new(tr("Step one generation"));
+            if ui.add_enabled(running, step).clicked() {
+                self.send(Control::Step);
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let mut limited = self.speed.is_some();
+            let mut changed = ui.checkbox(&mut limited, tr("Limit speed")).changed();
+            if changed {
+                self.speed = limited.then_some(SLOW_SPEED);
+            }
+            if let Some(speed) = &mut self.speed {
+                let value = egui::DragValue::new(speed)
+                    .clamp_range(0.1..=100.0)
+                    .speed(0.1)
+                    .suffix(tr(" generations/s"));
+                changed |= ui.add(value).changed();
+            }
+            if changed {
+                self.send(Control::Delay(self.delay()));
+            }
+        });
+
+        if ui.button(tr("Reset")).clicked() {
+            self.reset_game();
+        }
+
+        if ui.button(tr("Export series")).clicked() {
+            let recorder = self.shared.run.recorder.lock().unwrap().clone();
+            self.export_status =
+                match stats::write_series("evolution.csv", &recorder, self.normalization) {
+                    Ok(()) => "wrote evolution.csv".to_string(),
+                    Err(err) => format!("export failed: {err}"),
+                };
+        }
+
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.snapshot_path);
+            if ui.button(tr("Save run")).clicked() {
+                self.export_status = match self.save_run() {
+                    Ok(()) => format!("wrote {}", self.snapshot_path),
+                    Err(err) => format!("cannot save {}: {err}", self.snapshot_path),
+                };
+            }
+            if ui.button(tr("Load run")).clicked() {
+                self.export_status = match self.load_run() {
+                    Ok(()) => format!("loaded {}", self.snapshot_path),
+                    Err(err) => format!("cannot load {}: {err}", self.snapshot_path),
+                };
+            }
+        });
+
+        ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+            ui.add(egui::Image::new(egui::include_image!("../felix.png")));
+        });
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui) {
+        show_columns(ui, 0.2, |lui, rui| {
+            self.show_left(lui);
+            self.show_right(rui);
+        });
+    }
+}
+
+/// Shows individual `id` of `lineage`, the `slot`-th parent of the one above it, as a node
+/// that opens onto its parents.
+fn show_ancestor(
+    ui: &mut egui::Ui,
+    lineage: &Lineage,
+    id: usize,
+    slot: usize,
+    inspected: &mut Option<u8>,
+) {
+    let Some(individual) = lineage.get(id) else {
+        return;
+    };
+    let mut text = format!(
+        "({}), {} {}",
+        genome_label(&number_to_genome(individual.genome)),
+        tr("born in generation"),
+        individual.born
+    );
+    let mutations = lineage.mutations(id).count_ones();
+    if mutations > 0 {
+        text.push_str(&format!(", {}: {mutations}", tr("mutated genes")));
+    }
+    let header = |ui: &mut egui::Ui, inspected: &mut Option<u8>| {
+        ui.label(tr("Genome"));
+        genome_link(ui, individual.genome, inspected);
+        ui.label(&text);
+    };
+    match individual.parents {
+        None => {
+            ui.horizontal(|ui| {
+                header(ui, inspected);
+                ui.label(tr("founder"));
+            });
+        }
+        // The same ancestor can appear on several paths, or as both parents.
+        Some((p1, p2)) => {
+            let state = ui.make_persistent_id((id, slot));
+            egui::collapsing_header::CollapsingState::load_with_default_open(
+                ui.ctx(),
+                state,
+                false,
+            )
+            .show_header(ui, |ui| header(ui, inspected))
+            .body(|ui| {
+                show_ancestor(ui, lineage, p1, 0, inspected);
+                show_ancestor(ui, lineage, p2, 1, inspected);
+            });
+        }
+    }
+}
+
+/// Shows `genome` as a link that opens it in the genome inspector, with its decision table
+/// on hover.
+fn genome_link(ui: &mut egui::Ui, genome: u8, inspected: &mut Option<u8>) {
+    if ui.link(genome.to_string()).on_hover_text(describe_genome(genome)).clicked() {
+        *inspected = Some(genome);
+    }
+}
+
+/// Writes the series of the run that just finished to its own file and adds the run to the
+/// history.
+fn record_run(shared: &Shared, settings: Settings) {
+    let recorder = shared.run.recorder.lock().unwrap().clone();
+    let best = recorder.best_scores().last().copied();
+    let mvp = best.zip(*shared.run.mvp.lock().unwrap());
+    let record = RunRecord::now(settings.encode(), recorder.generations.len(), mvp);
+    let written = history::append(&record)
+        .and_then(|()| stats::write_series(&record.results, &recorder, Normalization::Raw));
+    if let Err(err) = written {
+        let mut note = shared.note.lock();
+        note.push_str(&format!(" (could not record the run: {err})"));
+    }
+    shared.history.lock().push(record);
+}
+
+/// Writes the generations of the run that just finished as an animated PNG, noting the
+/// outcome after the status of the run.
+fn record_animation(shared: &Shared) {
+    let recorder = shared.run.recorder.lock().unwrap().clone();
+    let outcome = match animation::write_apng("evolution.apng", &recorder) {
+        Ok(()) => " (wrote evolution.apng)".to_string(),
+        Err(err) => format!(" (could not write evolution.apng: {err})"),
+    };
+    shared.note.lock().push_str(&outcome);
+}
+
+/// What the window says about a run in `status`.
+fn status_text(status: Status) -> String {
+    match status {
+        Status::Idle => String::new(),
+        Status::Running => tr("Running").to_string(),
+        Status::Paused(generation) => tr_fill("Paused before generation {}", &[&generation]),
+        Status::Extinct(generation) => tr_fill(
+            "Stopped at generation {}: the population went extinct",
+            &[&generation],
+        ),
+        Status::Plateaued {
+            generation,
+            generations,
+        } => tr_fill(
+            "Stopped early at generation {}: best score and diversity plateaued for {} generations",
+            &[&generation, &generations],
+        ),
+        Status::Completed(gen_count) => tr_fill("Completed all {} generations", &[&gen_count]),
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if self.config.is_some() {
+            self.poll_config(false);
+            ctx.request_repaint_after(config::POLL_INTERVAL);
+        }
+        if let Some(rect) = self.screenshot {
+            let screenshot = ctx.input(|i| {
+                i.events.iter().find_map(|e| match e {
+                    egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                    _ => None,
+                })
+            });
+            if let Some(image) = screenshot {
+                self.screenshot = None;
+                self.export_status =
+                    match chart::write_png("evolution.png", &image, rect, ctx.pixels_per_point()) {
+                        Ok(()) => "wrote evolution.svg and evolution.png".to_string(),
+                        Err(err) => format!("export failed: {err}"),
+                    };
+            }
+        }
+        egui::CentralPanel::default().show(ctx, |ui| self.show(ui));
+        if let Some(genome) = self.inspected {
+            let mut open = true;
+            egui::Window::new(format!("{} {genome}", tr("Genome")))
+                .open(&mut open)
+                .show(ctx, |ui| self.show_genome(ui, genome));
+            if !open {
+                self.inspected = None;
+            }
+        }
+    }
+}
+
+/// Options of the window, printed by `--help`.
+const USAGE: &str = "\
+usage: gametheory [--config PATH]
+
+Opens the window, reading and watching the config file if one is given. The commands that run
+without the window belong to gametheory-cli, see `gametheory-cli --help`.
+";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("--help" | "-h" | "help") => {
+            print!("{USAGE}");
+            Ok(())
+        }
+        Some("--config") => match args.next() {
+            Some(config) => open_window(Some(config)),
+            None => Err("--config expects a path".to_string()),
+        },
+        Some(other) => Err(format!("unknown option {other}, see --help")),
+        None => open_window(None),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
     }
 }
 
-fn main() -> Result<(), Error> {
+fn open_window(config: Option<String>) -> Result<(), String> {
     eframe::run_native(
         "Game Theory",
         eframe::NativeOptions {
@@ -211,7 +3562,8 @@ fn main() -> Result<(), Error> {
         },
         Box::new(move |cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
-            Box::new(App::new(cc))
+            Box::new(App::new(cc, config))
         }),
     )
+    .map_err(|err| err.to_string())
 }
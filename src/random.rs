@@ -0,0 +1,58 @@
+//! The engine's source of randomness: one replaceable generator per thread, so that a
+//! simulation run on a single thread can be repeated exactly from its seed, and tests can make
+//! it draw a fixed sequence.
+
+use rand::rngs::StdRng;
+use rand::{Error, RngCore, SeedableRng};
+use std::cell::RefCell;
+
+thread_local! {
+    /// Generator of the current thread, seeded from the operating system until [`seed`] or
+    /// [`with_rng`] replaces it.
+    static GENERATOR: RefCell<Box<dyn RngCore>> = RefCell::new(Box::new(StdRng::from_entropy()));
+}
+
+/// Reseeds the generator of the current thread, so that everything run on it afterwards draws
+/// the same numbers as after any other call with the same `seed`.
+pub fn seed(seed: u64) {
+    GENERATOR.with(|generator| *generator.borrow_mut() = Box::new(StdRng::seed_from_u64(seed)));
+}
+
+/// Runs `f` with `rng` in place of the generator of the current thread, and puts the previous
+/// generator back afterwards. Everything the engine draws within `f` comes from `rng`, or from
+/// the generators [`crate::Tournament::run`] seeds from it for its rounds, e.g. a
+/// `rand::rngs::mock::StepRng` to make every random decision of a single match predictable.
+pub fn with_rng<T>(rng: impl RngCore + 'static, f: impl FnOnce() -> T) -> T {
+    let previous = GENERATOR.with(|generator| generator.replace(Box::new(rng)));
+    let result = f();
+    GENERATOR.with(|generator| *generator.borrow_mut() = previous);
+    result
+}
+
+/// Handle to the generator of the current thread, drawn from wherever the engine needs random
+/// numbers in place of `rand::thread_rng`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SeededRng;
+
+/// The generator of the current thread.
+pub fn rng() -> SeededRng {
+    SeededRng
+}
+
+impl RngCore for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        GENERATOR.with(|generator| generator.borrow_mut().next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        GENERATOR.with(|generator| generator.borrow_mut().next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        GENERATOR.with(|generator| generator.borrow_mut().fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        GENERATOR.with(|generator| generator.borrow_mut().try_fill_bytes(dest))
+    }
+}
@@ -0,0 +1,243 @@
+use crate::gametheory::Tournament;
+use std::collections::HashMap;
+
+/// Number of minorisation-maximisation sweeps used to fit the Bradley–Terry model.
+const FIT_ITERATIONS: usize = 200;
+
+/// Pairwise matchup outcomes aggregated over any number of tournaments.
+#[derive(Clone, Default)]
+pub struct PairwiseTable {
+    /// Keyed by (a, b) with a < b, storing (points of a, matchups played), where
+    /// a win is worth one point and a draw half a point.
+    results: HashMap<(String, String), (f64, f64)>,
+}
+
+impl PairwiseTable {
+    /// Adds the outcome of every matchup of a tournament that has been run.
+    pub fn record(&mut self, game: &Tournament) {
+        for (name, games) in game.matchups().iter().take(game.player_count()) {
+            for m in games {
+                let points = match m.own_score.cmp(&m.other_score) {
+                    std::cmp::Ordering::Greater => 1.0,
+                    std::cmp::Ordering::Less => 0.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                };
+                self.add(name, &m.opponent, points);
+            }
+        }
+    }
+
+    fn add(&mut self, a: &str, b: &str, points_a: f64) {
+        if a == b {
+            return;
+        }
+        let (key, points) = if a < b {
+            ((a.to_string(), b.to_string()), points_a)
+        } else {
+            ((b.to_string(), a.to_string()), 1.0 - points_a)
+        };
+        let entry = self.results.entry(key).or_insert((0.0, 0.0));
+        entry.0 += points;
+        entry.1 += 1.0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Builds the graph of who beats whom, with an edge from a to b when a has won more than
+    /// half of the recorded matchups between them.
+    pub fn dominance(&self) -> DominanceGraph {
+        let mut names: Vec<String> =
+            self.results.keys().flat_map(|(a, b)| [a.clone(), b.clone()]).collect();
+        names.sort();
+        names.dedup();
+        let index = |name: &String| names.binary_search(name).unwrap();
+
+        let mut edges = Vec::new();
+        for ((a, b), &(points, games)) in &self.results {
+            if points > games / 2.0 {
+                edges.push((index(a), index(b)));
+            } else if points < games / 2.0 {
+                edges.push((index(b), index(a)));
+            }
+        }
+        edges.sort();
+        DominanceGraph { names, edges }
+    }
+
+    /// Fits a Bradley–Terry model to the recorded outcomes and returns the strengths on the
+    /// Elo scale (1500 being average), sorted from strongest to weakest.
+    ///
+    /// Every participant is given one virtual draw against an average opponent so that
+    /// participants that never won still receive a finite rating.
+    pub fn bradley_terry(&self) -> Vec<(String, f64)> {
+        let mut index = HashMap::new();
+        let mut names = Vec::new();
+        for (a, b) in self.results.keys() {
+            for name in [a, b] {
+                if !index.contains_key(name) {
+                    index.insert(name.clone(), names.len());
+                    names.push(name.clone());
+                }
+            }
+        }
+        let pairs: Vec<(usize, usize, f64, f64)> = self
+            .results
+            .iter()
+            .map(|((a, b), &(points, games))| (index[a], index[b], points, games))
+            .collect();
+
+        let mut strength = vec![1.0; names.len()];
+        for _ in 0..FIT_ITERATIONS {
+            let mut wins = vec![0.5; names.len()];
+            let mut denom: Vec<f64> = strength.iter().map(|p| 1.0 / (p + 1.0)).collect();
+            for &(a, b, points, games) in &pairs {
+                wins[a] += points;
+                wins[b] += games - points;
+                let d = games / (strength[a] + strength[b]);
+                denom[a] += d;
+                denom[b] += d;
+            }
+            for ((p, w), d) in strength.iter_mut().zip(wins).zip(denom) {
+                *p = w / d;
+            }
+            let log_mean = strength.iter().map(|p| p.ln()).sum::<f64>() / names.len() as f64;
+            for p in strength.iter_mut() {
+                *p /= log_mean.exp();
+            }
+        }
+
+        let mut ratings: Vec<(String, f64)> = names
+            .into_iter()
+            .zip(strength)
+            .map(|(name, p)| (name, 1500.0 + 400.0 * p.log10()))
+            .collect();
+        ratings.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        ratings
+    }
+}
+
+/// Directed graph where an edge (a, b) means participant a outscores participant b head-to-head.
+#[derive(Clone, Default)]
+pub struct DominanceGraph {
+    pub names: Vec<String>,
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl DominanceGraph {
+    /// Strongly connected components in topological order, so that no component is beaten by
+    /// one listed after it. Components with more than one member contain a cycle of the form
+    /// a beats b beats c beats a, i.e. the dominance relation is not transitive there.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let n = self.names.len();
+        let mut successors = vec![Vec::new(); n];
+        for &(a, b) in &self.edges {
+            successors[a].push(b);
+        }
+
+        // Iterative Tarjan, which emits components in reverse topological order.
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next = 0;
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            let mut work = vec![(root, 0)];
+            while let Some((v, child)) = work.pop() {
+                if child == 0 {
+                    index[v] = next;
+                    low[v] = next;
+                    next += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                }
+                if let Some(&w) = successors[v].get(child) {
+                    work.push((v, child + 1));
+                    if index[w] == usize::MAX {
+                        work.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                    continue;
+                }
+                if let Some(&(parent, _)) = work.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+                if low[v] == index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    component.sort();
+                    components.push(component);
+                }
+            }
+        }
+        components.reverse();
+        components
+    }
+
+    /// Edges of the condensation, as pairs of indices into [`DominanceGraph::components`].
+    pub fn condensation(&self, components: &[Vec<usize>]) -> Vec<(usize, usize)> {
+        let mut component_of = vec![0; self.names.len()];
+        for (c, members) in components.iter().enumerate() {
+            for &v in members {
+                component_of[v] = c;
+            }
+        }
+        let mut edges: Vec<(usize, usize)> = self
+            .edges
+            .iter()
+            .map(|&(a, b)| (component_of[a], component_of[b]))
+            .filter(|(a, b)| a != b)
+            .collect();
+        edges.sort();
+        edges.dedup();
+        edges
+    }
+
+    /// Whether the relation is free of cycles, and therefore a strict ranking up to ties.
+    pub fn is_transitive(&self) -> bool {
+        self.components().iter().all(|c| c.len() == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_cycle_is_one_component() {
+        let graph = DominanceGraph {
+            names: ["rock", "paper", "scissors", "loser"].map(String::from).to_vec(),
+            edges: vec![(1, 0), (2, 1), (0, 2), (0, 3), (1, 3), (2, 3)],
+        };
+        let mut components = graph.components();
+        components[0].sort();
+        assert_eq!(components, [vec![0, 1, 2], vec![3]]);
+        assert!(!graph.is_transitive());
+    }
+
+    #[test]
+    fn bradley_terry_follows_a_transitive_table() {
+        let mut table = PairwiseTable::default();
+        for (winner, loser, times) in [("a", "b", 3), ("b", "c", 3), ("a", "c", 4)] {
+            for _ in 0..times {
+                table.add(winner, loser, 1.0);
+            }
+            table.add(winner, loser, 0.0);
+        }
+        let ranked: Vec<String> = table.bradley_terry().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(ranked, ["a", "b", "c"]);
+        assert!(table.dominance().is_transitive());
+    }
+}
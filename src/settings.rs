@@ -0,0 +1,766 @@
+//! Settings of a simulation run, written to and read from the window's compact encoding and
+//! TOML configuration files.
+
+use crate::experiments::Setup;
+use crate::{
+    default_strategies, payoff_table, prisoners_dillemma_rules, strategy_names, Crossover,
+    GeneticConfig, PayoffMatrix, PayoffTable, Selection, MAX_DEPTH, STRATEGY_COUNT,
+};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Parameters of a simulation run, copied into the simulation thread.
+#[derive(Clone, Copy)]
+pub struct Settings {
+    pub gen_count: u32,
+    pub game_count: u32,
+    pub update_rule: UpdateRule,
+    /// Stop once the best score and diversity have not changed for this many generations.
+    pub early_stop: Option<usize>,
+    /// Let evolved genomes respond to public image scores instead of previous moves.
+    pub reputation: bool,
+    /// Payoff of both participants in a round that either sits out.
+    pub loner_payoff: i32,
+    /// Let participants refuse partners that exploited them or have a poor image.
+    pub ostracism: bool,
+    /// Replace every survivor of the genetic algorithm that reaches this age in generations.
+    pub lifespan: Option<u32>,
+    /// Highest probability that a decision is flipped before it is played, the trembling
+    /// hand of [`Tournament::with_noise`](crate::Tournament::with_noise).
+    pub noise: f64,
+    /// How the noise level changes over the generations.
+    pub noise_schedule: NoiseSchedule,
+    /// Probability that a participant remembers the other's move as the opposite one, while
+    /// the payoff still follows the moves actually played.
+    pub perception_noise: f64,
+    /// Probability that every participant forgets the previous moves before a round.
+    pub forgetting: f64,
+    /// Which fixed players every individual meets.
+    pub pairing: Pairing,
+    /// Cost per round paid by evolved genomes that respond to the previous moves.
+    pub memory_cost: i32,
+    /// Cost of every cooperation, paid from a budget replenished by payoffs.
+    pub cooperation_cost: Option<i32>,
+    /// Whom the population plays.
+    pub mixing: Mixing,
+    /// Alternate between the prisoner's dilemma and the game of
+    /// [`GAME_PRESETS`](crate::GAME_PRESETS) at the given index, switching after the given
+    /// number of generations.
+    pub seasons: Option<(u32, usize)>,
+    /// Symmetric payoffs of the game played, before any scheduled change.
+    pub payoffs: PayoffTable,
+    /// Sizes of the evolved population and of the fittest part of it that breeds.
+    pub genetic: GeneticConfig,
+    /// Seed of the random number generator, or `None` for a fresh seed every run.
+    pub seed: Option<u64>,
+    /// Number of champions from the hall of fame of earlier runs placed in the initial
+    /// population, as it stands when the run starts.
+    pub champions: usize,
+    /// Which named strategies take part among the fixed players, by index.
+    pub strategies: [bool; STRATEGY_COUNT],
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            gen_count: 100,
+            game_count: 10,
+            update_rule: UpdateRule::Genetic,
+            early_stop: None,
+            lifespan: None,
+            noise: 0.0,
+            noise_schedule: NoiseSchedule::Constant,
+            perception_noise: 0.0,
+            forgetting: 0.0,
+            pairing: Pairing::All,
+            memory_cost: 0,
+            cooperation_cost: None,
+            mixing: Mixing::FixedPlayers,
+            seasons: None,
+            payoffs: payoff_table(prisoners_dillemma_rules),
+            genetic: GeneticConfig::default(),
+            seed: None,
+            champions: 0,
+            strategies: default_strategies(),
+            reputation: false,
+            loner_payoff: -2,
+            ostracism: false,
+        }
+    }
+}
+
+impl Settings {
+    /// The configuration experiments rerun: these settings without the scheduled changes,
+    /// custom players and update rules that only the main simulation knows.
+    pub fn setup(&self) -> Setup {
+        Setup {
+            game_count: self.game_count,
+            gen_count: self.gen_count,
+            payoffs: self.payoffs,
+            genetic: self.genetic,
+            execution_noise: self.noise,
+            perception_noise: self.perception_noise,
+            forgetting: self.forgetting,
+            loner_payoff: self.loner_payoff,
+            strategies: self.strategies,
+        }
+    }
+
+    /// Writes the settings as whitespace-separated `key=value` pairs, with `-` for a disabled
+    /// option, e.g. `generations=100 games=10 update=genetic early-stop=- ...`.
+    pub fn encode(&self) -> String {
+        fn optional<T: std::fmt::Display>(value: Option<T>) -> String {
+            value.map_or("-".to_string(), |v| v.to_string())
+        }
+        let update = match self.update_rule {
+            UpdateRule::Genetic => "genetic".to_string(),
+            UpdateRule::BestResponse(share) => format!("best-response:{share}"),
+            UpdateRule::Demographic => "demographic".to_string(),
+            UpdateRule::SocialLearning(rate) => format!("social-learning:{rate}"),
+        };
+        let schedule = match self.noise_schedule {
+            NoiseSchedule::Constant => "constant".to_string(),
+            NoiseSchedule::Decreasing => "decreasing".to_string(),
+            NoiseSchedule::Oscillating(period) => format!("oscillating:{period}"),
+            NoiseSchedule::Withdrawn(fraction) => format!("withdrawn:{fraction}"),
+        };
+        let [r, s, t, p] = self.payoffs.map(|(own, _)| own);
+        let crossover = match self.genetic.crossover {
+            Crossover::Alternating => "alternating",
+            Crossover::SinglePoint => "single-point",
+            Crossover::TwoPoint => "two-point",
+            Crossover::Uniform => "uniform",
+        };
+        let selection = match self.genetic.selection {
+            Selection::Truncation => "truncation".to_string(),
+            Selection::RouletteWheel => "roulette".to_string(),
+            Selection::Rank => "rank".to_string(),
+            Selection::Tournament(k) => format!("tournament:{k}"),
+        };
+        let mixing = match self.mixing {
+            Mixing::FixedPlayers => "fixed",
+            Mixing::SelfPlay => "self-play",
+            Mixing::RoundRobin => "round-robin",
+            Mixing::Coevolution => "coevolution",
+        };
+        let strategies = if self.strategies.iter().all(|&s| s) {
+            "all".to_string()
+        } else if self.strategies.iter().all(|&s| !s) {
+            "-".to_string()
+        } else {
+            let indices = (0..STRATEGY_COUNT).filter(|&i| self.strategies[i]);
+            indices.map(|i| i.to_string()).collect::<Vec<_>>().join(",")
+        };
+        let pairing = match self.pairing {
+            Pairing::All => "all".to_string(),
+            Pairing::Random(n) => format!("random:{n}"),
+            Pairing::RotatingPool(n) => format!("rotating:{n}"),
+        };
+        [
+            format!("generations={}", self.gen_count),
+            format!("games={}", self.game_count),
+            format!("population={}", self.genetic.population_size),
+            format!("parents={}", self.genetic.generation_size),
+            format!("elitism={}", self.genetic.elitism),
+            format!("steady-state={}", optional(self.genetic.steady_state)),
+            format!("selection={selection}"),
+            format!("crossover={crossover}"),
+            format!("mutation={}", self.genetic.mutation_rate),
+            format!("depth={}", self.genetic.depth),
+            format!("update={update}"),
+            format!("early-stop={}", optional(self.early_stop)),
+            format!("reputation={}", self.reputation),
+            format!("loner-payoff={}", self.loner_payoff),
+            format!("ostracism={}", self.ostracism),
+            format!("lifespan={}", optional(self.lifespan)),
+            format!("noise={}", self.noise),
+            format!("noise-schedule={schedule}"),
+            format!("perception-noise={}", self.perception_noise),
+            format!("forgetting={}", self.forgetting),
+            format!("pairing={pairing}"),
+            format!("memory-cost={}", self.memory_cost),
+            format!("cooperation-cost={}", optional(self.cooperation_cost)),
+            format!("mixing={mixing}"),
+            format!(
+                "seasons={}",
+                optional(self.seasons.map(|(length, game)| format!("{length}:{game}")))
+            ),
+            format!("payoffs={r},{s},{t},{p}"),
+            format!("seed={}", optional(self.seed)),
+            format!("champions={}", self.champions),
+            format!("strategies={strategies}"),
+        ]
+        .join(" ")
+    }
+
+    /// Reads settings written by [`Settings::encode`], keeping the default of every key that is
+    /// missing. Fails with the offending key if it is unknown or its value cannot be read, or
+    /// with the broken constraint as [`Settings::check`] does.
+    pub fn decode(text: &str) -> Result<Settings, String> {
+        let mut settings = Settings::default();
+        for pair in text.split_whitespace() {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected `key=value`, got `{pair}`"))?;
+            settings.set(key, value)?;
+        }
+        settings.check()
+    }
+
+    /// Sets the setting `key` to `value` as written by [`Settings::encode`], failing with the
+    /// offending key if it is unknown or its value cannot be read.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        fn optional<T: std::str::FromStr>(value: &str) -> Option<Option<T>> {
+            match value {
+                "-" => Some(None),
+                value => value.parse().ok().map(Some),
+            }
+        }
+        fn argument<T: std::str::FromStr>(value: &str, name: &str) -> Option<T> {
+            value.strip_prefix(name)?.strip_prefix(':')?.parse().ok()
+        }
+        /// Sets `key` to `value`, returning `Some(false)` for an unknown key and `None` for a
+        /// value that cannot be read.
+        fn set(settings: &mut Settings, key: &str, value: &str) -> Option<bool> {
+            match key {
+                "generations" => settings.gen_count = value.parse().ok()?,
+                "games" => settings.game_count = value.parse().ok()?,
+                "population" => settings.genetic.population_size = value.parse().ok()?,
+                "parents" => settings.genetic.generation_size = value.parse().ok()?,
+                "elitism" => settings.genetic.elitism = value.parse().ok()?,
+                "steady-state" => settings.genetic.steady_state = optional(value)?,
+                "selection" => {
+                    settings.genetic.selection = match value {
+                        "truncation" => Selection::Truncation,
+                        "roulette" => Selection::RouletteWheel,
+                        "rank" => Selection::Rank,
+                        _ => argument(value, "tournament").map(Selection::Tournament)?,
+                    }
+                }
+                "mutation" => settings.genetic.mutation_rate = value.parse().ok()?,
+                "depth" => settings.genetic.depth = value.parse().ok()?,
+                "crossover" => {
+                    settings.genetic.crossover = match value {
+                        "alternating" => Crossover::Alternating,
+                        "single-point" => Crossover::SinglePoint,
+                        "two-point" => Crossover::TwoPoint,
+                        "uniform" => Crossover::Uniform,
+                        _ => return None,
+                    }
+                }
+                "update" => {
+                    settings.update_rule = match value {
+                        "genetic" => UpdateRule::Genetic,
+                        "demographic" => UpdateRule::Demographic,
+                        _ => argument(value, "best-response")
+                            .map(UpdateRule::BestResponse)
+                            .or_else(|| {
+                                argument(value, "social-learning").map(UpdateRule::SocialLearning)
+                            })?,
+                    }
+                }
+                "early-stop" => settings.early_stop = optional(value)?,
+                "seed" => settings.seed = optional(value)?,
+                "champions" => settings.champions = value.parse().ok()?,
+                "strategies" => {
+                    settings.strategies = [value == "all"; STRATEGY_COUNT];
+                    if !matches!(value, "all" | "-") {
+                        for index in value.split(',') {
+                            let index: usize = index.parse().ok()?;
+                            *settings.strategies.get_mut(index)? = true;
+                        }
+                    }
+                }
+                "reputation" => settings.reputation = value.parse().ok()?,
+                "loner-payoff" => settings.loner_payoff = value.parse().ok()?,
+                "ostracism" => settings.ostracism = value.parse().ok()?,
+                "lifespan" => settings.lifespan = optional(value)?,
+                "noise" => settings.noise = value.parse().ok()?,
+                "noise-schedule" => {
+                    settings.noise_schedule = match value {
+                        "constant" => NoiseSchedule::Constant,
+                        "decreasing" => NoiseSchedule::Decreasing,
+                        _ => {
+                            argument(value, "oscillating").map(NoiseSchedule::Oscillating).or_else(
+                                || argument(value, "withdrawn").map(NoiseSchedule::Withdrawn),
+                            )?
+                        }
+                    }
+                }
+                "perception-noise" => settings.perception_noise = value.parse().ok()?,
+                "forgetting" => settings.forgetting = value.parse().ok()?,
+                "pairing" => {
+                    settings.pairing = match value {
+                        "all" => Pairing::All,
+                        _ => argument(value, "random")
+                            .map(Pairing::Random)
+                            .or_else(|| argument(value, "rotating").map(Pairing::RotatingPool))?,
+                    }
+                }
+                "memory-cost" => settings.memory_cost = value.parse().ok()?,
+                "cooperation-cost" => settings.cooperation_cost = optional(value)?,
+                "mixing" => {
+                    settings.mixing = match value {
+                        "fixed" => Mixing::FixedPlayers,
+                        "self-play" => Mixing::SelfPlay,
+                        "round-robin" => Mixing::RoundRobin,
+                        "coevolution" => Mixing::Coevolution,
+                        _ => return None,
+                    }
+                }
+                // Written before the other kinds of mixing were added.
+                "self-play" if value.parse().ok()? => settings.mixing = Mixing::SelfPlay,
+                "self-play" => {}
+                "seasons" => {
+                    settings.seasons = match value {
+                        "-" => None,
+                        _ => {
+                            let (length, game) = value.split_once(':')?;
+                            Some((length.parse().ok()?, game.parse().ok()?))
+                        }
+                    }
+                }
+                "payoffs" => {
+                    let values: Option<Vec<i32>> =
+                        value.split(',').map(|v| v.parse().ok()).collect();
+                    let &[r, s, t, p] = values?.as_slice() else {
+                        return None;
+                    };
+                    settings.payoffs = PayoffMatrix::symmetric(r, s, t, p).outcomes();
+                }
+                _ => return Some(false),
+            }
+            Some(true)
+        }
+
+        match set(self, key, value) {
+            Some(true) => Ok(()),
+            Some(false) => Err(format!("unknown setting `{key}`")),
+            None => Err(format!("cannot read `{value}` as the value of `{key}`")),
+        }
+    }
+
+    /// Returns the settings unless more individuals breed than the population holds, the
+    /// mutation rate is not a probability or the genomes remember more rounds than the run can
+    /// evolve, naming the broken constraint.
+    pub fn check(self) -> Result<Settings, String> {
+        let GeneticConfig {
+            population_size,
+            generation_size,
+            mutation_rate,
+            depth,
+            ..
+        } = self.genetic;
+        if !(1..=population_size).contains(&generation_size) {
+            return Err(format!(
+                "`parents` ({generation_size}) must be between 1 and `population` ({population_size})"
+            ));
+        }
+        if !(0.0..=1.0).contains(&mutation_rate) {
+            return Err(format!(
+                "`mutation` ({mutation_rate}) must be between 0 and 1"
+            ));
+        }
+        if !(1..=MAX_DEPTH).contains(&depth) {
+            return Err(format!(
+                "`depth` ({depth}) must be between 1 and {MAX_DEPTH}"
+            ));
+        }
+        // Everything but the genetic algorithm among the fixed players needs numbered genomes.
+        let memory_one_only = self.mixing != Mixing::FixedPlayers
+            || self.update_rule != UpdateRule::Genetic
+            || self.lifespan.is_some()
+            || self.champions > 0
+            || self.memory_cost != 0;
+        if depth > 1 && memory_one_only {
+            return Err(format!(
+                "`depth` ({depth}) above 1 needs fixed players and the genetic algorithm, \
+                 without `lifespan`, `champions` or `memory-cost`"
+            ));
+        }
+        Ok(self)
+    }
+}
+
+/// Reads a configuration file: settings as in [`Settings::encode`], spread over any number of
+/// lines, and payoff changes as in [`PayoffChange::parse_schedule`], one per line starting with
+/// its generation. Text after `#` is a comment. Returns the settings and the payoff schedule.
+pub fn parse_config(text: &str) -> Result<(Settings, String), String> {
+    let lines = text.lines().map(|line| line.split('#').next().unwrap_or("").trim());
+    let (schedule, settings): (Vec<&str>, Vec<&str>) =
+        lines.partition(|line| line.starts_with(|c: char| c.is_ascii_digit()));
+    let schedule = schedule.join("\n");
+    PayoffChange::parse_schedule(&schedule)?;
+    let settings = Settings::decode(&settings.join(" "))?;
+    Ok((settings, schedule))
+}
+
+/// Reads a configuration file with [`from_toml`] if its name ends in `.toml`, and with
+/// [`parse_config`] otherwise.
+pub fn load_config(path: &Path, text: &str) -> Result<(Settings, String), String> {
+    if path.extension().is_some_and(|e| e == "toml") {
+        from_toml(text)
+    } else {
+        parse_config(text)
+    }
+}
+
+/// Which of the fixed players the individuals meet in a generation.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Pairing {
+    /// Every individual meets every fixed player.
+    All,
+    /// Every individual meets this many fixed players drawn at random for it alone.
+    Random(usize),
+    /// Every individual meets the same this many fixed players, drawn afresh each generation.
+    RotatingPool(usize),
+}
+
+/// Whom the evolved population plays in a generation.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mixing {
+    /// The fixed players only.
+    FixedPlayers,
+    /// The population itself instead of the fixed players.
+    SelfPlay,
+    /// The fixed players and the population itself.
+    RoundRobin,
+    /// A second evolving population that takes the place of the fixed players.
+    Coevolution,
+}
+
+impl Mixing {
+    pub const ALL: [Mixing; 4] = [
+        Mixing::FixedPlayers,
+        Mixing::SelfPlay,
+        Mixing::RoundRobin,
+        Mixing::Coevolution,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Mixing::FixedPlayers => "Fixed players",
+            Mixing::SelfPlay => "Self-play",
+            Mixing::RoundRobin => "Full round robin",
+            Mixing::Coevolution => "Co-evolution",
+        }
+    }
+}
+
+/// Change to one payoff of the prisoner's dilemma from a given generation on.
+#[derive(Clone, Copy)]
+pub struct PayoffChange {
+    pub generation: u32,
+    /// Position in a [`PayoffTable`] of the outcome whose payoff changes: 0 for the reward R,
+    /// 1 for the sucker's payoff S, 2 for the temptation T and 3 for the punishment P.
+    pub outcome: usize,
+    pub value: i32,
+    /// Whether `value` is added to the current payoff instead of replacing it.
+    pub relative: bool,
+}
+
+impl PayoffChange {
+    /// Parses one change per line, written as `generation payoff=value` to set a payoff or
+    /// `generation payoff+=value` to shift it, e.g. `50 T=1` or `20 T+=-1`.
+    pub fn parse_schedule(text: &str) -> Result<Vec<PayoffChange>, String> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let invalid = || format!("expected `generation payoff=value`, got `{line}`");
+                let (generation, change) = line.split_once(' ').ok_or_else(invalid)?;
+                let (payoff, value) = change.trim().split_once('=').ok_or_else(invalid)?;
+                let (payoff, relative) = match payoff.strip_suffix('+') {
+                    Some(payoff) => (payoff, true),
+                    None => (payoff, false),
+                };
+                let outcome = ["R", "S", "T", "P"]
+                    .iter()
+                    .position(|&p| p == payoff.trim())
+                    .ok_or_else(|| format!("unknown payoff in `{line}`, use R, S, T or P"))?;
+                Ok(PayoffChange {
+                    generation: generation.parse().map_err(|_| invalid())?,
+                    outcome,
+                    value: value.trim().parse().map_err(|_| invalid())?,
+                    relative,
+                })
+            })
+            .collect()
+    }
+
+    /// The change as it is written in a schedule, without the generation.
+    pub fn label(self) -> String {
+        let payoff = ["R", "S", "T", "P"][self.outcome];
+        let operator = if self.relative { "+=" } else { "=" };
+        format!("{payoff}{operator}{}", self.value)
+    }
+
+    /// Applies the change to a symmetric payoff table, keeping it symmetric.
+    pub fn apply(self, payoffs: &mut PayoffTable) {
+        let current = payoffs[self.outcome].0;
+        payoffs[self.outcome].0 = if self.relative {
+            current + self.value
+        } else {
+            self.value
+        };
+        payoffs[0].1 = payoffs[0].0;
+        payoffs[1].1 = payoffs[2].0;
+        payoffs[2].1 = payoffs[1].0;
+        payoffs[3].1 = payoffs[3].0;
+    }
+}
+
+/// Course of the execution noise over a run, scaled by [`Settings::noise`].
+#[derive(Clone, Copy, PartialEq)]
+pub enum NoiseSchedule {
+    /// The same noise in every generation.
+    Constant,
+    /// Noise falling linearly from its peak in the first generation to none in the last.
+    Decreasing,
+    /// Noise rising and falling back to none over the given number of generations.
+    Oscillating(u32),
+    /// Noise for the first given fraction of the run, none afterwards.
+    Withdrawn(f64),
+}
+
+impl NoiseSchedule {
+    /// Noise level of `generation` in a run of `gen_count` generations that peaks at `noise`.
+    pub fn level(self, noise: f64, generation: u32, gen_count: u32) -> f64 {
+        let progress = generation as f64 / (gen_count.max(2) - 1) as f64;
+        match self {
+            NoiseSchedule::Constant => noise,
+            NoiseSchedule::Decreasing => noise * (1.0 - progress),
+            NoiseSchedule::Oscillating(period) => {
+                let phase = generation as f64 / period as f64 * std::f64::consts::TAU;
+                noise * (1.0 - phase.cos()) / 2.0
+            }
+            NoiseSchedule::Withdrawn(fraction) => {
+                if progress < fraction {
+                    noise
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NoiseSchedule::Constant => "Constant",
+            NoiseSchedule::Decreasing => "Decreasing",
+            NoiseSchedule::Oscillating(_) => "Oscillating",
+            NoiseSchedule::Withdrawn(_) => "Withdrawn",
+        }
+    }
+}
+
+/// How the population of the next generation is derived from the current one.
+#[derive(Clone, Copy, PartialEq)]
+pub enum UpdateRule {
+    /// Selection followed by crossover and mutation.
+    Genetic,
+    /// The given fraction of the population switches to the best response against it.
+    BestResponse(f64),
+    /// Individuals live off their accumulated payoffs, dying when poor and splitting when rich.
+    Demographic,
+    /// Every individual watches a random match of another with the given probability and
+    /// imitates it if it did better there than the watcher does on average.
+    SocialLearning(f64),
+}
+
+/// Reads the settings and payoff schedule of the config file at `path` for a command, or the
+/// defaults without one.
+pub fn read_config(path: Option<String>) -> Result<(Settings, Vec<PayoffChange>), String> {
+    let loaded = match path {
+        Some(path) => std::fs::read_to_string(&path)
+            .map_err(|err| format!("{path}: {err}"))
+            .and_then(|text| load_config(Path::new(&path), &text)),
+        None => Ok((Settings::default(), String::new())),
+    };
+    loaded
+        .and_then(|(settings, schedule)| {
+            PayoffChange::parse_schedule(&schedule).map(|schedule| (settings, schedule))
+        })
+        .map_err(|err| format!("invalid config: {err}"))
+}
+
+/// A TOML configuration, so that an experiment can be shared as a declarative file such as
+///
+/// ```toml
+/// generations = 200
+/// seed = 42
+/// strategies = ["trusting t4t", "grim trigger", "random"]
+/// payoffs = [3, 0, 5, 1]
+/// payoff_schedule = ["50 T=4"]
+///
+/// [genetic]
+/// population = 32
+/// mutation = 0.05
+/// ```
+///
+/// Keys are those of the settings with `_` for `-`, the genetic algorithm's under `[genetic]`,
+/// and values that name a mode, such as `update = "best-response:0.5"`, are written as in the
+/// settings. `strategies` lists the fixed players that take part by name or index and
+/// `payoff_schedule` holds one payoff change per string.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct TomlConfig {
+    generations: Option<u32>,
+    games: Option<u32>,
+    update: Option<String>,
+    early_stop: Option<usize>,
+    reputation: Option<bool>,
+    loner_payoff: Option<i32>,
+    ostracism: Option<bool>,
+    lifespan: Option<u32>,
+    noise: Option<f64>,
+    noise_schedule: Option<String>,
+    perception_noise: Option<f64>,
+    forgetting: Option<f64>,
+    pairing: Option<String>,
+    memory_cost: Option<i32>,
+    cooperation_cost: Option<i32>,
+    mixing: Option<String>,
+    seasons: Option<String>,
+    payoffs: Option<[i32; 4]>,
+    seed: Option<u64>,
+    champions: Option<usize>,
+    strategies: Option<Vec<String>>,
+    #[serde(default)]
+    payoff_schedule: Vec<String>,
+    #[serde(default)]
+    genetic: GeneticTable,
+}
+
+/// The `[genetic]` table of a [`TomlConfig`].
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct GeneticTable {
+    population: Option<usize>,
+    parents: Option<usize>,
+    elitism: Option<usize>,
+    steady_state: Option<usize>,
+    selection: Option<String>,
+    crossover: Option<String>,
+    mutation: Option<f64>,
+    depth: Option<usize>,
+}
+
+/// Reads a TOML configuration as described at `TomlConfig`, keeping the default of every
+/// key that is missing. Returns the settings and the payoff schedule, one change per line.
+pub fn from_toml(text: &str) -> Result<(Settings, String), String> {
+    let config: TomlConfig = toml::from_str(text).map_err(|err| err.message().to_string())?;
+    let mut settings = Settings::default();
+    let genetic = &mut settings.genetic;
+    genetic.population_size = config.genetic.population.unwrap_or(genetic.population_size);
+    genetic.generation_size = config.genetic.parents.unwrap_or(genetic.generation_size);
+    genetic.elitism = config.genetic.elitism.unwrap_or(genetic.elitism);
+    genetic.steady_state = config.genetic.steady_state.or(genetic.steady_state);
+    genetic.mutation_rate = config.genetic.mutation.unwrap_or(genetic.mutation_rate);
+    genetic.depth = config.genetic.depth.unwrap_or(genetic.depth);
+    settings.gen_count = config.generations.unwrap_or(settings.gen_count);
+    settings.game_count = config.games.unwrap_or(settings.game_count);
+    settings.early_stop = config.early_stop.or(settings.early_stop);
+    settings.reputation = config.reputation.unwrap_or(settings.reputation);
+    settings.loner_payoff = config.loner_payoff.unwrap_or(settings.loner_payoff);
+    settings.ostracism = config.ostracism.unwrap_or(settings.ostracism);
+    settings.lifespan = config.lifespan.or(settings.lifespan);
+    settings.noise = config.noise.unwrap_or(settings.noise);
+    settings.perception_noise = config.perception_noise.unwrap_or(settings.perception_noise);
+    settings.forgetting = config.forgetting.unwrap_or(settings.forgetting);
+    settings.memory_cost = config.memory_cost.unwrap_or(settings.memory_cost);
+    settings.cooperation_cost = config.cooperation_cost.or(settings.cooperation_cost);
+    settings.seed = config.seed.or(settings.seed);
+    settings.champions = config.champions.unwrap_or(settings.champions);
+    if let Some([r, s, t, p]) = config.payoffs {
+        settings.payoffs = PayoffMatrix::symmetric(r, s, t, p).outcomes();
+    }
+    if let Some(strategies) = config.strategies {
+        let names = strategy_names();
+        settings.strategies = [false; STRATEGY_COUNT];
+        for name in strategies {
+            let index = names.iter().position(|n| n.replace('\n', " ") == name);
+            let index = index
+                .or_else(|| name.parse().ok().filter(|&i| i < names.len()))
+                .ok_or_else(|| format!("unknown strategy `{name}`"))?;
+            settings.strategies[index] = true;
+        }
+    }
+    let modes = [
+        ("update", config.update),
+        ("noise-schedule", config.noise_schedule),
+        ("pairing", config.pairing),
+        ("mixing", config.mixing),
+        ("seasons", config.seasons),
+        ("selection", config.genetic.selection),
+        ("crossover", config.genetic.crossover),
+    ];
+    for (key, value) in modes {
+        if let Some(value) = value {
+            settings.set(key, &value)?;
+        }
+    }
+    let schedule = config.payoff_schedule.join("\n");
+    PayoffChange::parse_schedule(&schedule)?;
+    Ok((settings.check()?, schedule))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_map_onto_the_settings() {
+        let toml = "seed = 42\npayoffs = [\n    3, 0,\n    5, 1, # T, P\n]\n\
+                    payoff_schedule = [\"50 T=4\"]\n\n[genetic]\npopulation = 32\n\
+                    mutation = 0.05\nselection = \"tournament:3\"\ndepth = 2\n";
+        let (settings, schedule) = from_toml(toml).unwrap();
+        assert_eq!(settings.seed, Some(42));
+        assert_eq!(
+            settings.payoffs,
+            PayoffMatrix::symmetric(3, 0, 5, 1).outcomes()
+        );
+        assert_eq!(settings.genetic.population_size, 32);
+        assert_eq!(settings.genetic.mutation_rate, 0.05);
+        assert_eq!(settings.genetic.selection, Selection::Tournament(3));
+        assert_eq!(settings.genetic.depth, 2);
+        assert_eq!(schedule, "50 T=4");
+    }
+
+    #[test]
+    fn strategies_are_named_or_numbered() {
+        let toml = "strategies = ['grim trigger', \"evil\", \"0\"] # the [fixed] players\n";
+        let (settings, _) = from_toml(toml).unwrap();
+        let names = strategy_names();
+        let index = |name| names.iter().position(|&n| n == name).unwrap();
+        let chosen: Vec<usize> = (0..STRATEGY_COUNT).filter(|&i| settings.strategies[i]).collect();
+        let mut expected = vec![0, index("grim\ntrigger"), index("evil")];
+        expected.sort();
+        assert_eq!(chosen, expected);
+        assert!(from_toml("strategies = [\"nobody\"]").is_err_and(|e| e.contains("`nobody`")));
+    }
+
+    #[test]
+    fn unknown_keys_and_bad_modes_are_rejected() {
+        assert!(from_toml("mutaton = 0.05").is_err());
+        assert!(from_toml("[run]\nseed = 1\n").is_err());
+        assert!(from_toml("mixing = \"everyone\"").is_err_and(|e| e.contains("`mixing`")));
+        assert!(from_toml("payoffs = [3, 0,\n").is_err());
+    }
+
+    #[test]
+    fn settings_name_what_they_cannot_read() {
+        let decoded = Settings::decode(&Settings::default().encode()).map(|s| s.encode());
+        assert_eq!(decoded, Ok(Settings::default().encode()));
+        let error = |text| Settings::decode(text).err().unwrap_or_default();
+        assert!(error("mutaton=0.05").contains("`mutaton`"));
+        assert!(error("bogus=1").contains("`bogus`"));
+        assert!(error("games=many").contains("`games`"));
+        assert!(error("population=8").contains("`parents`"));
+        assert!(error("mutation=2").contains("`mutation`"));
+        assert!(error("depth=4").contains("`depth`"));
+        assert!(error("depth=2 mixing=self-play").contains("`depth`"));
+        let toml = from_toml("[genetic]\npopulation = 8\n");
+        assert!(toml.is_err_and(|err| err.contains("`population` (8)")));
+    }
+}
@@ -0,0 +1,520 @@
+//! The simulation loop shared by the window and the command line: generation after
+//! generation of tournaments under [`Settings`], with what it records published as it goes.
+
+use crate::analysis::{self, Cluster, CrossCheck, SensitivityRow, Stability};
+use crate::demography::Demography;
+use crate::lineage::Lineage;
+use crate::ratings::PairwiseTable;
+use crate::settings::{Mixing, Pairing, PayoffChange, Settings, UpdateRule};
+use crate::stats::{self, BatchGeneration, BitAssociation, StatsRecorder, StrategyStats};
+use crate::{
+    breed, genome_to_number, get_aged_generation, get_new_generation, get_traced_generation,
+    history, is_punisher, is_reactive, payoff_table, prisoners_dillemma_rules, random,
+    PayoffMatrix, ScoreGrid, Standing, Tournament, GAME_PRESETS, PUNISH_COST, PUNISH_FINE,
+    STRATEGY_COUNT,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Shifts applied to each payoff parameter in the sensitivity analysis.
+const PAYOFF_DELTAS: [f64; 9] = [-2.0, -1.5, -1.0, -0.5, 0.0, 0.5, 1.0, 1.5, 2.0];
+
+/// Maximum distance between response profiles for participants to share a cluster.
+const CLUSTER_THRESHOLD: f64 = 0.2;
+
+/// Where a run stands, or why it ended.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status {
+    /// No run has started since the last reset.
+    #[default]
+    Idle,
+    Running,
+    /// Paused before the given generation.
+    Paused(u32),
+    /// Stopped at the given generation because no genome was left.
+    Extinct(u32),
+    /// Stopped early because the best score and the diversity stayed put for `generations`
+    /// generations.
+    Plateaued {
+        generation: u32,
+        generations: usize,
+    },
+    /// Played all the given number of generations.
+    Completed(u32),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Status::Idle => Ok(()),
+            Status::Running => write!(f, "Running"),
+            Status::Paused(generation) => write!(f, "Paused before generation {generation}"),
+            Status::Extinct(generation) => write!(
+                f,
+                "Stopped at generation {generation}: the population went extinct"
+            ),
+            Status::Plateaued {
+                generation,
+                generations,
+            } => write!(
+                f,
+                "Stopped early at generation {generation}: best score and diversity plateaued \
+                 for {generations} generations"
+            ),
+            Status::Completed(gen_count) => write!(f, "Completed all {gen_count} generations"),
+        }
+    }
+}
+
+/// Results a simulation shares with whoever watches it, updated after every generation.
+#[derive(Clone, Default)]
+pub struct Progress {
+    /// Fitness distribution, cooperation, inequality and genome counts of every generation.
+    pub recorder: Arc<Mutex<StatsRecorder>>,
+    /// Statistics of every participant in the latest generation.
+    pub stats: Arc<Mutex<Vec<StrategyStats>>>,
+    /// Matchup outcomes of every generation simulated since the last reset.
+    pub outcomes: Arc<Mutex<PairwiseTable>>,
+    /// Behavioural clusters of the latest generation.
+    pub clusters: Arc<Mutex<Vec<Cluster>>>,
+    /// Simulated scores of the fixed players next to their Markov chain expectation.
+    pub cross_check: Arc<Mutex<Vec<CrossCheck>>>,
+    /// Genome of the best opponent in the latest generation, if it remembers one round.
+    pub mvp: Arc<Mutex<Option<u8>>>,
+    /// Evolutionary stability of every genome under the rules of the latest run.
+    pub stability: Arc<Mutex<Vec<Stability>>>,
+    /// Fitness per genome bit value over every generation since the last reset.
+    pub genes: Arc<Mutex<BitAssociation>>,
+    /// Ancestry of the latest run, traced only under the genetic algorithm.
+    pub lineage: Arc<Mutex<Lineage>>,
+    /// Where the latest run stands.
+    pub status: Arc<Mutex<Status>>,
+    /// Generations of the latest run at which the game changed, with a description of the
+    /// new regime.
+    pub regimes: Arc<Mutex<Vec<(u32, String)>>>,
+    /// Payoff sensitivity of the final generation of the latest run.
+    pub sensitivity: Arc<Mutex<Vec<SensitivityRow>>>,
+    /// Genomes of the latest generation played, if they remember one round.
+    pub population: Arc<Mutex<Vec<u8>>>,
+    /// Scores of every participant in the generation being played, updated every round.
+    pub standings: Arc<Mutex<Vec<Standing>>>,
+    /// Scores of every pairing of the latest generation.
+    pub score_grid: Arc<Mutex<Option<ScoreGrid>>>,
+    /// Final tournament of the latest run as JSON.
+    pub final_tournament: Arc<Mutex<String>>,
+    /// Best score per generation over the runs of the latest batch finished so far.
+    pub batch: Arc<Mutex<Vec<BatchGeneration>>>,
+}
+
+/// Command sent from the window to the running simulation.
+pub enum Control {
+    Pause,
+    Resume,
+    /// Plays one more generation and pauses again.
+    Step,
+    /// Least time between the starts of two generations.
+    Delay(Duration),
+}
+
+/// Receiving end of the commands of the window, consulted by a simulation before every
+/// generation. Runs without a window are never paused.
+#[derive(Default)]
+pub struct Controller {
+    /// Whether the simulation is running, cleared by the window to abandon the current
+    /// generation.
+    running: Arc<AtomicBool>,
+    commands: Option<Receiver<Control>>,
+    paused: bool,
+    delay: Duration,
+    /// When the latest generation started.
+    started: Option<Instant>,
+    /// Whether the window let go of the simulation, which then stops.
+    stopped: bool,
+}
+
+impl Controller {
+    pub fn new(running: Arc<AtomicBool>, commands: Receiver<Control>) -> Self {
+        Controller {
+            running,
+            commands: Some(commands),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the window let go of the simulation.
+    pub fn stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Handles the commands sent since the last generation, waiting for one that lets the
+    /// simulation go on while paused and calling `on_pause` before every wait, and holds the
+    /// next generation back until the delay since the latest one has passed. Returns whether
+    /// to play the next generation.
+    fn proceed(&mut self, on_pause: impl Fn()) -> bool {
+        let Some(commands) = &self.commands else {
+            return true;
+        };
+        loop {
+            let command = if self.paused {
+                on_pause();
+                commands.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                let elapsed = self.started.map_or(self.delay, |s| s.elapsed());
+                commands.recv_timeout(self.delay.saturating_sub(elapsed))
+            };
+            match command {
+                Ok(Control::Pause) => self.paused = true,
+                Ok(Control::Resume) => self.paused = false,
+                Ok(Control::Delay(delay)) => self.delay = delay,
+                Ok(Control::Step) => {
+                    self.paused = true;
+                    self.started = Some(Instant::now());
+                    return true;
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    self.started = Some(Instant::now());
+                    return true;
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.stopped = true;
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+/// Runs the generations of `settings`, changing the payoffs as `schedule` says and letting
+/// `custom` players join the fixed ones, and publishes every generation to `progress`.
+/// `repaint` is called whenever something was published. The settings are expected to pass
+/// [`Settings::check`].
+pub fn simulate(
+    progress: &Progress,
+    control: &mut Controller,
+    settings: Settings,
+    schedule: &[PayoffChange],
+    custom: &[(String, u8)],
+    repaint: impl Fn(),
+) {
+    let Settings {
+        gen_count,
+        game_count,
+        update_rule,
+        early_stop,
+        reputation,
+        loner_payoff,
+        ostracism,
+        lifespan,
+        noise,
+        noise_schedule,
+        perception_noise,
+        forgetting,
+        pairing,
+        memory_cost,
+        cooperation_cost,
+        mixing,
+        seasons,
+        payoffs: initial_payoffs,
+        genetic,
+        seed,
+        champions,
+        strategies,
+    } = settings;
+    if let Some(seed) = seed {
+        random::seed(seed);
+    }
+    let champions = match champions {
+        0 => Vec::new(),
+        n => history::champions(&history::load(), n),
+    };
+    // Genomes remembering more than one round have no number and evolve in `deep` instead.
+    let (mut gen, mut deep) = match genetic.depth {
+        1 => (genetic.seeded_population(&champions), Vec::new()),
+        _ => (Box::default(), genetic.initial_genomes()),
+    };
+    // Second gene pool standing in for the fixed players when co-evolving.
+    let mut pool = genetic.initial_population();
+    let mut demography = Demography::new(gen.len());
+    let mut ages = vec![0; gen.len()];
+    let mut rules = initial_payoffs;
+    // Only the genetic algorithm breeds children of known parents.
+    let traced = matches!(update_rule, UpdateRule::Genetic) && deep.is_empty();
+    *progress.lineage.lock().unwrap() = if traced {
+        Lineage::new(&gen)
+    } else {
+        Lineage::default()
+    };
+
+    let payoffs = analysis::payoffs_of(PayoffMatrix::new(initial_payoffs));
+    let set_status = |status| *progress.status.lock().unwrap() = status;
+
+    control.running.store(true, Ordering::Relaxed);
+    progress.recorder.lock().unwrap().clear();
+    *progress.stability.lock().unwrap() = analysis::evolutionary_stability(&payoffs, game_count);
+    set_status(Status::Running);
+    progress.regimes.lock().unwrap().clear();
+
+    for generation in 0..gen_count {
+        let paused = || {
+            set_status(Status::Paused(generation));
+            repaint();
+        };
+        if !control.proceed(paused) {
+            return;
+        }
+        set_status(Status::Running);
+        if gen.is_empty() && deep.is_empty() {
+            set_status(Status::Extinct(generation));
+            control.running.store(false, Ordering::Relaxed);
+            return;
+        }
+        for change in schedule.iter().filter(|c| c.generation == generation) {
+            change.apply(&mut rules);
+            progress.regimes.lock().unwrap().push((generation, change.label()));
+        }
+        let mut active = rules;
+        if let Some((length, preset)) = seasons {
+            let game = if generation / length % 2 == 1 {
+                preset
+            } else {
+                0
+            };
+            if game != 0 {
+                active = payoff_table(GAME_PRESETS[game].1);
+            }
+            if generation > 0 && generation % length == 0 {
+                let regime = GAME_PRESETS[game].0.to_string();
+                progress.regimes.lock().unwrap().push((generation, regime));
+            }
+        }
+        let population = gen.clone();
+        let costs: Vec<i32> =
+            gen.iter().map(|&g| if is_reactive(g) { memory_cost } else { 0 }).collect();
+        let (game, player_costs) = match mixing {
+            Mixing::FixedPlayers if !deep.is_empty() => (
+                Tournament::with_genomes(
+                    game_count,
+                    prisoners_dillemma_rules,
+                    genetic.depth,
+                    &deep,
+                    custom,
+                ),
+                Vec::new(),
+            ),
+            Mixing::FixedPlayers => (
+                Tournament::with_custom_players(game_count, prisoners_dillemma_rules, gen, custom),
+                Vec::new(),
+            ),
+            Mixing::SelfPlay => (
+                Tournament::self_play(game_count, prisoners_dillemma_rules, &gen),
+                costs.clone(),
+            ),
+            Mixing::RoundRobin => (
+                Tournament::with_round_robin(game_count, prisoners_dillemma_rules, &gen, custom),
+                [vec![0; STRATEGY_COUNT + custom.len()], costs.clone()].concat(),
+            ),
+            Mixing::Coevolution => (
+                Tournament::coevolving(game_count, prisoners_dillemma_rules, &gen, &pool),
+                pool.iter().map(|&g| if is_reactive(g) { memory_cost } else { 0 }).collect(),
+            ),
+        };
+        let game = game
+            .with_reputation(reputation)
+            .with_loner_payoff(loner_payoff)
+            .with_ostracism(ostracism)
+            .with_noise(
+                noise_schedule.level(noise, generation, gen_count),
+                perception_noise,
+            )
+            .with_forgetting(forgetting)
+            .with_costs(&player_costs, &costs)
+            .with_payoffs(active);
+        let game = match cooperation_cost {
+            Some(cost) => game.with_budgets(cost),
+            None => game,
+        };
+        let mut game = match pairing {
+            Pairing::All => game,
+            Pairing::Random(k) => game.with_random_pairing(k),
+            Pairing::RotatingPool(m) => game.with_sampled_players(m),
+        };
+        if matches!(mixing, Mixing::FixedPlayers | Mixing::RoundRobin) {
+            game = game.with_enabled_players(&strategies);
+            // Choosing the moralist brings in the punishment stage it stands for.
+            if (0..STRATEGY_COUNT).any(|s| strategies[s] && is_punisher(s)) {
+                game = game.with_punishment(&[], PUNISH_COST, PUNISH_FINE);
+            }
+        }
+        game.run_observed(|game| {
+            *progress.standings.lock().unwrap() = game.standings();
+            repaint();
+        });
+        let Some((fittest, mvp_score)) = game.select_fittest_and_bestscore(genetic.generation_size)
+        else {
+            return;
+        };
+        let mvp = deep.is_empty().then(|| genome_to_number(&fittest[0]));
+
+        if !control.running.load(Ordering::Relaxed) {
+            return;
+        }
+
+        progress.recorder.lock().unwrap().record(&game, mvp_score, &population);
+        *progress.population.lock().unwrap() = population.to_vec();
+        *progress.stats.lock().unwrap() = stats::strategy_stats(&game);
+        progress.outcomes.lock().unwrap().record(&game);
+        progress.genes.lock().unwrap().record(&game);
+        *progress.clusters.lock().unwrap() =
+            analysis::cluster_by_behaviour(&game.response_profiles(), CLUSTER_THRESHOLD);
+        *progress.cross_check.lock().unwrap() = analysis::cross_check(&game, &payoffs);
+        *progress.mvp.lock().unwrap() = mvp;
+        *progress.score_grid.lock().unwrap() = Some(game.score_grid());
+        if traced {
+            let mut lineage = progress.lineage.lock().unwrap();
+            lineage.champion = game.ranking().first().and_then(|&i| lineage.id(i));
+        }
+        let plateaued = early_stop.is_some_and(|k| {
+            let recorder = progress.recorder.lock().unwrap();
+            stats::has_plateaued(&recorder.best_scores(), &recorder.frequencies(), k)
+        });
+        if generation + 1 == gen_count || plateaued {
+            *progress.sensitivity.lock().unwrap() =
+                analysis::payoff_sensitivity(&game, &payoffs, &PAYOFF_DELTAS);
+            *progress.final_tournament.lock().unwrap() = game.to_json();
+        }
+        repaint();
+
+        if plateaued {
+            set_status(Status::Plateaued {
+                generation,
+                generations: early_stop.unwrap_or_default(),
+            });
+            control.running.store(false, Ordering::Relaxed);
+            return;
+        }
+
+        let (next, origins) = match update_rule {
+            UpdateRule::Genetic if !deep.is_empty() => {
+                deep = breed(&game, &genetic);
+                (Box::default(), Vec::new())
+            }
+            UpdateRule::Genetic => match lifespan {
+                Some(lifespan) => {
+                    let (next, next_ages, origins) =
+                        get_aged_generation(&game, &population, &ages, lifespan, &genetic);
+                    ages = next_ages;
+                    (next, origins)
+                }
+                None => get_traced_generation(&game, &genetic),
+            },
+            UpdateRule::BestResponse(fraction) => (
+                analysis::best_response_step(&population, fraction, &payoffs, game_count),
+                Vec::new(),
+            ),
+            UpdateRule::Demographic => (demography.step(&population, &game), Vec::new()),
+            UpdateRule::SocialLearning(observation) => (
+                analysis::social_learning_step(&game, &population, observation),
+                Vec::new(),
+            ),
+        };
+        if traced {
+            progress.lineage.lock().unwrap().advance(&next, &origins, generation + 1);
+        }
+        gen = next;
+        if mixing == Mixing::Coevolution {
+            pool = get_new_generation(&game.swap_sides(), &genetic);
+        }
+    }
+
+    set_status(Status::Completed(gen_count));
+    control.running.store(false, Ordering::Relaxed);
+}
+
+/// Simulates `settings` once with every seed of `seeds`, publishing each run to `progress` as
+/// [`simulate`] does and, after it, the best score per generation over the runs finished so
+/// far to [`Progress::batch`]. Stops when the window lets go of the batch. Returns how each run
+/// finished, with its seed.
+pub fn simulate_batch(
+    progress: &Progress,
+    control: &mut Controller,
+    settings: Settings,
+    seeds: Range<u64>,
+    schedule: &[PayoffChange],
+    custom: &[(String, u8)],
+    repaint: impl Fn(),
+) -> Vec<(u64, Status)> {
+    progress.batch.lock().unwrap().clear();
+    let mut best = Vec::new();
+    let mut finished = Vec::new();
+    for seed in seeds {
+        let settings = Settings {
+            seed: Some(seed),
+            ..settings
+        };
+        simulate(progress, control, settings, schedule, custom, &repaint);
+        if control.stopped() {
+            break;
+        }
+        finished.push((seed, *progress.status.lock().unwrap()));
+        best.push(progress.recorder.lock().unwrap().best_scores());
+        *progress.batch.lock().unwrap() = stats::aggregate_best(&best);
+        repaint();
+    }
+    finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_run_once_per_seed() {
+        let settings = Settings {
+            gen_count: 2,
+            ..Settings::default()
+        };
+        let progress = Progress::default();
+        let control = &mut Controller::default();
+        let finished = simulate_batch(&progress, control, settings, 7..9, &[], &[], || {});
+        assert_eq!(
+            finished,
+            [(7, Status::Completed(2)), (8, Status::Completed(2))]
+        );
+        let batch = progress.batch.lock().unwrap().clone();
+        assert_eq!(batch.len(), 2);
+        assert!(batch.iter().all(|g| g.runs == 2));
+    }
+
+    #[test]
+    fn runs_breed_genomes_of_their_depth() {
+        let mut settings = Settings {
+            gen_count: 3,
+            ..Settings::default()
+        };
+        settings.genetic.depth = 2;
+        let progress = Progress::default();
+        simulate(
+            &progress,
+            &mut Controller::default(),
+            settings,
+            &[],
+            &[],
+            || {},
+        );
+        assert_eq!(*progress.status.lock().unwrap(), Status::Completed(3));
+        assert_eq!(*progress.mvp.lock().unwrap(), None);
+        let standings = progress.standings.lock().unwrap();
+        let evolved: Vec<&str> =
+            standings.iter().filter(|s| s.evolved).map(|s| s.name.as_str()).collect();
+        assert_eq!(evolved.len(), settings.genetic.population_size);
+        for name in evolved {
+            let genes = name.split('#').next().unwrap();
+            assert_eq!(genes.len(), crate::genome_length(2), "{name}");
+        }
+    }
+}
@@ -0,0 +1,973 @@
+use crate::analysis::SensitivityRow;
+use crate::experiments::{
+    AdaptiveRow, BracketMatch, ContractRow, DepthRow, EnvironmentRow, ForgettingRow, KinRow,
+    MetaRow, NoiseRow, OpeningRow, PunishRow, RoundRobinRow, SoloForgettingRow, TagRow, TalkRow,
+    VariantRow,
+};
+use crate::gametheory::{number_to_genome, Matchup, Tournament, GAME_PRESETS, GENOME_COUNT};
+use crate::random;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{self, Write};
+
+/// Number of resamples drawn when bootstrapping confidence intervals.
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+
+/// How scores are scaled before they are shown or exported.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Normalization {
+    /// Cumulative totals as accumulated by the tournament.
+    Raw,
+    /// Totals divided by the number of rounds per matchup.
+    PerRound,
+    /// Totals divided by the number of opponents faced.
+    PerOpponent,
+    /// Average payoff of a single round.
+    PerRoundPerOpponent,
+}
+
+impl Normalization {
+    pub const ALL: [Normalization; 4] = [
+        Normalization::Raw,
+        Normalization::PerRound,
+        Normalization::PerOpponent,
+        Normalization::PerRoundPerOpponent,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Normalization::Raw => "Cumulative",
+            Normalization::PerRound => "Per round",
+            Normalization::PerOpponent => "Per opponent",
+            Normalization::PerRoundPerOpponent => "Per round and opponent",
+        }
+    }
+
+    /// Scales a total accumulated over `games` matchups of `rounds` rounds each. No games or no
+    /// rounds count as one, so that the empty total of 0 stays 0.
+    pub fn apply(self, total: f64, rounds: u32, games: usize) -> f64 {
+        let (rounds, games) = (rounds.max(1) as f64, games.max(1) as f64);
+        match self {
+            Normalization::Raw => total,
+            Normalization::PerRound => total / rounds,
+            Normalization::PerOpponent => total / games,
+            Normalization::PerRoundPerOpponent => total / (rounds * games),
+        }
+    }
+
+    /// Scales the standard deviation of the score of a single matchup into that of a score
+    /// scaled by [`Normalization::apply`]: a total over `games` independent matchups spreads
+    /// by its square root, while the score per opponent keeps the spread across opponents.
+    pub fn spread(self, std_dev: f64, rounds: u32, games: usize) -> f64 {
+        let total = std_dev * (games as f64).sqrt();
+        let rounds = rounds.max(1) as f64;
+        match self {
+            Normalization::Raw => total,
+            Normalization::PerRound => total / rounds,
+            Normalization::PerOpponent => std_dev,
+            Normalization::PerRoundPerOpponent => std_dev / rounds,
+        }
+    }
+}
+
+/// Head-to-head results of a participant, where a matchup is won by having the higher total.
+#[derive(Clone, Copy, Default)]
+pub struct Record {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl Record {
+    /// Tallies the outcome of every matchup.
+    pub fn from_matchups(games: &[Matchup]) -> Self {
+        let mut record = Record::default();
+        for m in games {
+            match m.own_score.cmp(&m.other_score) {
+                Ordering::Greater => record.wins += 1,
+                Ordering::Less => record.losses += 1,
+                Ordering::Equal => record.draws += 1,
+            }
+        }
+        record
+    }
+}
+
+/// Summary of how a single participant performed over a finished [`Tournament`].
+#[derive(Clone)]
+pub struct StrategyStats {
+    /// Name of the participant.
+    pub name: String,
+    /// Sum of the participant's scores over all matchups.
+    pub total: i32,
+    /// Number of matchups played.
+    pub games: usize,
+    /// Rounds per matchup.
+    pub rounds: u32,
+    /// Mean matchup score.
+    pub mean: f64,
+    /// Standard deviation of the matchup scores across opponents.
+    pub std_dev: f64,
+    /// Median matchup score.
+    pub median: f64,
+    /// Average payoff of a single round.
+    pub per_round: f64,
+    /// Opponent against which the participant scored the highest, and that score.
+    pub best_matchup: (String, i32),
+    /// Opponent against which the participant scored the lowest, and that score.
+    pub worst_matchup: (String, i32),
+    /// Win/loss/draw record over all matchups.
+    pub record: Record,
+    /// Bootstrapped 95% confidence interval of the mean matchup score, `None` without
+    /// matchups.
+    pub mean_ci: Option<(f64, f64)>,
+    /// Bootstrapped 95% confidence interval of the rank by mean score among the participants
+    /// that played, 1 being the best, `None` without matchups.
+    pub rank_ci: Option<(usize, usize)>,
+    /// Public image score at the end of the tournament.
+    pub image: i32,
+}
+
+impl StrategyStats {
+    /// Computes the statistics of one participant from its matchups, all zero for a
+    /// participant that was not scheduled to play.
+    pub fn from_matchups(name: &str, games: &[Matchup], rounds: u32) -> Self {
+        let mut scores: Vec<i32> = games.iter().map(|m| m.own_score).collect();
+        scores.sort();
+        let n = scores.len().max(1) as f64;
+        let total: i32 = scores.iter().sum();
+        let mean = total as f64 / n;
+        let variance = scores.iter().map(|&s| (s as f64 - mean).powi(2)).sum::<f64>() / n;
+        let mid = scores.len() / 2;
+        let median = match scores.len() {
+            0 => 0.0,
+            len if len % 2 == 0 => (scores[mid - 1] + scores[mid]) as f64 / 2.0,
+            _ => scores[mid] as f64,
+        };
+        let matchup = |m: &Matchup| (m.opponent.clone(), m.own_score);
+        let best = games.iter().max_by_key(|m| m.own_score).map(matchup).unwrap_or_default();
+        let worst = games.iter().min_by_key(|m| m.own_score).map(matchup).unwrap_or_default();
+
+        StrategyStats {
+            name: name.to_string(),
+            total,
+            games: games.len(),
+            rounds,
+            mean,
+            std_dev: variance.sqrt(),
+            median,
+            per_round: mean / rounds as f64,
+            best_matchup: best,
+            worst_matchup: worst,
+            record: Record::from_matchups(games),
+            mean_ci: None,
+            rank_ci: None,
+            image: 0,
+        }
+    }
+
+    /// Total score scaled by `normalization`.
+    pub fn score(&self, normalization: Normalization) -> f64 {
+        normalization.apply(self.total as f64, self.rounds, self.games)
+    }
+}
+
+/// Computes [`StrategyStats`] for every participant of a tournament that has been run.
+pub fn strategy_stats(game: &Tournament) -> Vec<StrategyStats> {
+    let matchups = game.matchups();
+    let mut stats: Vec<StrategyStats> = matchups
+        .iter()
+        .map(|(name, games)| StrategyStats::from_matchups(name, games, game.rounds()))
+        .collect();
+    let scores: Vec<Vec<i32>> =
+        matchups.iter().map(|(_, games)| games.iter().map(|m| m.own_score).collect()).collect();
+    for (s, intervals) in stats.iter_mut().zip(bootstrap(&scores, BOOTSTRAP_RESAMPLES)) {
+        s.mean_ci = intervals.map(|(mean_ci, _)| mean_ci);
+        s.rank_ci = intervals.map(|(_, rank_ci)| rank_ci);
+    }
+    for (s, image) in stats.iter_mut().zip(game.images()) {
+        s.image = image;
+    }
+    stats
+}
+
+/// Confidence intervals of the mean score and of the rank of a participant.
+pub type Intervals = ((f64, f64), (usize, usize));
+
+/// Resamples every participant's matchup scores with replacement and returns the 95%
+/// percentile intervals of its mean score and of its rank among the participants that played,
+/// or `None` for a participant without matchups and for everyone without resamples.
+pub fn bootstrap(scores: &[Vec<i32>], resamples: usize) -> Vec<Option<Intervals>> {
+    let mut rng = random::rng();
+    let mut means = vec![Vec::with_capacity(resamples); scores.len()];
+    let mut ranks = vec![Vec::with_capacity(resamples); scores.len()];
+    for _ in 0..resamples {
+        let sample: Vec<Option<f64>> = scores
+            .iter()
+            .map(|s| {
+                let total = (0..s.len()).map(|_| s[rng.gen_range(0..s.len())] as f64).sum::<f64>();
+                (!s.is_empty()).then(|| total / s.len() as f64)
+            })
+            .collect();
+        for (i, &m) in sample.iter().enumerate() {
+            let Some(m) = m else {
+                continue;
+            };
+            means[i].push(m);
+            ranks[i].push(1 + sample.iter().flatten().filter(|&&other| other > m).count());
+        }
+    }
+
+    let percentile = |n: usize, q: f64| ((n - 1) as f64 * q).round() as usize;
+    means
+        .into_iter()
+        .zip(ranks)
+        .map(|(mut m, mut r)| {
+            if m.is_empty() {
+                return None;
+            }
+            m.sort_by(f64::total_cmp);
+            r.sort();
+            let (lo, hi) = (percentile(m.len(), 0.025), percentile(m.len(), 0.975));
+            Some(((m[lo], m[hi]), (r[lo], r[hi])))
+        })
+        .collect()
+}
+
+/// Gini coefficient of a set of non-negative values, 0 meaning perfect equality.
+pub fn gini(values: &[f64]) -> f64 {
+    let total: f64 = values.iter().sum();
+    if values.is_empty() || total == 0.0 {
+        return 0.0;
+    }
+    let mut abs_diffs = 0.0;
+    for a in values {
+        for b in values {
+            abs_diffs += (a - b).abs();
+        }
+    }
+    abs_diffs / (2.0 * values.len() as f64 * total)
+}
+
+/// Gini coefficient of the evolving population's total payoffs. Since payoffs can be
+/// negative, every total is measured relative to the worst total attainable.
+pub fn population_gini(game: &Tournament) -> f64 {
+    let totals: Vec<f64> = game
+        .matchups()
+        .iter()
+        .skip(game.player_count())
+        .map(|(_, games)| {
+            let floor = game.worst_payoff() as f64 * game.rounds() as f64 * games.len() as f64;
+            games.iter().map(|m| m.own_score as f64).sum::<f64>() - floor
+        })
+        .collect();
+    gini(&totals)
+}
+
+/// Number of individuals carrying each genome.
+pub fn genome_counts(population: &[u8]) -> [u32; GENOME_COUNT] {
+    let mut counts = [0; GENOME_COUNT];
+    for &g in population {
+        counts[g as usize] += 1;
+    }
+    counts
+}
+
+/// Situation each genome bit responds to, in genome order.
+pub const GENE_CONTEXTS: [&str; 5] = [
+    "opening move",
+    "after CC",
+    "after CD",
+    "after DC",
+    "after DD",
+];
+
+/// Fitness of evolved individuals split by the value of each of their genome bits.
+#[derive(Clone, Default)]
+pub struct BitAssociation {
+    /// Summed fitness per bit, for a defecting (0) and cooperating (1) gene.
+    sums: [[f64; 2]; 5],
+    /// Number of individuals summed per bit and value.
+    counts: [[u32; 2]; 5],
+}
+
+impl BitAssociation {
+    /// Adds every evolved individual of a tournament that has been run, skipping those that
+    /// are not named by a memory-one genome, such as deeper genomes and custom players.
+    pub fn record(&mut self, game: &Tournament) {
+        for (name, games) in game.matchups().iter().skip(game.player_count()) {
+            let Ok(number) = name.parse() else {
+                continue;
+            };
+            let genome = number_to_genome(number);
+            let fitness: i32 = games.iter().map(|m| m.own_score).sum();
+            for (bit, &cooperates) in genome.iter().enumerate() {
+                self.sums[bit][cooperates as usize] += fitness as f64;
+                self.counts[bit][cooperates as usize] += 1;
+            }
+        }
+    }
+
+    /// Mean fitness of individuals defecting and cooperating in each context, if any were seen.
+    pub fn means(&self) -> [(Option<f64>, Option<f64>); 5] {
+        std::array::from_fn(|bit| {
+            let mean = |v: usize| {
+                (self.counts[bit][v] > 0).then(|| self.sums[bit][v] / self.counts[bit][v] as f64)
+            };
+            (mean(0), mean(1))
+        })
+    }
+}
+
+/// Number of generations the modal genome has to stay the same to count as converged.
+pub const CONVERGENCE_WINDOW: usize = 10;
+
+/// Number of distinct genomes present in a population.
+pub fn diversity(counts: &[u32; GENOME_COUNT]) -> usize {
+    counts.iter().filter(|&&n| n > 0).count()
+}
+
+/// Most common genome of a population, the lowest number on ties.
+pub fn modal_genome(counts: &[u32; GENOME_COUNT]) -> u8 {
+    let mut modal = 0;
+    for (g, &n) in counts.iter().enumerate() {
+        if n > counts[modal] {
+            modal = g;
+        }
+    }
+    modal as u8
+}
+
+/// Generation at which the population converged: either the first generation from which the
+/// modal genome never changed again for at least [`CONVERGENCE_WINDOW`] generations, or the
+/// first one whose diversity fell to `diversity_threshold`, whichever came first.
+pub fn convergence_generation(
+    frequencies: &[[u32; GENOME_COUNT]],
+    diversity_threshold: usize,
+) -> Option<usize> {
+    let last = frequencies.last()?;
+    let final_mode = modal_genome(last);
+    let stable_from = frequencies
+        .iter()
+        .rposition(|counts| modal_genome(counts) != final_mode)
+        .map_or(0, |g| g + 1);
+    let by_mode = (frequencies.len() - stable_from >= CONVERGENCE_WINDOW).then_some(stable_from);
+    let by_diversity =
+        frequencies.iter().position(|counts| diversity(counts) <= diversity_threshold);
+    match (by_mode, by_diversity) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Whether neither the best score improved nor the number of distinct genomes changed
+/// during the last `k` generations.
+pub fn has_plateaued(best_scores: &[i32], frequencies: &[[u32; GENOME_COUNT]], k: usize) -> bool {
+    if best_scores.len() <= k || frequencies.len() <= k {
+        return false;
+    }
+    let (before, window) = best_scores.split_at(best_scores.len() - k);
+    let best_before = before.iter().max().expect("window leaves earlier generations");
+    let diversities: Vec<usize> =
+        frequencies[frequencies.len() - k - 1..].iter().map(diversity).collect();
+    window.iter().all(|s| s <= best_before) && diversities.windows(2).all(|w| w[0] == w[1])
+}
+
+/// Summary of a single generation of the evolved population.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct GenerationRecord {
+    /// Score of the fittest individual, as used for selection.
+    pub best: i32,
+    /// Minimum, lower quartile, median, upper quartile and maximum of the totals.
+    pub quartiles: [f64; 5],
+    pub mean: f64,
+    /// Fraction of the population's decisions that were to cooperate.
+    pub cooperation: f64,
+    /// Fraction of pairings refused through partner choice.
+    pub refusals: f64,
+    /// Fraction of decisions forced to defection by an exhausted budget.
+    pub forced: f64,
+    pub gini: f64,
+    /// Individuals per genome at the start of the generation.
+    pub counts: [u32; GENOME_COUNT],
+    /// Matchups played by every evolved individual, which depends on the pairing.
+    pub games: usize,
+}
+
+/// Linearly interpolated quantile of sorted values, if there are any.
+fn quantile(sorted: &[f64], q: f64) -> Option<f64> {
+    let pos = q * sorted.len().checked_sub(1)? as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    Some(sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64))
+}
+
+/// Per-generation results of a run, shared by the plots and the exporters.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct StatsRecorder {
+    /// Rounds per matchup.
+    pub rounds: u32,
+    /// Number of fixed players, which come first in the tournament's results.
+    pub player_count: usize,
+    pub generations: Vec<GenerationRecord>,
+}
+
+impl StatsRecorder {
+    /// Appends the summary of a tournament played by `population`.
+    pub fn record(&mut self, game: &Tournament, best: i32, population: &[u8]) {
+        let mut totals: Vec<f64> = game
+            .matchups()
+            .iter()
+            .skip(game.player_count())
+            .map(|(_, games)| games.iter().map(|m| m.own_score as f64).sum())
+            .collect();
+        totals.sort_by(f64::total_cmp);
+        self.rounds = game.rounds();
+        self.player_count = game.player_count();
+        self.generations.push(GenerationRecord {
+            best,
+            quartiles: [0.0, 0.25, 0.5, 0.75, 1.0].map(|q| quantile(&totals, q).unwrap_or(0.0)),
+            mean: totals.iter().sum::<f64>() / totals.len().max(1) as f64,
+            cooperation: game.cooperation_rate(),
+            refusals: game.refusal_rate(),
+            forced: game.forced_defection_rate(),
+            gini: population_gini(game),
+            counts: genome_counts(population),
+            games: game
+                .matchups()
+                .iter()
+                .skip(game.player_count())
+                .map(|(_, games)| games.len())
+                .max()
+                .unwrap_or(0),
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.generations.clear();
+    }
+
+    pub fn best_scores(&self) -> Vec<i32> {
+        self.generations.iter().map(|g| g.best).collect()
+    }
+
+    pub fn frequencies(&self) -> Vec<[u32; GENOME_COUNT]> {
+        self.generations.iter().map(|g| g.counts).collect()
+    }
+
+    /// Scales a total of an evolved individual in generation `g` by `normalization`, so that
+    /// runs with different numbers of rounds or partners can be compared.
+    pub fn normalize(&self, g: &GenerationRecord, total: f64, normalization: Normalization) -> f64 {
+        normalization.apply(total, self.rounds, g.games)
+    }
+}
+
+/// Writes one CSV row per generation with the score distribution scaled by `normalization`,
+/// the cooperation rate, the payoff Gini coefficient, the number of distinct genomes and the
+/// modal genome.
+pub fn write_series(
+    path: &str,
+    recorder: &StatsRecorder,
+    normalization: Normalization,
+) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(
+        handle,
+        "generation,best_score,min,q1,median,q3,max,mean,cooperation,refusals,forced,gini,\
+         diversity,modal_genome"
+    )?;
+    for (gen, g) in recorder.generations.iter().enumerate() {
+        let scaled = |v: f64| recorder.normalize(g, v, normalization);
+        let [min, q1, median, q3, max] = g.quartiles.map(scaled);
+        writeln!(
+            handle,
+            "{gen},{},{min},{q1},{median},{q3},{max},{},{},{},{},{},{},{}",
+            scaled(g.best as f64),
+            scaled(g.mean),
+            g.cooperation,
+            g.refusals,
+            g.forced,
+            g.gini,
+            diversity(&g.counts),
+            modal_genome(&g.counts)
+        )?;
+    }
+    Ok(())
+}
+
+/// Best score of one generation over a batch of runs of the same configuration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatchGeneration {
+    /// Number of runs that reached the generation.
+    pub runs: usize,
+    pub mean: f64,
+    /// Sample variance over the runs, 0 when only one reached the generation.
+    pub variance: f64,
+    pub min: i32,
+    pub max: i32,
+}
+
+/// Aggregates the best score per generation over `runs`, each the best scores of one run. Runs
+/// stopped early or by extinction only count for the generations they reached.
+pub fn aggregate_best(runs: &[Vec<i32>]) -> Vec<BatchGeneration> {
+    let length = runs.iter().map(Vec::len).max().unwrap_or(0);
+    (0..length)
+        .map(|gen| {
+            let scores: Vec<i32> = runs.iter().filter_map(|r| r.get(gen).copied()).collect();
+            let n = scores.len() as f64;
+            let mean = scores.iter().map(|&s| s as f64).sum::<f64>() / n;
+            let squares = scores.iter().map(|&s| (s as f64 - mean).powi(2)).sum::<f64>();
+            BatchGeneration {
+                runs: scores.len(),
+                mean,
+                variance: if scores.len() > 1 {
+                    squares / (n - 1.0)
+                } else {
+                    0.0
+                },
+                min: *scores.iter().min().expect("some run reached the generation"),
+                max: *scores.iter().max().expect("some run reached the generation"),
+            }
+        })
+        .collect()
+}
+
+/// Writes one CSV row per generation with the spread of the best score over a batch of runs.
+pub fn write_batch(path: &str, batch: &[BatchGeneration]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(
+        handle,
+        "generation,runs,mean_best,variance,min_best,max_best"
+    )?;
+    for (gen, g) in batch.iter().enumerate() {
+        writeln!(
+            handle,
+            "{gen},{},{},{},{},{}",
+            g.runs, g.mean, g.variance, g.min, g.max
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the generations × genomes matrix of genome counts, one generation per row.
+pub fn write_genotypes(path: &str, frequencies: &[[u32; GENOME_COUNT]]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    let header: Vec<String> = (0..GENOME_COUNT).map(|g| format!("genome_{g}")).collect();
+    writeln!(handle, "generation,{}", header.join(","))?;
+    for (gen, counts) in frequencies.iter().enumerate() {
+        let row: Vec<String> = counts.iter().map(|c| c.to_string()).collect();
+        writeln!(handle, "{gen},{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+/// Writes `name:score:std` lines in the format read by `graph_results.py`, after a `# label`
+/// line naming the normalization of the scores, where the standard deviation is that of the
+/// normalized score as given by [`Normalization::spread`].
+pub fn write_results(
+    path: &str,
+    stats: &[StrategyStats],
+    normalization: Normalization,
+) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "# {}", normalization.label())?;
+    for s in stats {
+        let std_dev = normalization.spread(s.std_dev, s.rounds, s.games);
+        writeln!(
+            handle,
+            "{}:{}:{}",
+            s.name.replace('\n', " "),
+            s.score(normalization),
+            std_dev
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per participant with its [`StrategyStats`], scores unscaled.
+pub fn write_strategy_stats(path: &str, stats: &[StrategyStats]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(
+        handle,
+        "strategy,total,games,mean,mean_ci_low,mean_ci_high,rank_ci_best,rank_ci_worst,std_dev,\
+         median,per_round,best_opponent,best_score,worst_opponent,worst_score,wins,losses,\
+         draws,image"
+    )?;
+    for s in stats {
+        let (best, best_score) = &s.best_matchup;
+        let (worst, worst_score) = &s.worst_matchup;
+        // Intervals are left empty for participants without matchups.
+        let mean_ci = s.mean_ci.map_or(",".to_string(), |(lo, hi)| format!("{lo},{hi}"));
+        let rank_ci = s.rank_ci.map_or(",".to_string(), |(best, worst)| format!("{best},{worst}"));
+        writeln!(
+            handle,
+            "{},{},{},{},{mean_ci},{rank_ci},{},{},{},{},{best_score},{},{worst_score},{},{},{},{}",
+            s.name.replace('\n', " "),
+            s.total,
+            s.games,
+            s.mean,
+            s.std_dev,
+            s.median,
+            s.per_round,
+            best.replace('\n', " "),
+            worst.replace('\n', " "),
+            s.record.wins,
+            s.record.losses,
+            s.record.draws,
+            s.image
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the payoff sensitivity analysis as CSV.
+pub fn write_sensitivity(path: &str, rows: &[SensitivityRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "parameter,delta,winner,winner_score,cooperation")?;
+    for r in rows {
+        writeln!(
+            handle,
+            "{},{},{},{},{}",
+            r.parameter,
+            r.delta,
+            r.winner.replace('\n', " "),
+            r.winner_score,
+            r.cooperation
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the forgetting report as CSV, one row per forgetting level and player.
+pub fn write_forgetting_report(path: &str, rows: &[ForgettingRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "forgetting,strategy,rank,evolved_winner")?;
+    for r in rows {
+        for (name, rank) in &r.ranks {
+            writeln!(
+                handle,
+                "{},{},{},{}",
+                r.forgetting,
+                name.replace('\n', " "),
+                rank,
+                r.winner
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per named strategy with its rank with and without forgetting alone.
+pub fn write_solo_forgetting(path: &str, rows: &[SoloForgettingRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "strategy,recall_rank,forgetful_rank")?;
+    for r in rows {
+        writeln!(
+            handle,
+            "{},{},{}",
+            r.name.replace('\n', " "),
+            r.recall_rank,
+            r.forgetful_rank
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the noise-robustness report as CSV, one row per noise combination and player.
+pub fn write_noise_report(path: &str, rows: &[NoiseRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(
+        handle,
+        "execution_noise,perception_noise,strategy,rank,evolved_winner"
+    )?;
+    for r in rows {
+        for (name, rank) in &r.ranks {
+            writeln!(
+                handle,
+                "{},{},{},{},{}",
+                r.execution,
+                r.perception,
+                name.replace('\n', " "),
+                rank,
+                r.winner
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per fixed player with its rank in every game preset and the aggregate.
+pub fn write_meta_tournament(path: &str, rows: &[MetaRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    let games: Vec<String> =
+        GAME_PRESETS.iter().map(|(name, _)| name.to_lowercase().replace(' ', "_")).collect();
+    writeln!(handle, "strategy,{},mean_rank,worst_rank", games.join(","))?;
+    for r in rows {
+        let ranks: Vec<String> = r.ranks.iter().map(|rank| rank.to_string()).collect();
+        writeln!(
+            handle,
+            "{},{},{},{}",
+            r.name.replace('\n', " "),
+            ranks.join(","),
+            r.robustness,
+            r.worst_rank
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the ranking table of the classic round-robin tournament, best strategy first.
+pub fn write_round_robin(path: &str, rows: &[RoundRobinRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "rank,strategy,mean_score,std_dev,wins,losses")?;
+    for (rank, r) in rows.iter().enumerate() {
+        writeln!(
+            handle,
+            "{},{},{},{},{},{}",
+            rank + 1,
+            r.name.replace('\n', " "),
+            r.score,
+            r.spread,
+            r.wins,
+            r.losses
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per match of the single-elimination bracket, round by round.
+pub fn write_bracket(path: &str, rounds: &[Vec<BracketMatch>]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "round,first,second,first_score,second_score,winner")?;
+    for (round, matches) in rounds.iter().enumerate() {
+        for m in matches {
+            let second = m.second.as_deref().unwrap_or("bye");
+            let winner = if m.first_wins { &m.first } else { second };
+            writeln!(
+                handle,
+                "{},{},{},{},{},{}",
+                round + 1,
+                m.first.replace('\n', " "),
+                second.replace('\n', " "),
+                m.scores.0,
+                m.scores.1,
+                winner.replace('\n', " ")
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per generation with the fitness and cooperation of every labelled
+/// population evolved side by side.
+pub fn write_environments(
+    path: &str,
+    populations: &[(String, Vec<EnvironmentRow>)],
+) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    let columns: Vec<String> = populations
+        .iter()
+        .map(|(label, _)| {
+            let label = label.to_lowercase().replace(' ', "_");
+            format!("{label}_fitness,{label}_cooperation")
+        })
+        .collect();
+    writeln!(handle, "generation,{}", columns.join(","))?;
+    let generations = populations.iter().map(|(_, rows)| rows.len()).max().unwrap_or(0);
+    for gen in 0..generations {
+        let cells: Vec<String> = populations
+            .iter()
+            .map(|(_, rows)| match rows.get(gen) {
+                Some(r) => format!("{},{}", r.fitness, r.cooperation),
+                None => ",".to_string(),
+            })
+            .collect();
+        writeln!(handle, "{gen},{}", cells.join(","))?;
+    }
+    Ok(())
+}
+
+/// Writes the scores of every named strategy next to those of its noisy variant.
+pub fn write_variants(path: &str, rows: &[VariantRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "strategy,clean,noisy,loss")?;
+    for r in rows {
+        writeln!(
+            handle,
+            "{},{},{},{}",
+            r.name.replace('\n', " "),
+            r.clean,
+            r.noisy,
+            r.clean - r.noisy
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per generation of the mixed-opening experiment.
+pub fn write_opening_report(path: &str, rows: &[OpeningRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "generation,opening,mixed,cooperation")?;
+    for (gen, r) in rows.iter().enumerate() {
+        writeln!(handle, "{gen},{},{},{}", r.opening, r.mixed, r.cooperation)?;
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per generation of the green-beard experiment.
+pub fn write_tag_report(path: &str, rows: &[TagRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "generation,green_beards,majority_tag,cooperation")?;
+    for (gen, r) in rows.iter().enumerate() {
+        writeln!(
+            handle,
+            "{gen},{},{},{}",
+            r.green_beards, r.majority_tag, r.cooperation
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per generation of the kin-selection experiment.
+pub fn write_kin_report(path: &str, rows: &[KinRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "generation,kin_pairs,lineages,cooperation")?;
+    for (gen, r) in rows.iter().enumerate() {
+        writeln!(
+            handle,
+            "{gen},{},{},{}",
+            r.kin_pairs, r.lineages, r.cooperation
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per generation of the cheap-talk experiment.
+pub fn write_talk_report(path: &str, rows: &[TalkRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(
+        handle,
+        "generation,cooperative_signals,trusting,responsive,cooperation"
+    )?;
+    for (gen, r) in rows.iter().enumerate() {
+        writeln!(
+            handle,
+            "{gen},{},{},{},{}",
+            r.cooperative_signals, r.trusting, r.responsive, r.cooperation
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per generation of the contract experiment.
+pub fn write_contract_report(path: &str, rows: &[ContractRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "generation,signers,breaches,cooperation")?;
+    for (gen, r) in rows.iter().enumerate() {
+        writeln!(
+            handle,
+            "{gen},{},{},{}",
+            r.signers, r.breaches, r.cooperation
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per generation of the memory-depth experiment.
+pub fn write_depth_report(path: &str, rows: &[DepthRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "generation,best_per_round,cooperation,winner")?;
+    for (gen, r) in rows.iter().enumerate() {
+        writeln!(handle, "{gen},{},{},{}", r.best, r.cooperation, r.winner)?;
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per generation of the self-adaptive mutation experiment.
+pub fn write_adaptive_report(path: &str, rows: &[AdaptiveRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "generation,mutation_rate,consensus,cooperation")?;
+    for (gen, r) in rows.iter().enumerate() {
+        writeln!(
+            handle,
+            "{gen},{},{},{}",
+            r.mutation_rate, r.consensus, r.cooperation
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes one CSV row per generation of the punishment experiment.
+pub fn write_punish_report(path: &str, rows: &[PunishRow]) -> io::Result<()> {
+    let mut handle = File::create(path)?;
+    writeln!(handle, "generation,punishers,cooperation")?;
+    for (gen, r) in rows.iter().enumerate() {
+        writeln!(handle, "{gen},{},{}", r.punishers, r.cooperation)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizing_without_games_or_rounds_gives_zero() {
+        for normalization in Normalization::ALL {
+            assert_eq!(normalization.apply(0.0, 0, 0), 0.0);
+            assert_eq!(normalization.spread(0.0, 0, 0), 0.0);
+        }
+    }
+
+    #[test]
+    fn recording_an_empty_population_reports_zero_scores() {
+        let mut game =
+            Tournament::with_custom_players(10, crate::prisoners_dillemma_rules, Box::new([]), &[]);
+        game.run();
+        let mut recorder = StatsRecorder::default();
+        recorder.record(&game, 0, &[]);
+        assert_eq!(recorder.generations[0].quartiles, [0.0; 5]);
+        assert_eq!(recorder.generations[0].mean, 0.0);
+    }
+
+    #[test]
+    fn batch_counts_only_runs_that_reached_a_generation() {
+        let batch = aggregate_best(&[vec![10, 20, 30], vec![14, 22]]);
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch[0].mean, 12.0);
+        assert_eq!(batch[0].variance, 8.0);
+        assert_eq!((batch[1].min, batch[1].max), (20, 22));
+        assert_eq!((batch[2].runs, batch[2].variance), (1, 0.0));
+    }
+
+    #[test]
+    fn bit_association_skips_genomes_deeper_than_one_round() {
+        let population = [crate::random_genome(2), crate::random_genome(2)];
+        let mut game =
+            Tournament::with_genomes(10, crate::prisoners_dillemma_rules, 2, &population, &[]);
+        game.run();
+        let mut genes = BitAssociation::default();
+        genes.record(&game);
+        assert_eq!(genes.means(), [(None, None); 5]);
+    }
+
+    #[test]
+    fn bootstrap_leaves_out_who_did_not_play() {
+        let scores = [vec![3, 3, 3], Vec::new(), vec![1, 2]];
+        assert_eq!(bootstrap(&scores, 0), [None; 3]);
+        let intervals = bootstrap(&scores, 100);
+        assert_eq!(intervals[0], Some(((3.0, 3.0), (1, 1))));
+        assert_eq!(intervals[1], None);
+        assert_eq!(intervals[2].map(|(_, rank_ci)| rank_ci), Some((2, 2)));
+    }
+
+    #[test]
+    fn totals_spread_by_the_square_root_of_their_games() {
+        // 4 matchups of 10 rounds whose scores spread by 2 around their mean.
+        assert_eq!(Normalization::Raw.spread(2.0, 10, 4), 4.0);
+        assert_eq!(Normalization::PerRound.spread(2.0, 10, 4), 0.4);
+        assert_eq!(Normalization::PerOpponent.spread(2.0, 10, 4), 2.0);
+        assert_eq!(Normalization::PerRoundPerOpponent.spread(2.0, 10, 4), 0.2);
+    }
+}
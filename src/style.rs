@@ -0,0 +1,116 @@
+use egui::Color32;
+use egui_plot::{BarChart, Line, LineStyle};
+
+/// Colours plot series and heatmaps are drawn in.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Palette {
+    /// Each plot's own colours.
+    Standard,
+    /// The Okabe–Ito palette, which stays distinguishable under the common forms of colour
+    /// blindness.
+    OkabeIto,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 2] = [Palette::Standard, Palette::OkabeIto];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Palette::Standard => "Standard",
+            Palette::OkabeIto => "Okabe-Ito",
+        }
+    }
+}
+
+/// Okabe–Ito colours, without black, which is invisible on the dark background.
+const OKABE_ITO: [Color32; 7] = [
+    Color32::from_rgb(230, 159, 0),
+    Color32::from_rgb(86, 180, 233),
+    Color32::from_rgb(0, 158, 115),
+    Color32::from_rgb(240, 228, 66),
+    Color32::from_rgb(0, 114, 178),
+    Color32::from_rgb(213, 94, 0),
+    Color32::from_rgb(204, 121, 167),
+];
+
+/// Ends of the colour-blind safe heatmap scale, whose lightness rises steadily so that it
+/// also reads in grayscale.
+const HEAT_LOW: Color32 = Color32::from_rgb(0, 32, 77);
+const HEAT_HIGH: Color32 = Color32::from_rgb(255, 233, 69);
+
+/// How plot series are told apart.
+#[derive(Clone, Copy)]
+pub struct PlotStyle {
+    pub palette: Palette,
+    /// Whether series also differ in line style, for grayscale prints.
+    pub patterned: bool,
+}
+
+impl PlotStyle {
+    /// Styles the `k`-th series of a plot.
+    pub fn line(self, line: Line, k: usize) -> Line {
+        let line = match self.palette {
+            Palette::Standard => line,
+            Palette::OkabeIto => line.color(OKABE_ITO[k % OKABE_ITO.len()]),
+        };
+        if self.patterned {
+            line.style(self.line_style(k))
+        } else {
+            line
+        }
+    }
+
+    /// Colour of the `k`-th series of a plot, whose own colour is `standard`.
+    pub fn color(self, k: usize, standard: Color32) -> Color32 {
+        match self.palette {
+            Palette::Standard => standard,
+            Palette::OkabeIto => OKABE_ITO[k % OKABE_ITO.len()],
+        }
+    }
+
+    /// Line style of the `k`-th series of a plot.
+    pub fn line_style(self, k: usize) -> LineStyle {
+        match k % 4 {
+            _ if !self.patterned => LineStyle::Solid,
+            0 => LineStyle::Solid,
+            1 => LineStyle::dashed_loose(),
+            2 => LineStyle::dotted_loose(),
+            _ => LineStyle::dashed_dense(),
+        }
+    }
+
+    /// Colours the `k`-th bar chart of a plot, such as a layer of a stacked chart.
+    pub fn bars(self, chart: BarChart, k: usize) -> BarChart {
+        match self.palette {
+            Palette::Standard => chart,
+            Palette::OkabeIto => chart.color(OKABE_ITO[k % OKABE_ITO.len()]),
+        }
+    }
+
+    /// Colour of a heatmap cell at `level`, between 0 and 1.
+    pub fn heat(self, level: f64) -> Color32 {
+        match self.palette {
+            Palette::Standard => {
+                let shade = (255.0 * level) as u8;
+                Color32::from_rgb(shade, shade / 2, 255 - shade)
+            }
+            Palette::OkabeIto => {
+                let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * level) as u8;
+                Color32::from_rgb(
+                    mix(HEAT_LOW.r(), HEAT_HIGH.r()),
+                    mix(HEAT_LOW.g(), HEAT_HIGH.g()),
+                    mix(HEAT_LOW.b(), HEAT_HIGH.b()),
+                )
+            }
+        }
+    }
+
+    /// Colour and width of highlighted edges of a graph, such as those of a cycle.
+    pub fn highlight(self) -> (Color32, f32) {
+        let width = if self.patterned { 2.5 } else { 1.0 };
+        match self.palette {
+            Palette::Standard => (Color32::RED, width),
+            Palette::OkabeIto => (OKABE_ITO[5], width),
+        }
+    }
+}